@@ -0,0 +1,77 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+// Imported at the root so the shared fixtures' `crate::` paths resolve in this crate too.
+use libpsi_core::{ColumnVector, Complex, Matrix, QuantumState, Vector};
+use rand::{rngs::StdRng, SeedableRng};
+use testing::{naive_dot, random_matrix};
+
+#[allow(dead_code)]
+#[path = "../src/testing.rs"]
+mod testing;
+
+/// The original element-by-element `Matrix::kronecker`.
+fn naive_kronecker(a: &Matrix<Complex<f64>>, b: &Matrix<Complex<f64>>) -> Matrix<Complex<f64>> {
+    let mut result = Matrix::zeros(a.rows * b.rows, a.cols * b.cols);
+    for i in 0..a.rows {
+        for j in 0..a.cols {
+            let value = a.get(i, j);
+            for k in 0..b.rows {
+                for l in 0..b.cols {
+                    result.set(i * b.rows + k, j * b.cols + l, value * b.get(k, l));
+                }
+            }
+        }
+    }
+    result
+}
+
+fn dot(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut group = c.benchmark_group("dot");
+    group.sample_size(10);
+
+    for n in [64, 256, 1024] {
+        let a = random_matrix(&mut rng, n, n);
+        let b = random_matrix(&mut rng, n, n);
+        let mut output = Matrix::zeros(n, n);
+
+        group.bench_with_input(BenchmarkId::new("naive", n), &n, |bencher, _| {
+            bencher.iter(|| naive_dot(black_box(&a), black_box(&b)))
+        });
+        group.bench_with_input(BenchmarkId::new("blocked", n), &n, |bencher, _| {
+            bencher.iter(|| black_box(&a).dot_unchecked(black_box(&b)))
+        });
+        group.bench_with_input(BenchmarkId::new("blocked_into", n), &n, |bencher, _| {
+            bencher.iter(|| black_box(&a).dot_into(black_box(&b), &mut output))
+        });
+    }
+
+    group.finish();
+}
+
+fn kronecker(c: &mut Criterion) {
+    let mut rng = StdRng::seed_from_u64(1);
+    let mut group = c.benchmark_group("kronecker");
+    group.sample_size(10);
+
+    // The product of two `sqrt(n) x sqrt(n)` factors is `n x n`.
+    for (n, factor) in [(64, 8), (256, 16), (1024, 32)] {
+        let a = random_matrix(&mut rng, factor, factor);
+        let b = random_matrix(&mut rng, factor, factor);
+        let mut output = Matrix::zeros(n, n);
+
+        group.bench_with_input(BenchmarkId::new("naive", n), &n, |bencher, _| {
+            bencher.iter(|| naive_kronecker(black_box(&a), black_box(&b)))
+        });
+        group.bench_with_input(BenchmarkId::new("kronecker", n), &n, |bencher, _| {
+            bencher.iter(|| black_box(&a).kronecker(black_box(&b)))
+        });
+        group.bench_with_input(BenchmarkId::new("kronecker_into", n), &n, |bencher, _| {
+            bencher.iter(|| black_box(&a).kronecker_into(black_box(&b), &mut output))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, dot, kronecker);
+criterion_main!(benches);
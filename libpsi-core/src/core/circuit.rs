@@ -1,19 +1,694 @@
-use super::{ClassicalRegister, QuantumRegister};
+use super::{
+    gates, ClassicalRegister, ClbitRef, CompositeGate, Instruction, QuantumGate, QuantumRegister,
+    QubitRef,
+};
+use crate::{Complex, Float};
+use core::{fmt, ops};
 
-#[allow(unused)]
-pub struct QuantumCircuit<'a> {
-    quantum_registers: &'a [QuantumRegister<'a>],
-    classical_registers: &'a [ClassicalRegister<'a>],
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CircuitError {
+    UnknownRegister(String),
+    QubitOutOfRange(QubitRef),
+    BitOutOfRange(ClbitRef),
+    /// The same qubit appears twice among the operands of one instruction.
+    DuplicateQubit(QubitRef),
+    /// The gate acts on `expected` qubits but was given `actual` targets.
+    ArityMismatch {
+        gate: String,
+        expected: usize,
+        actual: usize,
+    },
+    /// The gate acts on too many qubits for its matrix to be built.
+    TooManyQubits {
+        gate: String,
+        qubits: usize,
+    },
+    /// The condition value does not fit in the classical register.
+    ConditionOutOfRange {
+        register: usize,
+        value: u64,
+    },
 }
 
-impl<'a> QuantumCircuit<'a> {
+impl fmt::Display for CircuitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CircuitError::UnknownRegister(name) => write!(f, "no register named '{}'", name),
+            CircuitError::QubitOutOfRange(qubit) => write!(f, "qubit {} is out of range", qubit),
+            CircuitError::BitOutOfRange(bit) => write!(f, "classical bit {} is out of range", bit),
+            CircuitError::DuplicateQubit(qubit) => {
+                write!(f, "qubit {} is used twice by one instruction", qubit)
+            }
+            CircuitError::ArityMismatch {
+                gate,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "gate {} acts on {} qubits but was given {}",
+                gate, expected, actual
+            ),
+            CircuitError::TooManyQubits { gate, qubits } => write!(
+                f,
+                "gate {} on {} qubits is too large for a matrix",
+                gate, qubits
+            ),
+            CircuitError::ConditionOutOfRange { register, value } => write!(
+                f,
+                "value {} does not fit in classical register {}",
+                value, register
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CircuitError {}
+
+/// A quantum register as a circuit sees it: a name and the names of its qubits. It holds no
+/// state, so a register of any width can be described; [`RegisterDescriptor::allocate`] builds
+/// its `2^n` amplitudes only when the circuit is run.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RegisterDescriptor {
+    name: String,
+    qubits: Vec<String>,
+}
+
+impl RegisterDescriptor {
+    pub fn new(name: impl Into<String>, names: &[impl AsRef<str>]) -> RegisterDescriptor {
+        RegisterDescriptor {
+            name: name.into(),
+            qubits: names.iter().map(|name| name.as_ref().to_string()).collect(),
+        }
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn qubit_names(&self) -> &[String] {
+        &self.qubits
+    }
+
+    /// Number of qubits.
+    pub fn len(&self) -> usize {
+        self.qubits.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.qubits.is_empty()
+    }
+
+    /// A register of these qubits, all in `|0⟩`.
+    pub fn allocate<T: Float>(&self) -> QuantumRegister<T>
+    where
+        Complex<T>: Float,
+    {
+        QuantumRegister::new(self.name.clone(), &self.qubits)
+    }
+}
+
+impl<T: Float> From<&QuantumRegister<T>> for RegisterDescriptor
+where
+    Complex<T>: Float,
+{
+    fn from(register: &QuantumRegister<T>) -> RegisterDescriptor {
+        RegisterDescriptor {
+            name: register.get_name().to_string(),
+            qubits: register
+                .get_bits()
+                .iter()
+                .map(|qubit| qubit.get_name().to_string())
+                .collect(),
+        }
+    }
+}
+
+/// The name of qubit `index`.
+impl ops::Index<usize> for RegisterDescriptor {
+    type Output = str;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.qubits[index]
+    }
+}
+
+/// A circuit that owns its registers, so it can be assembled incrementally (e.g. by a parser or
+/// a generator) and returned from functions. Quantum registers are held as
+/// [`RegisterDescriptor`]s, without simulation state.
+///
+/// Instructions are validated as they are added, and the builder methods chain:
+///
+/// ```
+/// # use libpsi_core::*;
+/// # fn main() -> Result<(), CircuitError> {
+/// let mut circuit = QuantumCircuit::new(
+///     vec![RegisterDescriptor::new("q", &["q0", "q1"])],
+///     vec![ClassicalRegister::new("c", &["c0", "c1"])],
+/// );
+/// let (q0, q1) = (circuit.qubit("q", 0)?, circuit.qubit("q", 1)?);
+/// let c1 = circuit.clbit("c", 1)?;
+///
+/// circuit.h(q0)?.cx(q0, q1)?.measure(q1, c1)?;
+/// assert!(circuit.cx(q1, q1).is_err());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "RawQuantumCircuit"))]
+pub struct QuantumCircuit {
+    quantum_registers: Vec<RegisterDescriptor>,
+    classical_registers: Vec<ClassicalRegister>,
+    instructions: Vec<Instruction>,
+}
+
+/// Unchecked form of [`QuantumCircuit`], whose instructions are validated like those added
+/// through the builder before deserialization accepts them.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct RawQuantumCircuit {
+    quantum_registers: Vec<RegisterDescriptor>,
+    classical_registers: Vec<ClassicalRegister>,
+    instructions: Vec<Instruction>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<RawQuantumCircuit> for QuantumCircuit {
+    type Error = CircuitError;
+
+    fn try_from(raw: RawQuantumCircuit) -> Result<Self, Self::Error> {
+        let mut circuit = QuantumCircuit::new(raw.quantum_registers, raw.classical_registers);
+        for instruction in raw.instructions {
+            circuit.push(instruction)?;
+        }
+        Ok(circuit)
+    }
+}
+
+impl QuantumCircuit {
     pub fn new(
-        quantum_registers: &'a [QuantumRegister<'a>],
-        classical_registers: &'a [ClassicalRegister<'a>],
-    ) -> QuantumCircuit<'a> {
+        quantum_registers: Vec<RegisterDescriptor>,
+        classical_registers: Vec<ClassicalRegister>,
+    ) -> QuantumCircuit {
         QuantumCircuit {
             quantum_registers,
             classical_registers,
+            instructions: Vec::new(),
+        }
+    }
+
+    pub fn add_quantum_register(&mut self, register: RegisterDescriptor) -> &mut QuantumCircuit {
+        self.quantum_registers.push(register);
+        self
+    }
+
+    pub fn add_classical_register(&mut self, register: ClassicalRegister) -> &mut QuantumCircuit {
+        self.classical_registers.push(register);
+        self
+    }
+
+    pub fn quantum_registers(&self) -> &[RegisterDescriptor] {
+        &self.quantum_registers
+    }
+
+    pub fn classical_registers(&self) -> &[ClassicalRegister] {
+        &self.classical_registers
+    }
+
+    pub fn quantum_register(&self, name: &str) -> Option<&RegisterDescriptor> {
+        self.quantum_registers
+            .iter()
+            .find(|register| register.get_name() == name)
+    }
+
+    pub fn classical_register(&self, name: &str) -> Option<&ClassicalRegister> {
+        self.classical_registers
+            .iter()
+            .find(|register| register.get_name() == name)
+    }
+
+    pub fn instructions(&self) -> &[Instruction] {
+        &self.instructions
+    }
+
+    /// Resolves qubit `index` of the quantum register called `register`.
+    pub fn qubit(&self, register: &str, index: usize) -> Result<QubitRef, CircuitError> {
+        let position = self
+            .quantum_registers
+            .iter()
+            .position(|candidate| candidate.get_name() == register)
+            .ok_or_else(|| CircuitError::UnknownRegister(register.to_string()))?;
+
+        let qubit = QubitRef::new(position, index);
+        self.check_qubit(qubit)?;
+        Ok(qubit)
+    }
+
+    /// Resolves bit `index` of the classical register called `register`.
+    pub fn clbit(&self, register: &str, index: usize) -> Result<ClbitRef, CircuitError> {
+        let position = self
+            .classical_registers
+            .iter()
+            .position(|candidate| candidate.get_name() == register)
+            .ok_or_else(|| CircuitError::UnknownRegister(register.to_string()))?;
+
+        let bit = ClbitRef::new(position, index);
+        self.check_bit(bit)?;
+        Ok(bit)
+    }
+
+    /// Validates and appends an instruction.
+    pub fn push(&mut self, instruction: Instruction) -> Result<&mut QuantumCircuit, CircuitError> {
+        self.validate(&instruction)?;
+        self.instructions.push(instruction);
+        Ok(self)
+    }
+
+    pub fn gate(
+        &mut self,
+        gate: QuantumGate,
+        targets: &[QubitRef],
+    ) -> Result<&mut QuantumCircuit, CircuitError> {
+        self.controlled_gate(gate, &[], targets)
+    }
+
+    pub fn controlled_gate(
+        &mut self,
+        gate: QuantumGate,
+        controls: &[QubitRef],
+        targets: &[QubitRef],
+    ) -> Result<&mut QuantumCircuit, CircuitError> {
+        self.push(Instruction::Gate {
+            gate,
+            controls: controls.to_vec(),
+            targets: targets.to_vec(),
+        })
+    }
+
+    /// Adds a composite gate as a single opaque gate on `qubits`. Its matrix is only built once
+    /// the operands are valid.
+    pub fn composite_gate(
+        &mut self,
+        gate: &CompositeGate,
+        qubits: &[QubitRef],
+    ) -> Result<&mut QuantumCircuit, CircuitError> {
+        if qubits.len() != gate.qubits() {
+            return Err(CircuitError::ArityMismatch {
+                gate: gate.name().to_string(),
+                expected: gate.qubits(),
+                actual: qubits.len(),
+            });
+        }
+        self.validate(&Instruction::Barrier(qubits.to_vec()))?;
+
+        let opaque = gate.to_gate().map_err(|_| CircuitError::TooManyQubits {
+            gate: gate.name().to_string(),
+            qubits: gate.qubits(),
+        })?;
+        self.gate(opaque, qubits)
+    }
+
+    /// Adds the body of a composite gate, mapped onto `qubits`. Nothing is added unless every
+    /// body instruction is valid.
+    pub fn expand_composite(
+        &mut self,
+        gate: &CompositeGate,
+        qubits: &[QubitRef],
+    ) -> Result<&mut QuantumCircuit, CircuitError> {
+        self.validate(&Instruction::Barrier(qubits.to_vec()))?;
+
+        let body = gate.expand(qubits)?;
+        for instruction in &body {
+            self.validate(instruction)?;
+        }
+        self.instructions.extend(body);
+        Ok(self)
+    }
+
+    pub fn h(&mut self, qubit: QubitRef) -> Result<&mut QuantumCircuit, CircuitError> {
+        self.gate(gates::HADAMARD.clone(), &[qubit])
+    }
+
+    pub fn x(&mut self, qubit: QubitRef) -> Result<&mut QuantumCircuit, CircuitError> {
+        self.gate(gates::PAULI_X.clone(), &[qubit])
+    }
+
+    pub fn y(&mut self, qubit: QubitRef) -> Result<&mut QuantumCircuit, CircuitError> {
+        self.gate(gates::PAULI_Y.clone(), &[qubit])
+    }
+
+    pub fn z(&mut self, qubit: QubitRef) -> Result<&mut QuantumCircuit, CircuitError> {
+        self.gate(gates::PAULI_Z.clone(), &[qubit])
+    }
+
+    /// Controlled X, recorded as an X gate on `target` with one control.
+    pub fn cx(
+        &mut self,
+        control: QubitRef,
+        target: QubitRef,
+    ) -> Result<&mut QuantumCircuit, CircuitError> {
+        self.controlled_gate(gates::PAULI_X.clone(), &[control], &[target])
+    }
+
+    pub fn measure(
+        &mut self,
+        qubit: QubitRef,
+        bit: ClbitRef,
+    ) -> Result<&mut QuantumCircuit, CircuitError> {
+        self.push(Instruction::Measure { qubit, bit })
+    }
+
+    pub fn reset(&mut self, qubit: QubitRef) -> Result<&mut QuantumCircuit, CircuitError> {
+        self.push(Instruction::Reset(qubit))
+    }
+
+    pub fn barrier(&mut self, qubits: &[QubitRef]) -> Result<&mut QuantumCircuit, CircuitError> {
+        self.push(Instruction::Barrier(qubits.to_vec()))
+    }
+
+    /// Appends `instruction`, to be run only when the classical register called `register`
+    /// holds `value`.
+    pub fn conditional(
+        &mut self,
+        register: &str,
+        value: u64,
+        instruction: Instruction,
+    ) -> Result<&mut QuantumCircuit, CircuitError> {
+        let register = self
+            .classical_registers
+            .iter()
+            .position(|candidate| candidate.get_name() == register)
+            .ok_or_else(|| CircuitError::UnknownRegister(register.to_string()))?;
+
+        self.push(Instruction::Conditional {
+            register,
+            value,
+            instruction: Box::new(instruction),
+        })
+    }
+
+    fn check_qubit(&self, qubit: QubitRef) -> Result<(), CircuitError> {
+        match self.quantum_registers.get(qubit.register) {
+            Some(register) if qubit.index < register.len() => Ok(()),
+            _ => Err(CircuitError::QubitOutOfRange(qubit)),
+        }
+    }
+
+    fn check_bit(&self, bit: ClbitRef) -> Result<(), CircuitError> {
+        match self.classical_registers.get(bit.register) {
+            Some(register) if bit.index < register.len() => Ok(()),
+            _ => Err(CircuitError::BitOutOfRange(bit)),
+        }
+    }
+
+    fn validate(&self, instruction: &Instruction) -> Result<(), CircuitError> {
+        check_operands(instruction, |qubit| self.check_qubit(qubit))?;
+
+        match instruction {
+            Instruction::Measure { bit, .. } => self.check_bit(*bit)?,
+            Instruction::Conditional {
+                register,
+                value,
+                instruction,
+            } => {
+                let bits = self
+                    .classical_registers
+                    .get(*register)
+                    .ok_or(CircuitError::BitOutOfRange(ClbitRef::new(*register, 0)))?
+                    .len();
+                if bits < u64::BITS as usize && *value >> bits != 0 {
+                    return Err(CircuitError::ConditionOutOfRange {
+                        register: *register,
+                        value: *value,
+                    });
+                }
+                self.validate(instruction)?;
+            }
+            Instruction::Gate { .. } | Instruction::Reset(_) | Instruction::Barrier(_) => {}
         }
+        Ok(())
+    }
+}
+
+/// Checks every qubit of `instruction` with `check_qubit`, that no qubit repeats, and that a gate
+/// is given as many targets as it acts on.
+pub(crate) fn check_operands(
+    instruction: &Instruction,
+    check_qubit: impl Fn(QubitRef) -> Result<(), CircuitError>,
+) -> Result<(), CircuitError> {
+    let mut qubits = instruction.qubits();
+    for qubit in &qubits {
+        check_qubit(*qubit)?;
+    }
+    qubits.sort_unstable();
+    if let Some(pair) = qubits.windows(2).find(|pair| pair[0] == pair[1]) {
+        return Err(CircuitError::DuplicateQubit(pair[0]));
+    }
+
+    if let Instruction::Gate { gate, targets, .. } = instruction {
+        let dimension = gate.matrix.rows;
+        let expected = u32::try_from(targets.len())
+            .ok()
+            .and_then(|count| 1usize.checked_shl(count));
+        if gate.matrix.cols != dimension || expected != Some(dimension) {
+            return Err(CircuitError::ArityMismatch {
+                gate: gate.name.to_string(),
+                expected: gate.qubits(),
+                actual: targets.len(),
+            });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{QuantumBit, QuantumState, Vector};
+
+    #[cfg(feature = "serde")]
+    fn circuit() -> QuantumCircuit {
+        QuantumCircuit::new(
+            vec![
+                RegisterDescriptor::new("qr0", &["a", "b\"escaped\""]),
+                RegisterDescriptor::new("qr1", &["k0"]),
+            ],
+            vec![ClassicalRegister::new("cr0", &["c0", "c1"])],
+        )
+    }
+
+    #[cfg(feature = "serde")]
+    fn assert_same(left: &QuantumCircuit, right: &QuantumCircuit) {
+        assert_eq!(
+            serde_json::to_string(left).unwrap(),
+            serde_json::to_string(right).unwrap()
+        );
+        let register = &right.quantum_registers()[0];
+        assert_eq!(register.get_name(), "qr0");
+        assert_eq!(&register[1], "b\"escaped\"");
+        assert_eq!(right.classical_registers()[0][1].get_name(), "c1");
+    }
+
+    /// Registers named from runtime data, as a parser would produce them.
+    fn generated(registers: usize) -> QuantumCircuit {
+        let mut circuit = QuantumCircuit::default();
+        for i in 0..registers {
+            let names: Vec<String> = (0..=i).map(|bit| format!("q{}_{}", i, bit)).collect();
+            circuit.add_quantum_register(RegisterDescriptor::new(format!("qr{}", i), &names));
+        }
+        circuit.add_classical_register(ClassicalRegister::new("c", &["c0"]));
+        circuit
+    }
+
+    #[test]
+    fn circuits_own_generated_registers() {
+        let circuit = generated(3);
+        assert_eq!(circuit.quantum_registers().len(), 3);
+
+        let register = circuit.quantum_register("qr2").unwrap();
+        assert_eq!(register.len(), 3);
+        assert_eq!(&register[2], "q2_2");
+        assert_eq!(register.allocate::<f64>().get_state().size(), 8);
+        assert_eq!(circuit.classical_register("c").unwrap()[0].get_name(), "c0");
+        assert!(circuit.quantum_register("qr3").is_none());
+    }
+
+    #[test]
+    fn wide_registers_are_described_without_state() -> Result<(), CircuitError> {
+        let names: Vec<String> = (0..40).map(|i| format!("q{}", i)).collect();
+        let mut circuit = QuantumCircuit::new(vec![RegisterDescriptor::new("q", &names)], vec![]);
+        let (first, last) = (circuit.qubit("q", 0)?, circuit.qubit("q", 39)?);
+        circuit.h(first)?.cx(first, last)?;
+
+        assert_eq!(circuit.quantum_registers()[0].len(), 40);
+        assert_eq!(circuit.instructions().len(), 2);
+        assert!(circuit.qubit("q", 40).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn descriptors_of_existing_registers_keep_their_names() {
+        let mut bits = [
+            QuantumBit::new("a", QuantumState::state_1()),
+            QuantumBit::new("b", QuantumState::state_0()),
+        ];
+        let register: QuantumRegister = QuantumRegister::from("r", &mut bits);
+        let descriptor = RegisterDescriptor::from(&register);
+        assert_eq!(descriptor, RegisterDescriptor::new("r", &["a", "b"]));
+
+        // Allocating starts from |00⟩, not from the state of the described register.
+        let allocated: QuantumRegister = descriptor.allocate();
+        assert_eq!(allocated.get_name(), "r");
+        assert_eq!(allocated.get_state().get(0).real, 1.0);
+    }
+
+    #[test]
+    fn long_target_lists_are_arity_errors() {
+        let mut circuit = QuantumCircuit::default();
+        for register in 0..8 {
+            circuit
+                .add_quantum_register(RegisterDescriptor::new(format!("q{}", register), &["q"; 8]));
+        }
+        let targets: Vec<QubitRef> = (0..64)
+            .map(|qubit| QubitRef::new(qubit / 8, qubit % 8))
+            .collect();
+
+        assert_eq!(
+            circuit.gate(gates::HADAMARD.clone(), &targets).err(),
+            Some(CircuitError::ArityMismatch {
+                gate: "h".to_string(),
+                expected: 1,
+                actual: 64
+            })
+        );
+    }
+
+    fn bell() -> QuantumCircuit {
+        QuantumCircuit::new(
+            vec![RegisterDescriptor::new("q", &["q0", "q1"])],
+            vec![ClassicalRegister::new("c", &["c0", "c1"])],
+        )
+    }
+
+    #[test]
+    fn builder_records_instructions_in_order() -> Result<(), CircuitError> {
+        let mut circuit = bell();
+        let (q0, q1) = (circuit.qubit("q", 0)?, circuit.qubit("q", 1)?);
+        let (c0, c1) = (circuit.clbit("c", 0)?, circuit.clbit("c", 1)?);
+
+        circuit
+            .h(q0)?
+            .cx(q0, q1)?
+            .barrier(&[q0, q1])?
+            .measure(q0, c0)?
+            .measure(q1, c1)?
+            .conditional("c", 0b11, Instruction::Reset(q1))?;
+
+        let instructions = circuit.instructions();
+        assert_eq!(instructions.len(), 6);
+        assert!(matches!(
+            &instructions[1],
+            Instruction::Gate { gate, controls, targets }
+                if gate.name == "x" && controls == &[q0] && targets == &[q1]
+        ));
+        assert!(matches!(
+            instructions[3],
+            Instruction::Measure { qubit, bit } if qubit == q0 && bit == c0
+        ));
+        assert_eq!(instructions[5].qubits(), vec![q1]);
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_operands_are_rejected() {
+        let mut circuit = bell();
+        let q0 = QubitRef::new(0, 0);
+
+        assert_eq!(
+            circuit.qubit("r", 0),
+            Err(CircuitError::UnknownRegister("r".to_string()))
+        );
+        assert_eq!(
+            circuit.qubit("q", 2),
+            Err(CircuitError::QubitOutOfRange(QubitRef::new(0, 2)))
+        );
+        assert_eq!(
+            circuit.h(QubitRef::new(1, 0)).err(),
+            Some(CircuitError::QubitOutOfRange(QubitRef::new(1, 0)))
+        );
+        assert_eq!(
+            circuit.cx(q0, q0).err(),
+            Some(CircuitError::DuplicateQubit(q0))
+        );
+        assert_eq!(
+            circuit.barrier(&[q0, QubitRef::new(0, 1), q0]).err(),
+            Some(CircuitError::DuplicateQubit(q0))
+        );
+        assert_eq!(
+            circuit.measure(q0, ClbitRef::new(0, 5)).err(),
+            Some(CircuitError::BitOutOfRange(ClbitRef::new(0, 5)))
+        );
+        assert!(matches!(
+            circuit.gate(gates::CNOT.clone(), &[q0]),
+            Err(CircuitError::ArityMismatch {
+                expected: 2,
+                actual: 1,
+                ..
+            })
+        ));
+        assert_eq!(
+            circuit.conditional("c", 4, Instruction::Reset(q0)).err(),
+            Some(CircuitError::ConditionOutOfRange {
+                register: 0,
+                value: 4
+            })
+        );
+        assert!(circuit.instructions().is_empty());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn circuits_round_trip_through_json() {
+        let circuit = circuit();
+        let json = serde_json::to_string(&circuit).unwrap();
+        let decoded: QuantumCircuit = serde_json::from_str(&json).unwrap();
+        assert_same(&circuit, &decoded);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialized_instructions_are_validated() {
+        let mut circuit = bell();
+        let (q0, q1) = (QubitRef::new(0, 0), QubitRef::new(0, 1));
+        circuit.cx(q0, q1).unwrap();
+        let json = serde_json::to_value(&circuit).unwrap();
+
+        let with_target = |register: usize, index: usize| {
+            let mut json = json.clone();
+            json["instructions"][0]["Gate"]["targets"][0] =
+                serde_json::json!({ "register": register, "index": index });
+            serde_json::from_value::<QuantumCircuit>(json)
+                .err()
+                .map(|error| error.to_string())
+        };
+        assert_eq!(with_target(0, 1), None);
+        assert_eq!(
+            with_target(0, 2),
+            Some(CircuitError::QubitOutOfRange(QubitRef::new(0, 2)).to_string())
+        );
+        assert_eq!(
+            with_target(0, 0),
+            Some(CircuitError::DuplicateQubit(q0).to_string())
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn circuits_round_trip_through_bincode() {
+        let circuit = circuit();
+        let bytes = bincode::serialize(&circuit).unwrap();
+        let decoded: QuantumCircuit = bincode::deserialize(&bytes).unwrap();
+        assert_same(&circuit, &decoded);
     }
 }
@@ -1,62 +1,83 @@
 use core::ops;
 
-#[derive(Clone, Copy)]
-pub struct ClassicalBit<'a> {
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClassicalBit {
     state: bool,
-    name: &'a str,
+    name: String,
 }
 
 #[derive(Clone)]
-pub struct ClassicalRegister<'a> {
-    bits: Vec<ClassicalBit<'a>>,
-    name: &'a str,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClassicalRegister {
+    bits: Vec<ClassicalBit>,
+    name: String,
 }
 
-impl<'a> ClassicalBit<'a> {
-    pub fn new(name: &'a str, state: bool) -> ClassicalBit {
-        ClassicalBit { name, state }
+impl ClassicalBit {
+    pub fn new(name: impl Into<String>, state: bool) -> ClassicalBit {
+        ClassicalBit {
+            name: name.into(),
+            state,
+        }
     }
 
-    pub fn get_name(&self) -> &'a str {
-        self.name
+    pub fn get_name(&self) -> &str {
+        &self.name
     }
 
     pub fn get_state(&self) -> bool {
         self.state
     }
+
+    pub fn set_state(&mut self, state: bool) {
+        self.state = state;
+    }
 }
 
-impl<'a> ClassicalRegister<'a> {
-    pub fn new(name: &'a str, names: &'a [&'a str]) -> ClassicalRegister<'a> {
-        let mut bits: Vec<ClassicalBit<'a>> = Vec::new();
-        for i in 0..names.len() {
-            bits.push(ClassicalBit::new(names[i], false));
+impl ClassicalRegister {
+    pub fn new(name: impl Into<String>, names: &[impl AsRef<str>]) -> ClassicalRegister {
+        let mut bits: Vec<ClassicalBit> = Vec::new();
+        for name in names {
+            bits.push(ClassicalBit::new(name.as_ref(), false));
+        }
+        ClassicalRegister {
+            name: name.into(),
+            bits,
         }
-        ClassicalRegister { name, bits }
     }
 
-    pub fn set_bits(&mut self, bits: Vec<ClassicalBit<'a>>) {
+    pub fn set_bits(&mut self, bits: Vec<ClassicalBit>) {
         self.bits = bits;
     }
 
-    pub fn get_bits(&self) -> Vec<ClassicalBit<'a>> {
+    pub fn get_bits(&self) -> Vec<ClassicalBit> {
         self.bits.clone()
     }
 
-    pub fn get_name(&self) -> &'a str {
-        self.name
+    /// Number of bits.
+    pub fn len(&self) -> usize {
+        self.bits.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits.is_empty()
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
     }
 }
 
-impl<'a> ops::Index<usize> for ClassicalRegister<'a> {
-    type Output = ClassicalBit<'a>;
+impl ops::Index<usize> for ClassicalRegister {
+    type Output = ClassicalBit;
 
     fn index(&self, index: usize) -> &Self::Output {
         &self.bits[index]
     }
 }
 
-impl<'a> ops::IndexMut<usize> for ClassicalRegister<'a> {
+impl ops::IndexMut<usize> for ClassicalRegister {
     fn index_mut(&mut self, index: usize) -> &mut Self::Output {
         &mut self.bits[index]
     }
@@ -0,0 +1,380 @@
+#[cfg(feature = "serde")]
+use super::circuit::check_operands;
+use super::{
+    apply_operator, controlled_matrix, CircuitError, GateError, Instruction, QuantumCircuit,
+    QuantumGate, QubitRef,
+};
+use crate::{Complex, Matrix, Numeric};
+
+/// A gate defined by a sub-circuit, like an OpenQASM `gate` block. It can be added to a circuit
+/// either as one opaque gate, whose matrix is the product of the body, or expanded into the body
+/// instructions.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "RawCompositeGate"))]
+pub struct CompositeGate {
+    name: String,
+    qubits: usize,
+    /// Body instructions, addressing the gate's qubits as `QubitRef::new(0, i)`.
+    body: Vec<Instruction>,
+}
+
+/// Unchecked form of [`CompositeGate`], whose body is validated before deserialization accepts
+/// it.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct RawCompositeGate {
+    name: String,
+    qubits: usize,
+    body: Vec<Instruction>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<RawCompositeGate> for CompositeGate {
+    type Error = GateError;
+
+    fn try_from(raw: RawCompositeGate) -> Result<Self, Self::Error> {
+        if dimension(raw.qubits).is_none() {
+            return Err(GateError::TooManyQubits(raw.qubits));
+        }
+        let check_qubit = |qubit: QubitRef| {
+            if qubit.register == 0 && qubit.index < raw.qubits {
+                Ok(())
+            } else {
+                Err(CircuitError::QubitOutOfRange(qubit))
+            }
+        };
+        for instruction in &raw.body {
+            if !matches!(
+                instruction,
+                Instruction::Gate { .. } | Instruction::Barrier(_)
+            ) {
+                return Err(GateError::UnsupportedInstruction);
+            }
+            check_operands(instruction, check_qubit).map_err(GateError::InvalidBody)?;
+        }
+
+        Ok(CompositeGate {
+            name: raw.name,
+            qubits: raw.qubits,
+            body: raw.body,
+        })
+    }
+}
+
+impl CompositeGate {
+    /// Defines a gate over the qubits of `circuit`, its quantum registers taken in order.
+    /// Measurements, resets and conditionals are rejected.
+    pub fn from_circuit(
+        name: impl Into<String>,
+        circuit: &QuantumCircuit,
+    ) -> Result<CompositeGate, GateError> {
+        let mut offsets = Vec::new();
+        let mut qubits = 0;
+        for register in circuit.quantum_registers() {
+            offsets.push(qubits);
+            qubits += register.len();
+        }
+        let flatten = |qubit: &QubitRef| QubitRef::new(0, offsets[qubit.register] + qubit.index);
+
+        let body = circuit
+            .instructions()
+            .iter()
+            .map(|instruction| match instruction {
+                Instruction::Gate {
+                    gate,
+                    controls,
+                    targets,
+                } => Ok(Instruction::Gate {
+                    gate: gate.clone(),
+                    controls: controls.iter().map(flatten).collect(),
+                    targets: targets.iter().map(flatten).collect(),
+                }),
+                Instruction::Barrier(qubits) => {
+                    Ok(Instruction::Barrier(qubits.iter().map(flatten).collect()))
+                }
+                _ => Err(GateError::UnsupportedInstruction),
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(CompositeGate {
+            name: name.into(),
+            qubits,
+            body,
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn qubits(&self) -> usize {
+        self.qubits
+    }
+
+    pub fn body(&self) -> &[Instruction] {
+        &self.body
+    }
+
+    /// The body instructions acting on `qubits` instead of the gate's own qubits, one for each.
+    pub fn expand(&self, qubits: &[QubitRef]) -> Result<Vec<Instruction>, CircuitError> {
+        if qubits.len() != self.qubits {
+            return Err(CircuitError::ArityMismatch {
+                gate: self.name.clone(),
+                expected: self.qubits,
+                actual: qubits.len(),
+            });
+        }
+
+        let map = |qubit: &QubitRef| qubits[qubit.index];
+        Ok(self
+            .body
+            .iter()
+            .map(|instruction| match instruction {
+                Instruction::Gate {
+                    gate,
+                    controls,
+                    targets,
+                } => Instruction::Gate {
+                    gate: gate.clone(),
+                    controls: controls.iter().map(map).collect(),
+                    targets: targets.iter().map(map).collect(),
+                },
+                Instruction::Barrier(barrier) => {
+                    Instruction::Barrier(barrier.iter().map(map).collect())
+                }
+                _ => unreachable!("composite bodies only hold gates and barriers"),
+            })
+            .collect())
+    }
+
+    /// The body as a single gate over `2^qubits` dimensions. Each column of the matrix is the body
+    /// run on one basis state with the in-place register kernels, so a `k`-qubit body gate costs
+    /// `O(4^n · 2^k)` on `n` qubits rather than a dense `2^n x 2^n` product.
+    pub fn to_gate(&self) -> Result<QuantumGate, GateError> {
+        let dimension = dimension(self.qubits).ok_or(GateError::TooManyQubits(self.qubits))?;
+        let bit = |qubit: &QubitRef| 1 << (self.qubits - 1 - qubit.index);
+        let operators: Vec<(Matrix<Complex<f64>>, Vec<usize>)> = self
+            .body
+            .iter()
+            .filter_map(|instruction| match instruction {
+                Instruction::Gate {
+                    gate,
+                    controls,
+                    targets,
+                } => Some((
+                    controlled_matrix(&gate.matrix, controls.len(), true),
+                    controls.iter().chain(targets).map(bit).collect(),
+                )),
+                _ => None,
+            })
+            .collect();
+
+        let mut unitary = Matrix::zeros(dimension, dimension);
+        let mut column = vec![Complex::zero(); dimension];
+        for index in 0..dimension {
+            column.fill(Complex::zero());
+            column[index] = Complex::one();
+            for (operator, bits) in &operators {
+                apply_operator(operator, bits, &mut column);
+            }
+            for (row, amplitude) in column.iter().enumerate() {
+                unitary.set(row, index, *amplitude);
+            }
+        }
+
+        Ok(QuantumGate::new(self.name.clone(), unitary))
+    }
+}
+
+/// The dimension `2^qubits` of a gate's matrix, or `None` when its `4^qubits` entries cannot be
+/// counted.
+fn dimension(qubits: usize) -> Option<usize> {
+    let qubits = u32::try_from(qubits).ok()?;
+    1usize.checked_shl(qubits.checked_mul(2)?)?;
+    Some(1 << qubits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{gates, ClassicalRegister, ClbitRef, QuantumRegister, RegisterDescriptor};
+
+    fn register(qubits: &[&str]) -> QuantumCircuit {
+        QuantumCircuit::new(vec![RegisterDescriptor::new("q", qubits)], vec![])
+    }
+
+    #[test]
+    fn opaque_composites_multiply_out() {
+        let mut bell = register(&["a", "b"]);
+        let (a, b) = (QubitRef::new(0, 0), QubitRef::new(0, 1));
+        bell.h(a).unwrap().cx(a, b).unwrap();
+
+        let gate = CompositeGate::from_circuit("bell", &bell)
+            .unwrap()
+            .to_gate()
+            .unwrap();
+        let expected = gates::CNOT
+            .matrix
+            .dot(&gates::HADAMARD.matrix.kronecker(&Matrix::identity(2)))
+            .unwrap();
+        assert!(gate.matrix.approx_eq(&expected, 1e-12));
+        assert_eq!(gate.qubits(), 2);
+
+        // A CNOT with the control below the target, spanning a spectator qubit.
+        let mut reversed = register(&["a", "b", "c"]);
+        reversed
+            .cx(QubitRef::new(0, 2), QubitRef::new(0, 0))
+            .unwrap();
+        let gate = CompositeGate::from_circuit("reversed", &reversed)
+            .unwrap()
+            .to_gate()
+            .unwrap();
+        let mut expected = Matrix::zeros(8, 8);
+        for column in 0..8 {
+            let row = if column & 1 == 1 { column ^ 4 } else { column };
+            expected.set(row, column, Complex::one());
+        }
+        assert!(gate.matrix.approx_eq(&expected, 1e-12));
+    }
+
+    #[test]
+    fn opaque_composites_match_the_simulated_body() {
+        let mut circuit = register(&["a", "b", "c", "d"]);
+        let q = |index| QubitRef::new(0, index);
+        circuit
+            .h(q(3))
+            .unwrap()
+            .cx(q(3), q(0))
+            .unwrap()
+            .gate(gates::StandardGate::Ccx.to_gate(), &[q(0), q(2), q(1)])
+            .unwrap()
+            .controlled_gate(gates::HADAMARD.clone(), &[q(1), q(2)], &[q(3)])
+            .unwrap();
+        let gate = CompositeGate::from_circuit("g", &circuit)
+            .unwrap()
+            .to_gate()
+            .unwrap();
+
+        // Each column is the body run on a register prepared in one basis state.
+        let mut expected = Matrix::zeros(16, 16);
+        for column in 0..16 {
+            let mut state: QuantumRegister = QuantumRegister::new("s", &["a", "b", "c", "d"]);
+            state.amplitudes_mut().fill(Complex::zero());
+            state.amplitudes_mut()[column] = Complex::one();
+            for instruction in circuit.instructions() {
+                if let Instruction::Gate {
+                    gate,
+                    controls,
+                    targets,
+                } = instruction
+                {
+                    let qubits: Vec<usize> = controls
+                        .iter()
+                        .chain(targets)
+                        .map(|qubit| qubit.index)
+                        .collect();
+                    state
+                        .apply(&gate.controlled(controls.len()), &qubits)
+                        .unwrap();
+                }
+            }
+            for row in 0..16 {
+                expected.set(row, column, state.get_state()[row]);
+            }
+        }
+        assert!(gate.matrix.approx_eq(&expected, 1e-12));
+        assert!(gate.matrix.is_unitary(1e-12));
+    }
+
+    #[test]
+    fn composites_expand_onto_circuit_qubits() {
+        let mut definition = register(&["a", "b"]);
+        let (a, b) = (QubitRef::new(0, 0), QubitRef::new(0, 1));
+        definition
+            .h(b)
+            .unwrap()
+            .barrier(&[a, b])
+            .unwrap()
+            .cx(a, b)
+            .unwrap();
+        let composite = CompositeGate::from_circuit("entangle", &definition).unwrap();
+
+        let mut circuit = register(&["q0", "q1", "q2"]);
+        let (q0, q2) = (QubitRef::new(0, 0), QubitRef::new(0, 2));
+        circuit.expand_composite(&composite, &[q2, q0]).unwrap();
+        circuit.composite_gate(&composite, &[q0, q2]).unwrap();
+
+        let instructions = circuit.instructions();
+        assert_eq!(instructions.len(), 4);
+        assert_eq!(instructions[0].qubits(), vec![q0]);
+        assert_eq!(instructions[2].qubits(), vec![q2, q0]);
+        assert!(matches!(
+            &instructions[3],
+            Instruction::Gate { gate, targets, .. } if gate.name == "entangle" && targets == &[q0, q2]
+        ));
+        assert!(circuit.composite_gate(&composite, &[q0]).is_err());
+        assert_eq!(
+            composite.expand(&[q0]).err(),
+            Some(CircuitError::ArityMismatch {
+                gate: "entangle".to_string(),
+                expected: 2,
+                actual: 1
+            })
+        );
+        assert!(circuit.expand_composite(&composite, &[q0, q0]).is_err());
+        assert_eq!(circuit.instructions().len(), 4);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialized_bodies_are_validated() {
+        let mut definition = register(&["a", "b"]);
+        definition
+            .cx(QubitRef::new(0, 0), QubitRef::new(0, 1))
+            .unwrap();
+        let composite = CompositeGate::from_circuit("c", &definition).unwrap();
+        let json = serde_json::to_value(&composite).unwrap();
+        let decoded: CompositeGate = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(decoded.qubits(), 2);
+
+        let mut narrowed = json.clone();
+        narrowed["qubits"] = serde_json::json!(1);
+        let error = match serde_json::from_value::<CompositeGate>(narrowed) {
+            Ok(_) => panic!("a body beyond the gate's qubits was accepted"),
+            Err(error) => error,
+        };
+        let expected = GateError::InvalidBody(CircuitError::QubitOutOfRange(QubitRef::new(0, 1)));
+        assert_eq!(error.to_string(), expected.to_string());
+
+        let mut wide = json.clone();
+        wide["qubits"] = serde_json::json!(64);
+        wide["body"] = serde_json::json!([]);
+        let error = match serde_json::from_value::<CompositeGate>(wide) {
+            Ok(_) => panic!("a gate too wide for a matrix was accepted"),
+            Err(error) => error,
+        };
+        assert_eq!(error.to_string(), GateError::TooManyQubits(64).to_string());
+
+        let mut measuring = json;
+        measuring["body"][0] = serde_json::json!({
+            "Measure": { "qubit": { "register": 0, "index": 0 }, "bit": { "register": 0, "index": 0 } }
+        });
+        assert!(serde_json::from_value::<CompositeGate>(measuring).is_err());
+    }
+
+    #[test]
+    fn composites_reject_non_unitary_instructions() {
+        let mut circuit = QuantumCircuit::new(
+            vec![RegisterDescriptor::new("q", &["a"])],
+            vec![ClassicalRegister::new("c", &["c0"])],
+        );
+        circuit
+            .measure(QubitRef::new(0, 0), ClbitRef::new(0, 0))
+            .unwrap();
+        assert_eq!(
+            CompositeGate::from_circuit("m", &circuit).err(),
+            Some(GateError::UnsupportedInstruction)
+        );
+    }
+}
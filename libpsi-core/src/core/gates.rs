@@ -1,37 +1,669 @@
-use crate::{complex, matrix, QuantumGate};
+use super::quantum_components::controlled_matrix;
+use crate::{complex, Complex, GateError, Matrix, QuantumGate, SMatrix};
+use core::f64::consts::{FRAC_1_SQRT_2, FRAC_PI_2, PI};
+
+/// Matrix of a single-qubit gate.
+pub type GateMatrix1 = SMatrix<Complex<f64>, 2, 2>;
+/// Matrix of a two-qubit gate.
+pub type GateMatrix2 = SMatrix<Complex<f64>, 4, 4>;
+
+const ZERO: Complex<f64> = complex!(0.0, 0.0);
+const ONE: Complex<f64> = complex!(1.0, 0.0);
+
+#[rustfmt::skip]
+pub const HADAMARD_MATRIX: GateMatrix1 = SMatrix::from_rows([
+    [complex!(FRAC_1_SQRT_2, 0.0), complex!( FRAC_1_SQRT_2, 0.0)],
+    [complex!(FRAC_1_SQRT_2, 0.0), complex!(-FRAC_1_SQRT_2, 0.0)],
+]);
+
+#[rustfmt::skip]
+pub const PAULI_X_MATRIX: GateMatrix1 = SMatrix::from_rows([
+    [ZERO, ONE ],
+    [ONE,  ZERO],
+]);
+
+#[rustfmt::skip]
+pub const PAULI_Y_MATRIX: GateMatrix1 = SMatrix::from_rows([
+    [ZERO,               complex!(0.0, -1.0)],
+    [complex!(0.0, 1.0), ZERO               ],
+]);
+
+#[rustfmt::skip]
+pub const PAULI_Z_MATRIX: GateMatrix1 = SMatrix::from_rows([
+    [ONE,  ZERO               ],
+    [ZERO, complex!(-1.0, 0.0)],
+]);
+
+#[rustfmt::skip]
+pub const S_MATRIX: GateMatrix1 = SMatrix::from_rows([
+    [ONE,  ZERO              ],
+    [ZERO, complex!(0.0, 1.0)],
+]);
 
 #[rustfmt::skip]
+pub const T_MATRIX: GateMatrix1 = SMatrix::from_rows([
+    [ONE,  ZERO                                  ],
+    [ZERO, complex!(FRAC_1_SQRT_2, FRAC_1_SQRT_2)],
+]);
+
+#[rustfmt::skip]
+pub const SX_MATRIX: GateMatrix1 = SMatrix::from_rows([
+    [complex!(0.5,  0.5), complex!(0.5, -0.5)],
+    [complex!(0.5, -0.5), complex!(0.5,  0.5)],
+]);
+
+#[rustfmt::skip]
+pub const CNOT_MATRIX: GateMatrix2 = SMatrix::from_rows([
+    [ONE,  ZERO, ZERO, ZERO],
+    [ZERO, ONE,  ZERO, ZERO],
+    [ZERO, ZERO, ZERO, ONE ],
+    [ZERO, ZERO, ONE,  ZERO],
+]);
+
+#[rustfmt::skip]
+pub const SWAP_MATRIX: GateMatrix2 = SMatrix::from_rows([
+    [ONE,  ZERO, ZERO, ZERO],
+    [ZERO, ZERO, ONE,  ZERO],
+    [ZERO, ONE,  ZERO, ZERO],
+    [ZERO, ZERO, ZERO, ONE ],
+]);
+
+#[rustfmt::skip]
+pub const ISWAP_MATRIX: GateMatrix2 = SMatrix::from_rows([
+    [ONE,  ZERO,               ZERO,               ZERO],
+    [ZERO, ZERO,               complex!(0.0, 1.0), ZERO],
+    [ZERO, complex!(0.0, 1.0), ZERO,               ZERO],
+    [ZERO, ZERO,               ZERO,               ONE ],
+]);
+
 lazy_static::lazy_static! {
-    pub static ref HADAMARD: QuantumGate<'static> = QuantumGate {
-        name: "H",
-        matrix: matrix!([complex!(1.0, 0.0), complex!( 1.0, 0.0)];
-                        [complex!(1.0, 0.0), complex!(-1.0, 0.0)]) *
-                complex!(1.0/2.0_f64.sqrt(), 0.0)
-    };
-
-    pub static ref PAULI_X: QuantumGate<'static> = QuantumGate {
-        name: "Pauli-X",
-        matrix: matrix!([complex!(0.0, 0.0), complex!(1.0, 0.0)];
-                        [complex!(1.0, 0.0), complex!(0.0, 0.0)])
-    };
-
-    pub static ref PAULI_Y: QuantumGate<'static> = QuantumGate {
-        name: "Pauli-Y", 
-        matrix: matrix!([complex!(0.0, 0.0), complex!(0.0, -1.0)];
-                        [complex!(0.0, 1.0), complex!(0.0,  0.0)])
-    };
-
-    pub static ref PAULI_Z: QuantumGate<'static> = QuantumGate {
-        name: "Pauli-Z", 
-        matrix: matrix!([complex!(1.0, 0.0), complex!( 0.0, 0.0)];
-                        [complex!(0.0, 0.0), complex!(-1.0, 0.0)])
-    };
-
-    pub static ref CNOT: QuantumGate<'static> = QuantumGate {
-        name: "CNOT", 
-        matrix: matrix!([complex!(1.0, 0.0), complex!(0.0, 0.0), complex!(0.0, 0.0), complex!(0.0, 0.0)];
-                        [complex!(0.0, 0.0), complex!(1.0, 0.0), complex!(0.0, 0.0), complex!(0.0, 0.0)];
-                        [complex!(0.0, 0.0), complex!(0.0, 0.0), complex!(0.0, 0.0), complex!(1.0, 0.0)];
-                        [complex!(0.0, 0.0), complex!(0.0, 0.0), complex!(1.0, 0.0), complex!(0.0, 0.0)])
-    };
+    pub static ref HADAMARD: QuantumGate = QuantumGate::new("h", HADAMARD_MATRIX.to_matrix());
+
+    pub static ref PAULI_X: QuantumGate = QuantumGate::new("x", PAULI_X_MATRIX.to_matrix());
+
+    pub static ref PAULI_Y: QuantumGate = QuantumGate::new("y", PAULI_Y_MATRIX.to_matrix());
+
+    pub static ref PAULI_Z: QuantumGate = QuantumGate::new("z", PAULI_Z_MATRIX.to_matrix());
+
+    pub static ref CNOT: QuantumGate = QuantumGate::new("cx", CNOT_MATRIX.to_matrix());
+}
+
+fn parameterised<const N: usize>(
+    name: &'static str,
+    parameters: &[f64],
+    matrix: SMatrix<Complex<f64>, N, N>,
+) -> QuantumGate {
+    QuantumGate::new(name, matrix.to_matrix()).with_parameters(parameters.to_vec())
+}
+
+/// OpenQASM 3's built-in `U(θ, φ, λ)`, without the global phase of `u3`.
+#[rustfmt::skip]
+fn u_matrix(theta: f64, phi: f64, lambda: f64) -> GateMatrix1 {
+    let (sin, cos) = (theta / 2.0).sin_cos();
+    SMatrix::from_rows([
+        [complex!(cos, 0.0),      -Complex::cis(lambda) * sin      ],
+        [Complex::cis(phi) * sin,  Complex::cis(phi + lambda) * cos],
+    ])
+}
+
+/// Rotation about the X axis, `exp(-iθX/2)`.
+#[rustfmt::skip]
+pub fn rx(theta: f64) -> QuantumGate {
+    let (sin, cos) = (theta / 2.0).sin_cos();
+    parameterised("rx", &[theta], SMatrix::from_rows([
+        [complex!(cos, 0.0),  complex!(0.0, -sin)],
+        [complex!(0.0, -sin), complex!(cos, 0.0) ],
+    ]))
+}
+
+/// Rotation about the Y axis, `exp(-iθY/2)`.
+#[rustfmt::skip]
+pub fn ry(theta: f64) -> QuantumGate {
+    let (sin, cos) = (theta / 2.0).sin_cos();
+    parameterised("ry", &[theta], SMatrix::from_rows([
+        [complex!(cos, 0.0), complex!(-sin, 0.0)],
+        [complex!(sin, 0.0), complex!(cos, 0.0) ],
+    ]))
+}
+
+/// Rotation about the Z axis, `exp(-iθZ/2)`.
+#[rustfmt::skip]
+pub fn rz(theta: f64) -> QuantumGate {
+    parameterised("rz", &[theta], SMatrix::from_rows([
+        [Complex::cis(-theta / 2.0), ZERO                     ],
+        [ZERO,                       Complex::cis(theta / 2.0)],
+    ]))
+}
+
+/// Phase shift of `|1⟩` by `λ`; equal to `rz(λ)` up to a global phase.
+#[rustfmt::skip]
+pub fn phase(lambda: f64) -> QuantumGate {
+    parameterised("p", &[lambda], SMatrix::from_rows([
+        [ONE,  ZERO                ],
+        [ZERO, Complex::cis(lambda)],
+    ]))
+}
+
+/// The general single-qubit gate `U(θ, φ, λ) = e^{i(φ+λ)/2} rz(φ) ry(θ) rz(λ)`.
+pub fn u(theta: f64, phi: f64, lambda: f64) -> QuantumGate {
+    parameterised("U", &[theta, phi, lambda], u_matrix(theta, phi, lambda))
+}
+
+/// `rz(φ) ry(θ) rz(λ)`, which is `U(θ, φ, λ)` with the global phase of OpenQASM 2.
+pub fn u3(theta: f64, phi: f64, lambda: f64) -> QuantumGate {
+    let matrix = u_matrix(theta, phi, lambda).scale(Complex::cis(-(phi + lambda) / 2.0));
+    parameterised("u3", &[theta, phi, lambda], matrix)
+}
+
+/// `u3(π/2, φ, λ)`, up to the global phase `e^{-iπ/2}` given by `stdgates.inc`.
+pub fn u2(phi: f64, lambda: f64) -> QuantumGate {
+    let matrix = u_matrix(FRAC_PI_2, phi, lambda).scale(Complex::cis(-(phi + lambda + PI) / 2.0));
+    parameterised("u2", &[phi, lambda], matrix)
+}
+
+/// `U(0, 0, λ)`, the same matrix as [`phase`].
+pub fn u1(lambda: f64) -> QuantumGate {
+    parameterised("u1", &[lambda], u_matrix(0.0, 0.0, lambda))
+}
+
+/// Global phase `e^{iγ}`, a gate on zero qubits.
+pub fn global_phase(gamma: f64) -> QuantumGate {
+    parameterised(
+        "gphase",
+        &[gamma],
+        SMatrix::from_rows([[Complex::cis(gamma)]]),
+    )
+}
+
+/// `|0⟩⟨0| ⊗ I + |1⟩⟨1| ⊗ matrix`, controlled by the first qubit.
+fn controlled(matrix: &Matrix<Complex<f64>>) -> Matrix<Complex<f64>> {
+    controlled_matrix(matrix, 1, true)
+}
+
+/// `exp(-iθ P⊗P/2)` for a Pauli matrix `P`.
+fn ising(pauli: GateMatrix1, theta: f64) -> Matrix<Complex<f64>> {
+    let (sin, cos) = (theta / 2.0).sin_cos();
+    let pair: GateMatrix2 = pauli.kronecker(&pauli);
+    (GateMatrix2::identity().scale(complex!(cos, 0.0)) + pair.scale(complex!(0.0, -sin)))
+        .to_matrix()
+}
+
+/// The gates of OpenQASM 3's `stdgates.inc` and built-ins, plus `sxdg`, `iswap`, its inverse
+/// `iswapdg`, and the Ising couplings `rxx`, `ryy` and `rzz`. Controlled gates take their
+/// control qubits first.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StandardGate {
+    Identity,
+    X,
+    Y,
+    Z,
+    H,
+    S,
+    Sdg,
+    T,
+    Tdg,
+    Sx,
+    Sxdg,
+    Rx(f64),
+    Ry(f64),
+    Rz(f64),
+    Phase(f64),
+    U1(f64),
+    U2(f64, f64),
+    U3(f64, f64, f64),
+    U(f64, f64, f64),
+    GlobalPhase(f64),
+    Cx,
+    Cy,
+    Cz,
+    Ch,
+    Cp(f64),
+    Crx(f64),
+    Cry(f64),
+    Crz(f64),
+    /// Controlled `U(θ, φ, λ)` with the extra phase `γ` on the controlled subspace.
+    Cu(f64, f64, f64, f64),
+    Swap,
+    ISwap,
+    ISwapDg,
+    Rxx(f64),
+    Ryy(f64),
+    Rzz(f64),
+    Ccx,
+    Cswap,
+}
+
+impl StandardGate {
+    /// Canonical name of every gate; [`StandardGate::from_name`] also accepts the
+    /// `stdgates.inc` aliases `CX`, `phase` and `cphase`.
+    pub const NAMES: [&'static str; 37] = [
+        "id", "x", "y", "z", "h", "s", "sdg", "t", "tdg", "sx", "sxdg", "rx", "ry", "rz", "p",
+        "u1", "u2", "u3", "U", "gphase", "cx", "cy", "cz", "ch", "cp", "crx", "cry", "crz", "cu",
+        "swap", "iswap", "iswapdg", "rxx", "ryy", "rzz", "ccx", "cswap",
+    ];
+
+    /// Number of angles taken by the gate called `name`.
+    pub fn parameter_count(name: &str) -> Option<usize> {
+        match name {
+            "id" | "x" | "y" | "z" | "h" | "s" | "sdg" | "t" | "tdg" | "sx" | "sxdg" | "cx"
+            | "CX" | "cy" | "cz" | "ch" | "swap" | "iswap" | "iswapdg" | "ccx" | "cswap" => Some(0),
+            "rx" | "ry" | "rz" | "p" | "phase" | "u1" | "gphase" | "cp" | "cphase" | "crx"
+            | "cry" | "crz" | "rxx" | "ryy" | "rzz" => Some(1),
+            "u2" => Some(2),
+            "u3" | "U" => Some(3),
+            "cu" => Some(4),
+            _ => None,
+        }
+    }
+
+    /// Resolves a gate by its OpenQASM name.
+    pub fn from_name(name: &str, parameters: &[f64]) -> Result<StandardGate, GateError> {
+        let expected = StandardGate::parameter_count(name)
+            .ok_or_else(|| GateError::UnknownGate(name.to_string()))?;
+        if parameters.len() != expected {
+            return Err(GateError::ParameterCount {
+                gate: name.to_string(),
+                expected,
+                actual: parameters.len(),
+            });
+        }
+
+        use StandardGate::*;
+        Ok(match (name, parameters) {
+            ("id", _) => Identity,
+            ("x", _) => X,
+            ("y", _) => Y,
+            ("z", _) => Z,
+            ("h", _) => H,
+            ("s", _) => S,
+            ("sdg", _) => Sdg,
+            ("t", _) => T,
+            ("tdg", _) => Tdg,
+            ("sx", _) => Sx,
+            ("sxdg", _) => Sxdg,
+            ("rx", &[theta]) => Rx(theta),
+            ("ry", &[theta]) => Ry(theta),
+            ("rz", &[theta]) => Rz(theta),
+            ("p" | "phase", &[lambda]) => Phase(lambda),
+            ("u1", &[lambda]) => U1(lambda),
+            ("u2", &[phi, lambda]) => U2(phi, lambda),
+            ("u3", &[theta, phi, lambda]) => U3(theta, phi, lambda),
+            ("U", &[theta, phi, lambda]) => U(theta, phi, lambda),
+            ("gphase", &[gamma]) => GlobalPhase(gamma),
+            ("cx" | "CX", _) => Cx,
+            ("cy", _) => Cy,
+            ("cz", _) => Cz,
+            ("ch", _) => Ch,
+            ("cp" | "cphase", &[lambda]) => Cp(lambda),
+            ("crx", &[theta]) => Crx(theta),
+            ("cry", &[theta]) => Cry(theta),
+            ("crz", &[theta]) => Crz(theta),
+            ("cu", &[theta, phi, lambda, gamma]) => Cu(theta, phi, lambda, gamma),
+            ("swap", _) => Swap,
+            ("iswap", _) => ISwap,
+            ("iswapdg", _) => ISwapDg,
+            ("rxx", &[theta]) => Rxx(theta),
+            ("ryy", &[theta]) => Ryy(theta),
+            ("rzz", &[theta]) => Rzz(theta),
+            ("ccx", _) => Ccx,
+            ("cswap", _) => Cswap,
+            _ => unreachable!("parameter counts are checked above"),
+        })
+    }
+
+    pub fn name(&self) -> &'static str {
+        use StandardGate::*;
+        match self {
+            Identity => "id",
+            X => "x",
+            Y => "y",
+            Z => "z",
+            H => "h",
+            S => "s",
+            Sdg => "sdg",
+            T => "t",
+            Tdg => "tdg",
+            Sx => "sx",
+            Sxdg => "sxdg",
+            Rx(_) => "rx",
+            Ry(_) => "ry",
+            Rz(_) => "rz",
+            Phase(_) => "p",
+            U1(_) => "u1",
+            U2(..) => "u2",
+            U3(..) => "u3",
+            U(..) => "U",
+            GlobalPhase(_) => "gphase",
+            Cx => "cx",
+            Cy => "cy",
+            Cz => "cz",
+            Ch => "ch",
+            Cp(_) => "cp",
+            Crx(_) => "crx",
+            Cry(_) => "cry",
+            Crz(_) => "crz",
+            Cu(..) => "cu",
+            Swap => "swap",
+            ISwap => "iswap",
+            ISwapDg => "iswapdg",
+            Rxx(_) => "rxx",
+            Ryy(_) => "ryy",
+            Rzz(_) => "rzz",
+            Ccx => "ccx",
+            Cswap => "cswap",
+        }
+    }
+
+    /// Number of qubits the gate acts on, controls included.
+    pub fn qubits(&self) -> usize {
+        use StandardGate::*;
+        match self {
+            GlobalPhase(_) => 0,
+            Cx | Cy | Cz | Ch | Cp(_) | Crx(_) | Cry(_) | Crz(_) | Cu(..) | Swap | ISwap
+            | ISwapDg | Rxx(_) | Ryy(_) | Rzz(_) => 2,
+            Ccx | Cswap => 3,
+            _ => 1,
+        }
+    }
+
+    pub fn parameters(&self) -> Vec<f64> {
+        use StandardGate::*;
+        match *self {
+            Rx(a) | Ry(a) | Rz(a) | Phase(a) | U1(a) | GlobalPhase(a) | Cp(a) | Crx(a) | Cry(a)
+            | Crz(a) | Rxx(a) | Ryy(a) | Rzz(a) => vec![a],
+            U2(a, b) => vec![a, b],
+            U3(a, b, c) | U(a, b, c) => vec![a, b, c],
+            Cu(a, b, c, d) => vec![a, b, c, d],
+            _ => Vec::new(),
+        }
+    }
+
+    /// The gate whose matrix is the adjoint of this one.
+    pub fn inverse(&self) -> StandardGate {
+        use StandardGate::*;
+        match *self {
+            S => Sdg,
+            Sdg => S,
+            T => Tdg,
+            Tdg => T,
+            Sx => Sxdg,
+            Sxdg => Sx,
+            ISwap => ISwapDg,
+            ISwapDg => ISwap,
+            Rx(theta) => Rx(-theta),
+            Ry(theta) => Ry(-theta),
+            Rz(theta) => Rz(-theta),
+            Phase(lambda) => Phase(-lambda),
+            U1(lambda) => U1(-lambda),
+            U2(phi, lambda) => U2(-lambda - PI, -phi - PI),
+            U3(theta, phi, lambda) => U3(-theta, -lambda, -phi),
+            U(theta, phi, lambda) => U(-theta, -lambda, -phi),
+            GlobalPhase(gamma) => GlobalPhase(-gamma),
+            Cp(lambda) => Cp(-lambda),
+            Crx(theta) => Crx(-theta),
+            Cry(theta) => Cry(-theta),
+            Crz(theta) => Crz(-theta),
+            Cu(theta, phi, lambda, gamma) => Cu(-theta, -lambda, -phi, -gamma),
+            Rxx(theta) => Rxx(-theta),
+            Ryy(theta) => Ryy(-theta),
+            Rzz(theta) => Rzz(-theta),
+            gate => gate,
+        }
+    }
+
+    pub fn matrix(&self) -> Matrix<Complex<f64>> {
+        use StandardGate::*;
+        match *self {
+            Identity => Matrix::identity(2),
+            X => PAULI_X_MATRIX.to_matrix(),
+            Y => PAULI_Y_MATRIX.to_matrix(),
+            Z => PAULI_Z_MATRIX.to_matrix(),
+            H => HADAMARD_MATRIX.to_matrix(),
+            S => S_MATRIX.to_matrix(),
+            Sdg => S_MATRIX.adjoint().to_matrix(),
+            T => T_MATRIX.to_matrix(),
+            Tdg => T_MATRIX.adjoint().to_matrix(),
+            Sx => SX_MATRIX.to_matrix(),
+            Sxdg => SX_MATRIX.adjoint().to_matrix(),
+            Rx(theta) => rx(theta).matrix,
+            Ry(theta) => ry(theta).matrix,
+            Rz(theta) => rz(theta).matrix,
+            Phase(lambda) => phase(lambda).matrix,
+            U1(lambda) => u1(lambda).matrix,
+            U2(phi, lambda) => u2(phi, lambda).matrix,
+            U3(theta, phi, lambda) => u3(theta, phi, lambda).matrix,
+            U(theta, phi, lambda) => u(theta, phi, lambda).matrix,
+            GlobalPhase(gamma) => global_phase(gamma).matrix,
+            Cx => CNOT_MATRIX.to_matrix(),
+            Cy => controlled(&PAULI_Y_MATRIX.to_matrix()),
+            Cz => controlled(&PAULI_Z_MATRIX.to_matrix()),
+            Ch => controlled(&HADAMARD_MATRIX.to_matrix()),
+            Cp(lambda) => controlled(&phase(lambda).matrix),
+            Crx(theta) => controlled(&rx(theta).matrix),
+            Cry(theta) => controlled(&ry(theta).matrix),
+            Crz(theta) => controlled(&rz(theta).matrix),
+            Cu(theta, phi, lambda, gamma) => controlled(
+                &u_matrix(theta, phi, lambda)
+                    .scale(Complex::cis(gamma))
+                    .to_matrix(),
+            ),
+            Swap => SWAP_MATRIX.to_matrix(),
+            ISwap => ISWAP_MATRIX.to_matrix(),
+            ISwapDg => ISWAP_MATRIX.adjoint().to_matrix(),
+            Rxx(theta) => ising(PAULI_X_MATRIX, theta),
+            Ryy(theta) => ising(PAULI_Y_MATRIX, theta),
+            Rzz(theta) => ising(PAULI_Z_MATRIX, theta),
+            Ccx => controlled(&CNOT_MATRIX.to_matrix()),
+            Cswap => controlled(&SWAP_MATRIX.to_matrix()),
+        }
+    }
+
+    pub fn to_gate(&self) -> QuantumGate {
+        QuantumGate::new(self.name(), self.matrix()).with_parameters(self.parameters())
+    }
+}
+
+impl From<StandardGate> for QuantumGate {
+    fn from(gate: StandardGate) -> QuantumGate {
+        gate.to_gate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gate_matrices_are_unitary() {
+        for matrix in [
+            HADAMARD_MATRIX,
+            PAULI_X_MATRIX,
+            PAULI_Y_MATRIX,
+            PAULI_Z_MATRIX,
+        ] {
+            assert!(matrix.is_unitary(1e-12));
+        }
+        assert!(CNOT_MATRIX.is_unitary(0.0));
+        assert!(HADAMARD.matrix.is_unitary(1e-12));
+    }
+
+    #[test]
+    fn cnot_is_controlled_x() {
+        let zero: GateMatrix1 = SMatrix::from_rows([[ONE, ZERO], [ZERO, ZERO]]);
+        let one: GateMatrix1 = SMatrix::from_rows([[ZERO, ZERO], [ZERO, ONE]]);
+
+        let controlled: GateMatrix2 =
+            zero.kronecker(&GateMatrix1::identity()) + one.kronecker(&PAULI_X_MATRIX);
+        assert_eq!(controlled, CNOT_MATRIX);
+    }
+
+    fn product(gates: &[QuantumGate]) -> Matrix<Complex<f64>> {
+        gates
+            .iter()
+            .map(|gate| gate.matrix.clone())
+            .reduce(|left, right| left.dot(&right).unwrap())
+            .unwrap()
+    }
+
+    #[test]
+    fn rotations_compose_additively() {
+        for (rotation, (a, b)) in [rx, ry, rz]
+            .into_iter()
+            .zip([(0.3, 1.1), (-2.0, 0.7), (PI, PI)])
+        {
+            let composed = product(&[rotation(a), rotation(b)]);
+            assert!(composed.approx_eq(&rotation(a + b).matrix, 1e-12));
+            assert!(rotation(a).matrix.is_unitary(1e-12));
+        }
+        assert!(rx(2.0 * PI)
+            .matrix
+            .approx_eq(&Matrix::identity(2).scale(-ONE), 1e-12));
+    }
+
+    #[test]
+    fn rotations_match_closed_forms() {
+        let minus_i = complex!(0.0, -1.0);
+        assert!(rx(PI)
+            .matrix
+            .approx_eq(&PAULI_X_MATRIX.scale(minus_i).to_matrix(), 1e-12));
+        assert!(ry(PI)
+            .matrix
+            .approx_eq(&PAULI_Y_MATRIX.scale(minus_i).to_matrix(), 1e-12));
+        assert!(rz(PI)
+            .matrix
+            .approx_eq(&PAULI_Z_MATRIX.scale(minus_i).to_matrix(), 1e-12));
+        assert!(product(&[HADAMARD.clone(), rz(0.4), HADAMARD.clone()])
+            .approx_eq(&rx(0.4).matrix, 1e-12));
+
+        let lambda = 0.9;
+        assert!(phase(lambda)
+            .matrix
+            .approx_eq(&rz(lambda).matrix.scale(Complex::cis(lambda / 2.0)), 1e-12));
+        assert!(u1(lambda).matrix.approx_eq(&phase(lambda).matrix, 1e-12));
+        assert_eq!(global_phase(lambda).matrix.get(0, 0), Complex::cis(lambda));
+    }
+
+    #[test]
+    fn euler_decompositions() {
+        let (theta, phi, lambda) = (0.7, -1.3, 2.1);
+        let euler = product(&[rz(phi), ry(theta), rz(lambda)]);
+
+        assert!(u3(theta, phi, lambda).matrix.approx_eq(&euler, 1e-12));
+        assert!(u(theta, phi, lambda)
+            .matrix
+            .approx_eq(&euler.scale(Complex::cis((phi + lambda) / 2.0)), 1e-12));
+        assert!(u(theta, -FRAC_PI_2, FRAC_PI_2)
+            .matrix
+            .approx_eq(&rx(theta).matrix, 1e-12));
+        assert!(u(theta, 0.0, 0.0)
+            .matrix
+            .approx_eq(&ry(theta).matrix, 1e-12));
+        assert!(u2(phi, lambda).matrix.approx_eq(
+            &u3(FRAC_PI_2, phi, lambda).matrix.scale(complex!(0.0, -1.0)),
+            1e-12
+        ));
+    }
+
+    #[test]
+    fn parameters_are_recorded() {
+        let gate = u3(0.5, 0.25, -1.0);
+        assert_eq!(gate.parameters, vec![0.5, 0.25, -1.0]);
+        assert_eq!(gate.to_string(), "u3(0.5, 0.25, -1)");
+        assert_eq!(HADAMARD.to_string(), "h");
+        assert_eq!(global_phase(1.0).matrix.rows, 1);
+    }
+
+    fn every_gate() -> impl Iterator<Item = StandardGate> {
+        let angles = [0.3, -1.1, 0.7, 2.0];
+        StandardGate::NAMES.iter().map(move |name| {
+            let count = StandardGate::parameter_count(name).unwrap();
+            StandardGate::from_name(name, &angles[..count]).unwrap()
+        })
+    }
+
+    #[test]
+    fn standard_gates_are_unitary() {
+        for gate in every_gate() {
+            let matrix = gate.matrix();
+            assert_eq!(matrix.rows, 1 << gate.qubits(), "{}", gate.name());
+            assert!(matrix.is_unitary(1e-12), "{} is not unitary", gate.name());
+        }
+    }
+
+    #[test]
+    fn standard_gate_inverses() {
+        for gate in every_gate() {
+            let product = gate.matrix().dot(&gate.inverse().matrix()).unwrap();
+            let identity = Matrix::identity(product.rows);
+            assert!(product.approx_eq(&identity, 1e-12), "{}", gate.name());
+            assert_eq!(gate.inverse().inverse().qubits(), gate.qubits());
+        }
+        assert_eq!(StandardGate::T.inverse(), StandardGate::Tdg);
+        assert_eq!(StandardGate::H.inverse(), StandardGate::H);
+    }
+
+    #[test]
+    fn standard_gates_by_name() {
+        for name in StandardGate::NAMES {
+            let count = StandardGate::parameter_count(name).unwrap();
+            assert_eq!(
+                StandardGate::from_name(name, &vec![0.5; count])
+                    .unwrap()
+                    .name(),
+                name
+            );
+        }
+        assert_eq!(StandardGate::from_name("CX", &[]), Ok(StandardGate::Cx));
+        assert_eq!(
+            StandardGate::from_name("cphase", &[1.0]),
+            Ok(StandardGate::Cp(1.0))
+        );
+        assert_eq!(
+            StandardGate::from_name("rx", &[]),
+            Err(GateError::ParameterCount {
+                gate: "rx".to_string(),
+                expected: 1,
+                actual: 0
+            })
+        );
+        assert_eq!(
+            StandardGate::from_name("foo", &[]),
+            Err(GateError::UnknownGate("foo".to_string()))
+        );
+
+        let gate = QuantumGate::from(StandardGate::Crz(0.25));
+        assert_eq!(gate.to_string(), "crz(0.25)");
+        assert!(gate
+            .matrix
+            .approx_eq(&StandardGate::Crz(0.25).matrix(), 0.0));
+    }
+
+    #[test]
+    fn standard_gates_match_definitions() {
+        let s = StandardGate::S.matrix();
+        assert!(s
+            .dot(&s)
+            .unwrap()
+            .approx_eq(&PAULI_Z_MATRIX.to_matrix(), 1e-12));
+        let t = StandardGate::T.matrix();
+        assert!(t.dot(&t).unwrap().approx_eq(&s, 1e-12));
+        let sx = StandardGate::Sx.matrix();
+        assert!(sx
+            .dot(&sx)
+            .unwrap()
+            .approx_eq(&PAULI_X_MATRIX.to_matrix(), 1e-12));
+
+        assert_eq!(StandardGate::Ccx.matrix().get(7, 6), ONE);
+        assert_eq!(StandardGate::Cswap.matrix().get(5, 6), ONE);
+        let rzz = StandardGate::Rzz(0.4).matrix();
+        assert!((rzz.get(0, 0) - Complex::cis(-0.2)).abs() < 1e-12);
+        assert!((rzz.get(2, 2) - Complex::cis(0.2)).abs() < 1e-12);
+        let ising = StandardGate::Rxx(PI).matrix();
+        let xx: GateMatrix2 = PAULI_X_MATRIX.kronecker(&PAULI_X_MATRIX);
+        assert!(ising.approx_eq(&xx.scale(complex!(0.0, -1.0)).to_matrix(), 1e-12));
+        assert!(StandardGate::Cu(0.5, 0.0, 0.0, 0.0)
+            .matrix()
+            .approx_eq(&StandardGate::Cry(0.5).matrix(), 1e-12));
+    }
 }
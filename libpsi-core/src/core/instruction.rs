@@ -0,0 +1,83 @@
+use super::QuantumGate;
+use core::fmt;
+
+/// A qubit of a circuit, addressed by the position of its register in the circuit and its index
+/// within that register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QubitRef {
+    pub register: usize,
+    pub index: usize,
+}
+
+/// A classical bit of a circuit, addressed like [`QubitRef`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ClbitRef {
+    pub register: usize,
+    pub index: usize,
+}
+
+impl QubitRef {
+    pub const fn new(register: usize, index: usize) -> QubitRef {
+        QubitRef { register, index }
+    }
+}
+
+impl ClbitRef {
+    pub const fn new(register: usize, index: usize) -> ClbitRef {
+        ClbitRef { register, index }
+    }
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Instruction {
+    /// Applies `gate` to `targets`, in the order of the gate's kronecker factors, when every
+    /// qubit of `controls` is `|1⟩`.
+    Gate {
+        gate: QuantumGate,
+        controls: Vec<QubitRef>,
+        targets: Vec<QubitRef>,
+    },
+    Measure {
+        qubit: QubitRef,
+        bit: ClbitRef,
+    },
+    Reset(QubitRef),
+    /// Prevents reordering across the given qubits.
+    Barrier(Vec<QubitRef>),
+    /// Runs `instruction` only when the classical register at `register` holds `value`, read
+    /// with bit 0 as the least significant bit.
+    Conditional {
+        register: usize,
+        value: u64,
+        instruction: Box<Instruction>,
+    },
+}
+
+impl Instruction {
+    /// Every qubit the instruction acts on, controls first.
+    pub fn qubits(&self) -> Vec<QubitRef> {
+        match self {
+            Instruction::Gate {
+                controls, targets, ..
+            } => controls.iter().chain(targets).copied().collect(),
+            Instruction::Measure { qubit, .. } | Instruction::Reset(qubit) => vec![*qubit],
+            Instruction::Barrier(qubits) => qubits.clone(),
+            Instruction::Conditional { instruction, .. } => instruction.qubits(),
+        }
+    }
+}
+
+impl fmt::Display for QubitRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "q{}[{}]", self.register, self.index)
+    }
+}
+
+impl fmt::Display for ClbitRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "c{}[{}]", self.register, self.index)
+    }
+}
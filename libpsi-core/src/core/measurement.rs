@@ -0,0 +1,730 @@
+use super::{cast_matrix, cumulative, gates, sample_index, QuantumRegister, RegisterError};
+use crate::{Complex, Float, MathError, Matrix, Numeric, Pauli};
+use core::fmt;
+use rand::Rng;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MeasurementError {
+    /// A measurement needs at least one outcome.
+    Empty,
+    NotSquare {
+        rows: usize,
+        cols: usize,
+    },
+    NotPowerOfTwo(usize),
+    /// Operator `index` is `rows x cols` instead of `expected x expected` like the first one.
+    DimensionMismatch {
+        index: usize,
+        expected: usize,
+        rows: usize,
+        cols: usize,
+    },
+    /// A basis factor was the identity, which measures nothing.
+    IdentityBasis(usize),
+    /// A basis of more factors than outcomes can be counted.
+    TooManyQubits(usize),
+    NotHermitian(usize),
+    NotProjector(usize),
+    NotPositive(usize),
+    /// The effects do not sum to the identity.
+    Incomplete,
+    Decomposition(MathError),
+}
+
+impl fmt::Display for MeasurementError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MeasurementError::Empty => write!(f, "measurement has no outcomes"),
+            MeasurementError::NotSquare { rows, cols } => {
+                write!(f, "expected a square operator, found {}x{}", rows, cols)
+            }
+            MeasurementError::NotPowerOfTwo(dimension) => {
+                write!(f, "operator dimension {} is not a power of two", dimension)
+            }
+            MeasurementError::DimensionMismatch {
+                index,
+                expected,
+                rows,
+                cols,
+            } => write!(
+                f,
+                "operator {} is {}x{}, expected {}x{}",
+                index, rows, cols, expected, expected
+            ),
+            MeasurementError::IdentityBasis(qubit) => {
+                write!(f, "basis factor {} is the identity", qubit)
+            }
+            MeasurementError::TooManyQubits(qubits) => {
+                write!(f, "a basis of {} qubits has too many outcomes", qubits)
+            }
+            MeasurementError::NotHermitian(index) => {
+                write!(f, "operator {} is not Hermitian", index)
+            }
+            MeasurementError::NotProjector(index) => {
+                write!(f, "operator {} is not a projector", index)
+            }
+            MeasurementError::NotPositive(index) => {
+                write!(f, "effect {} is not positive semidefinite", index)
+            }
+            MeasurementError::Incomplete => write!(f, "effects do not sum to the identity"),
+            MeasurementError::Decomposition(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for MeasurementError {}
+
+impl From<MathError> for MeasurementError {
+    fn from(error: MathError) -> MeasurementError {
+        MeasurementError::Decomposition(error)
+    }
+}
+
+/// How a [`Measurement`] was described.
+#[derive(Clone, Debug, PartialEq)]
+pub enum MeasurementKind {
+    /// Product Pauli basis, one factor per measured qubit.
+    Basis(Vec<Pauli>),
+    Projectors,
+    Povm,
+}
+
+/// A measurement of `k` qubits given by Kraus operators `Kᵢ`, whose effects `Kᵢ†Kᵢ` sum to the
+/// identity. Outcome `i` occurs with probability `⟨ψ|Kᵢ†Kᵢ|ψ⟩` and leaves the state `Kᵢ|ψ⟩`,
+/// renormalised.
+///
+/// Pauli bases only keep their factors: they are measured by rotating each target onto the
+/// computational basis and back, in `O(k · 2^n)` on `n` qubits. The other descriptors hold dense
+/// `2^k x 2^k` Kraus operators.
+#[derive(Clone, Debug)]
+pub struct Measurement {
+    kind: MeasurementKind,
+    qubits: usize,
+    /// Kraus operators, empty for a basis.
+    operators: Vec<Matrix<Complex<f64>>>,
+}
+
+impl Measurement {
+    /// Measures each qubit in the eigenbasis of its Pauli factor. Outcome bits follow the factors
+    /// with the first factor most significant, `0` for the `+1` eigenstate and `1` for `-1`, so
+    /// an all-Z basis has the outcomes of [`QuantumRegister::measure_all`].
+    pub fn basis(paulis: Vec<Pauli>) -> Result<Measurement, MeasurementError> {
+        if paulis.is_empty() {
+            return Err(MeasurementError::Empty);
+        }
+        if paulis.len() >= usize::BITS as usize {
+            return Err(MeasurementError::TooManyQubits(paulis.len()));
+        }
+        if let Some(qubit) = paulis.iter().position(|pauli| *pauli == Pauli::I) {
+            return Err(MeasurementError::IdentityBasis(qubit));
+        }
+
+        Ok(Measurement {
+            qubits: paulis.len(),
+            kind: MeasurementKind::Basis(paulis),
+            operators: Vec::new(),
+        })
+    }
+
+    /// A projective measurement: Hermitian, idempotent projectors summing to the identity.
+    pub fn projectors(
+        projectors: Vec<Matrix<Complex<f64>>>,
+        tolerance: f64,
+    ) -> Result<Measurement, MeasurementError> {
+        let qubits = check_operators(&projectors)?;
+        for (index, projector) in projectors.iter().enumerate() {
+            if !projector.is_hermitian(tolerance) {
+                return Err(MeasurementError::NotHermitian(index));
+            }
+            if !projector
+                .dot_unchecked(projector)
+                .approx_eq(projector, tolerance)
+            {
+                return Err(MeasurementError::NotProjector(index));
+            }
+        }
+        check_complete(projectors.iter().cloned(), qubits, tolerance)?;
+
+        Ok(Measurement {
+            kind: MeasurementKind::Projectors,
+            qubits,
+            operators: projectors,
+        })
+    }
+
+    /// A general measurement given by its Kraus operators.
+    pub fn povm(
+        kraus: Vec<Matrix<Complex<f64>>>,
+        tolerance: f64,
+    ) -> Result<Measurement, MeasurementError> {
+        let qubits = check_operators(&kraus)?;
+        check_complete(
+            kraus
+                .iter()
+                .map(|operator| operator.adjoint().dot_unchecked(operator)),
+            qubits,
+            tolerance,
+        )?;
+
+        Ok(Measurement {
+            kind: MeasurementKind::Povm,
+            qubits,
+            operators: kraus,
+        })
+    }
+
+    /// A general measurement given by its effects `Eᵢ`, with the Kraus operators `√Eᵢ`.
+    pub fn effects(
+        effects: Vec<Matrix<Complex<f64>>>,
+        tolerance: f64,
+    ) -> Result<Measurement, MeasurementError> {
+        let qubits = check_operators(&effects)?;
+        let mut kraus = Vec::with_capacity(effects.len());
+        for (index, effect) in effects.iter().enumerate() {
+            if !effect.is_hermitian(tolerance) {
+                return Err(MeasurementError::NotHermitian(index));
+            }
+
+            let eigen = effect.eigen_hermitian()?;
+            if eigen.eigenvalues.iter().any(|value| *value < -tolerance) {
+                return Err(MeasurementError::NotPositive(index));
+            }
+            let mut root = Matrix::zeros(effect.rows, effect.cols);
+            for (i, value) in eigen.eigenvalues.iter().enumerate() {
+                root.set(i, i, Complex::from(value.max(0.0).sqrt()));
+            }
+            kraus.push(
+                eigen
+                    .eigenvectors
+                    .dot_unchecked(&root)
+                    .dot_unchecked(&eigen.eigenvectors.adjoint()),
+            );
+        }
+        check_complete(effects.into_iter(), qubits, tolerance)?;
+
+        Ok(Measurement {
+            kind: MeasurementKind::Povm,
+            qubits,
+            operators: kraus,
+        })
+    }
+
+    pub fn kind(&self) -> &MeasurementKind {
+        &self.kind
+    }
+
+    /// Number of measured qubits.
+    pub fn qubits(&self) -> usize {
+        self.qubits
+    }
+
+    /// Number of outcomes.
+    pub fn outcomes(&self) -> usize {
+        match &self.kind {
+            MeasurementKind::Basis(_) => 1 << self.qubits,
+            _ => self.operators.len(),
+        }
+    }
+
+    /// The Kraus operators, or `None` for a basis, which does not store them.
+    pub fn kraus_operators(&self) -> Option<&[Matrix<Complex<f64>>]> {
+        match &self.kind {
+            MeasurementKind::Basis(_) => None,
+            _ => Some(&self.operators),
+        }
+    }
+
+    /// The effects `Kᵢ†Kᵢ`, whose expectation values are the outcome probabilities, or `None`
+    /// for a basis.
+    pub fn effect_operators(&self) -> Option<Vec<Matrix<Complex<f64>>>> {
+        let operators = self.kraus_operators()?;
+        Some(
+            operators
+                .iter()
+                .map(|operator| operator.adjoint().dot_unchecked(operator))
+                .collect(),
+        )
+    }
+}
+
+/// Rotation taking the eigenbasis of `pauli` onto the computational basis, or `None` when it
+/// already is the computational basis.
+fn basis_rotation(pauli: Pauli) -> Option<Matrix<Complex<f64>>> {
+    match pauli {
+        Pauli::X => Some(gates::HADAMARD_MATRIX.to_matrix()),
+        Pauli::Y => Some(
+            gates::HADAMARD_MATRIX
+                .to_matrix()
+                .dot_unchecked(&gates::S_MATRIX.adjoint().to_matrix()),
+        ),
+        Pauli::I | Pauli::Z => None,
+    }
+}
+
+/// The basis outcome of the basis state `index`: the bits `bits` of the targets, the first
+/// most significant.
+fn basis_outcome(index: usize, bits: &[usize]) -> usize {
+    bits.iter().fold(0, |outcome, bit| {
+        outcome << 1 | usize::from(index & bit != 0)
+    })
+}
+
+/// Checks that `operators` are non-empty and all square on the same number of qubits, which is
+/// returned.
+fn check_operators(operators: &[Matrix<Complex<f64>>]) -> Result<usize, MeasurementError> {
+    let first = operators.first().ok_or(MeasurementError::Empty)?;
+    if !first.is_square() {
+        return Err(MeasurementError::NotSquare {
+            rows: first.rows,
+            cols: first.cols,
+        });
+    }
+    if !first.rows.is_power_of_two() {
+        return Err(MeasurementError::NotPowerOfTwo(first.rows));
+    }
+
+    for (index, operator) in operators.iter().enumerate() {
+        if operator.rows != first.rows || operator.cols != first.cols {
+            return Err(MeasurementError::DimensionMismatch {
+                index,
+                expected: first.rows,
+                rows: operator.rows,
+                cols: operator.cols,
+            });
+        }
+    }
+    Ok(first.rows.trailing_zeros() as usize)
+}
+
+fn check_complete(
+    effects: impl Iterator<Item = Matrix<Complex<f64>>>,
+    qubits: usize,
+    tolerance: f64,
+) -> Result<(), MeasurementError> {
+    let dimension = 1 << qubits;
+    let sum = effects.fold(Matrix::zeros(dimension, dimension), |sum, effect| {
+        sum.add_unchecked(&effect)
+    });
+    if !sum.approx_eq(&Matrix::identity(dimension), tolerance) {
+        return Err(MeasurementError::Incomplete);
+    }
+    Ok(())
+}
+
+impl<T: Float> QuantumRegister<T>
+where
+    Complex<T>: Float,
+{
+    /// Probability of each outcome of `measurement` on the qubits `targets`, in the order of its
+    /// operators' kronecker factors. The state is left untouched.
+    pub fn measurement_probabilities(
+        &self,
+        measurement: &Measurement,
+        targets: &[usize],
+    ) -> Result<Vec<f64>, RegisterError> {
+        if let MeasurementKind::Basis(paulis) = &measurement.kind {
+            self.check_targets(targets, paulis.len())?;
+            let mut rotated = self.clone();
+            rotated.rotate_basis(paulis, targets, false);
+
+            let bits = self.target_bits(targets);
+            let mut probabilities = vec![0.0; measurement.outcomes()];
+            for (index, probability) in rotated.probabilities().iter().enumerate() {
+                probabilities[basis_outcome(index, &bits)] += probability;
+            }
+            return Ok(probabilities);
+        }
+
+        measurement
+            .operators
+            .iter()
+            .map(|operator| {
+                let mut register = self.clone();
+                register.apply_matrix(&cast_matrix::<f64, T>(operator), targets)?;
+                Ok(register.probabilities().iter().sum())
+            })
+            .collect()
+    }
+
+    /// Leaves the register in the post-measurement state of `outcome` and returns the probability
+    /// of that outcome. Outcomes whose probability is within rounding of zero, relative to the
+    /// norm of the state, are impossible.
+    pub fn collapse(
+        &mut self,
+        measurement: &Measurement,
+        targets: &[usize],
+        outcome: usize,
+    ) -> Result<f64, RegisterError> {
+        let norm: f64 = self.probabilities().iter().sum();
+        self.collapse_above(measurement, targets, outcome, f64::EPSILON * norm)
+    }
+
+    /// [`QuantumRegister::collapse`], rejecting outcomes of probability `cutoff` or less.
+    fn collapse_above(
+        &mut self,
+        measurement: &Measurement,
+        targets: &[usize],
+        outcome: usize,
+        cutoff: f64,
+    ) -> Result<f64, RegisterError> {
+        if outcome >= measurement.outcomes() {
+            return Err(RegisterError::OutcomeOutOfRange {
+                outcome,
+                outcomes: measurement.outcomes(),
+            });
+        }
+
+        let mut register = self.clone();
+        if let MeasurementKind::Basis(paulis) = &measurement.kind {
+            self.check_targets(targets, paulis.len())?;
+            register.rotate_basis(paulis, targets, false);
+            let bits = self.target_bits(targets);
+            for (index, amplitude) in register.amplitudes_mut().iter_mut().enumerate() {
+                if basis_outcome(index, &bits) != outcome {
+                    *amplitude = Complex::zero();
+                }
+            }
+        } else {
+            let operator = cast_matrix::<f64, T>(&measurement.operators[outcome]);
+            register.apply_matrix(&operator, targets)?;
+        }
+
+        let probability: f64 = register.probabilities().iter().sum();
+        if probability <= cutoff {
+            return Err(RegisterError::ImpossibleOutcome(outcome));
+        }
+        let scale = Complex::from(T::from_f64(probability.sqrt().recip()));
+        for amplitude in register.amplitudes_mut() {
+            *amplitude *= scale;
+        }
+        if let MeasurementKind::Basis(paulis) = &measurement.kind {
+            register.rotate_basis(paulis, targets, true);
+        }
+
+        *self = register;
+        Ok(probability)
+    }
+
+    /// Performs `measurement` on the qubits `targets`, returning the sampled outcome and leaving
+    /// the register in its post-measurement state.
+    pub fn measure_with<R: Rng + ?Sized>(
+        &mut self,
+        measurement: &Measurement,
+        targets: &[usize],
+        rng: &mut R,
+    ) -> Result<usize, RegisterError> {
+        let probabilities = self.measurement_probabilities(measurement, targets)?;
+        let outcome = sample_index(&cumulative(&probabilities), rng);
+        // The sampled outcome has a non-zero probability, however small.
+        self.collapse_above(measurement, targets, outcome, 0.0)?;
+        Ok(outcome)
+    }
+
+    /// Counts of each outcome over `shots` repetitions of `measurement` on fresh copies of the
+    /// state, which is left untouched.
+    pub fn sample_measurement<R: Rng + ?Sized>(
+        &self,
+        measurement: &Measurement,
+        targets: &[usize],
+        shots: usize,
+        rng: &mut R,
+    ) -> Result<Vec<usize>, RegisterError> {
+        let cumulative = cumulative(&self.measurement_probabilities(measurement, targets)?);
+        let mut counts = vec![0; measurement.outcomes()];
+        for _ in 0..shots {
+            counts[sample_index(&cumulative, rng)] += 1;
+        }
+        Ok(counts)
+    }
+
+    /// Rotates each of the checked `targets` from the eigenbasis of its factor of `paulis` onto
+    /// the computational basis, or back when `inverse` is set.
+    fn rotate_basis(&mut self, paulis: &[Pauli], targets: &[usize], inverse: bool) {
+        for (pauli, target) in paulis.iter().zip(targets) {
+            if let Some(rotation) = basis_rotation(*pauli) {
+                let rotation = if inverse {
+                    rotation.adjoint()
+                } else {
+                    rotation
+                };
+                self.apply_matrix(&cast_matrix::<f64, T>(&rotation), &[*target])
+                    .expect("basis targets are checked before rotating");
+            }
+        }
+    }
+
+    /// The state-vector index bit of each of `targets`.
+    fn target_bits(&self, targets: &[usize]) -> Vec<usize> {
+        targets
+            .iter()
+            .map(|target| 1 << (self.len() - 1 - target))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ColumnVector, QuantumState, Vector};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn register(state: Vec<Complex<f64>>) -> QuantumRegister {
+        let names = (0..state.len().trailing_zeros())
+            .map(|i| format!("q{}", i))
+            .collect();
+        QuantumRegister::from_state("r", names, ColumnVector::new(state)).unwrap()
+    }
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-12
+    }
+
+    #[test]
+    fn pauli_bases_diagonalise_their_eigenstates() {
+        let half = core::f64::consts::FRAC_1_SQRT_2;
+        let plus = vec![Complex::new(half, 0.0), Complex::new(half, 0.0)];
+        let plus_i = vec![Complex::new(half, 0.0), Complex::new(0.0, half)];
+
+        let x = Measurement::basis(vec![Pauli::X]).unwrap();
+        let y = Measurement::basis(vec![Pauli::Y]).unwrap();
+        let z = Measurement::basis(vec![Pauli::Z]).unwrap();
+        let probabilities = register(plus.clone())
+            .measurement_probabilities(&x, &[0])
+            .unwrap();
+        assert!(close(probabilities[0], 1.0) && close(probabilities[1], 0.0));
+        let probabilities = register(plus_i.clone())
+            .measurement_probabilities(&y, &[0])
+            .unwrap();
+        assert!(close(probabilities[0], 1.0) && close(probabilities[1], 0.0));
+        let probabilities = register(plus).measurement_probabilities(&z, &[0]).unwrap();
+        assert!(close(probabilities[0], 0.5) && close(probabilities[1], 0.5));
+
+        // Measuring |0⟩ in the Y basis and getting -1 leaves |-i⟩.
+        let mut register: QuantumRegister = QuantumRegister::new("q", &["a"]);
+        assert!(close(register.collapse(&y, &[0], 1).unwrap(), 0.5));
+        let minus_i: QuantumState =
+            ColumnVector::new(vec![Complex::new(half, 0.0), Complex::new(0.0, -half)]);
+        let overlap = register
+            .get_state()
+            .adjoint()
+            .dot(&minus_i.transpose())
+            .unwrap();
+        assert!(close(overlap.abs(), 1.0));
+
+        assert_eq!(
+            Measurement::basis(vec![Pauli::X, Pauli::I]).err(),
+            Some(MeasurementError::IdentityBasis(1))
+        );
+        assert!(x.kraus_operators().is_none());
+    }
+
+    #[test]
+    fn sampled_outcomes_collapse_however_unlikely() {
+        // |1⟩ has probability 1e-20: below the rounding of an explicit collapse, but an outcome
+        // that measure_with may still draw.
+        let small = 1e-10;
+        let state = ColumnVector::new(vec![
+            Complex::new((1.0 - small * small).sqrt(), 0.0),
+            Complex::new(small, 0.0),
+        ]);
+        let z = Measurement::basis(vec![Pauli::Z]).unwrap();
+        let mut register: QuantumRegister =
+            QuantumRegister::from_state("q", vec!["a".to_string()], state).unwrap();
+
+        assert_eq!(
+            register.clone().collapse(&z, &[0], 1),
+            Err(RegisterError::ImpossibleOutcome(1))
+        );
+        let probability = register.collapse_above(&z, &[0], 1, 0.0).unwrap();
+        assert!((probability - small * small).abs() < 1e-30);
+        assert!(close(register.get_state()[1].abs(), 1.0));
+    }
+
+    #[test]
+    fn wide_bases_do_not_build_dense_operators() {
+        // |+⟩^⊗16 is the all-zero outcome of the X basis on every qubit.
+        let qubits = 16;
+        let names: Vec<String> = (0..qubits).map(|i| format!("q{}", i)).collect();
+        let mut register: QuantumRegister = QuantumRegister::new("r", &names);
+        for qubit in 0..qubits {
+            register.apply(&gates::HADAMARD, &[qubit]).unwrap();
+        }
+        let plus = register.get_state();
+
+        let basis = Measurement::basis(vec![Pauli::X; qubits]).unwrap();
+        assert_eq!(basis.outcomes(), 1 << qubits);
+        let targets: Vec<usize> = (0..qubits).rev().collect();
+        let probabilities = register
+            .measurement_probabilities(&basis, &targets)
+            .unwrap();
+        assert!(close(probabilities[0], 1.0));
+
+        assert!(close(register.collapse(&basis, &targets, 0).unwrap(), 1.0));
+        assert!(register.get_state().distance(&plus).unwrap() < 1e-10);
+        assert_eq!(
+            register.collapse(&basis, &targets, 5),
+            Err(RegisterError::ImpossibleOutcome(5))
+        );
+        assert!(register.get_state().distance(&plus).unwrap() < 1e-10);
+        assert_eq!(
+            register.measurement_probabilities(&basis, &targets[1..]),
+            Err(RegisterError::ArityMismatch {
+                expected: qubits,
+                actual: qubits - 1
+            })
+        );
+    }
+
+    #[test]
+    fn product_bases_follow_target_order() {
+        // |0⟩ ⊗ |+⟩, measured as X on qubit 1 and Z on qubit 0.
+        let half = core::f64::consts::FRAC_1_SQRT_2;
+        let state = vec![
+            Complex::new(half, 0.0),
+            Complex::new(half, 0.0),
+            Complex::zero(),
+            Complex::zero(),
+        ];
+        let xz = Measurement::basis(vec![Pauli::X, Pauli::Z]).unwrap();
+        assert_eq!(xz.outcomes(), 4);
+        let probabilities = register(state.clone())
+            .measurement_probabilities(&xz, &[1, 0])
+            .unwrap();
+        assert!(close(probabilities[0b00], 1.0));
+
+        let mut register = register(state);
+        let counts = register
+            .sample_measurement(&xz, &[1, 0], 100, &mut StdRng::seed_from_u64(3))
+            .unwrap();
+        assert_eq!(counts, vec![100, 0, 0, 0]);
+        assert_eq!(
+            register.collapse(&xz, &[1, 0], 0b10),
+            Err(RegisterError::ImpossibleOutcome(0b10))
+        );
+        assert_eq!(
+            register.collapse(&xz, &[1, 0], 4),
+            Err(RegisterError::OutcomeOutOfRange {
+                outcome: 4,
+                outcomes: 4
+            })
+        );
+        assert_eq!(
+            register.measurement_probabilities(&xz, &[0]),
+            Err(RegisterError::ArityMismatch {
+                expected: 2,
+                actual: 1
+            })
+        );
+    }
+
+    #[test]
+    fn projector_sets_are_validated() {
+        let one = Complex::one();
+        let mut even = Matrix::zeros(4, 4);
+        even.set(0, 0, one);
+        even.set(3, 3, one);
+        let odd = Matrix::identity(4).subtract(&even).unwrap();
+        let parity = Measurement::projectors(vec![even.clone(), odd], 1e-12).unwrap();
+        assert_eq!(parity.kind(), &MeasurementKind::Projectors);
+
+        // Parity of a Bell state is even, and measuring it does not disturb the state.
+        let half = core::f64::consts::FRAC_1_SQRT_2;
+        let bell = vec![
+            Complex::new(half, 0.0),
+            Complex::zero(),
+            Complex::zero(),
+            Complex::new(half, 0.0),
+        ];
+        let mut state = register(bell);
+        let before = state.get_state();
+        let mut rng = StdRng::seed_from_u64(25);
+        assert_eq!(state.measure_with(&parity, &[0, 1], &mut rng), Ok(0));
+        assert!(state.get_state().distance(&before).unwrap() < 1e-12);
+
+        assert_eq!(
+            Measurement::projectors(vec![even.clone()], 1e-12).err(),
+            Some(MeasurementError::Incomplete)
+        );
+        let scaled = even.scale(Complex::new(0.5, 0.0));
+        assert_eq!(
+            Measurement::projectors(vec![scaled.clone(), scaled], 1e-12).err(),
+            Some(MeasurementError::NotProjector(0))
+        );
+        assert_eq!(
+            Measurement::projectors(vec![even, Matrix::identity(2)], 1e-12).err(),
+            Some(MeasurementError::DimensionMismatch {
+                index: 1,
+                expected: 4,
+                rows: 2,
+                cols: 2
+            })
+        );
+        let error = Measurement::povm(vec![Matrix::identity(2), Matrix::zeros(2, 1)], 1e-12)
+            .err()
+            .unwrap();
+        assert_eq!(error.to_string(), "operator 1 is 2x1, expected 2x2");
+        assert_eq!(
+            Measurement::projectors(vec![Matrix::identity(3)], 1e-12).err(),
+            Some(MeasurementError::NotPowerOfTwo(3))
+        );
+        assert_eq!(
+            Measurement::projectors(vec![], 1e-12).err(),
+            Some(MeasurementError::Empty)
+        );
+    }
+
+    #[test]
+    fn povms_from_kraus_operators_and_effects() {
+        // Amplitude-damping Kraus operators with decay probability 0.36.
+        let (gamma, one, zero) = (0.36_f64, Complex::one(), Complex::zero());
+        let decay = Matrix::new(2, 2, vec![zero, Complex::from(gamma.sqrt()), zero, zero]);
+        let keep = Matrix::new(
+            2,
+            2,
+            vec![one, zero, zero, Complex::from((1.0 - gamma).sqrt())],
+        );
+        let damping = Measurement::povm(vec![keep, decay], 1e-12).unwrap();
+
+        let mut excited = register(vec![zero, one]);
+        let probabilities = excited.measurement_probabilities(&damping, &[0]).unwrap();
+        assert!(close(probabilities[0], 0.64) && close(probabilities[1], 0.36));
+        assert!(close(excited.collapse(&damping, &[0], 1).unwrap(), 0.36));
+        assert!(close(excited.get_state().get(0).abs(), 1.0));
+
+        assert_eq!(
+            Measurement::povm(vec![Matrix::identity(2).scale(Complex::from(0.9))], 1e-12).err(),
+            Some(MeasurementError::Incomplete)
+        );
+
+        // The trine POVM: three effects (2/3)|ψₖ⟩⟨ψₖ| at 120° on the real great circle.
+        let trine: Vec<Matrix<Complex<f64>>> = (0..3)
+            .map(|k| {
+                let angle = 2.0 * core::f64::consts::PI * k as f64 / 3.0;
+                let (c, s) = ((angle / 2.0).cos(), (angle / 2.0).sin());
+                let entries = [c * c, c * s, s * c, s * s].map(|x| Complex::from(2.0 * x / 3.0));
+                Matrix::new(2, 2, entries.to_vec())
+            })
+            .collect();
+        let measurement = Measurement::effects(trine.clone(), 1e-12).unwrap();
+        let effects = measurement.effect_operators().unwrap();
+        for (effect, expected) in effects.iter().zip(&trine) {
+            assert!(effect.approx_eq(expected, 1e-10));
+        }
+
+        let counts = register(vec![one, zero])
+            .sample_measurement(&measurement, &[0], 3000, &mut StdRng::seed_from_u64(9))
+            .unwrap();
+        assert_eq!(counts.iter().sum::<usize>(), 3000);
+        // ⟨0|Eₖ|0⟩ is 2/3, 1/6 and 1/6.
+        for (count, expected) in counts.iter().zip([2000.0, 500.0, 500.0]) {
+            assert!((*count as f64 - expected).abs() < 100.0, "{:?}", counts);
+        }
+
+        let mut negative = trine;
+        negative[0] = negative[0].scale(Complex::from(-1.0));
+        assert_eq!(
+            Measurement::effects(negative, 1e-12).err(),
+            Some(MeasurementError::NotPositive(0))
+        );
+    }
+}
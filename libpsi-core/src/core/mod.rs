@@ -1,9 +1,15 @@
 pub mod circuit;
 pub mod classical_components;
+pub mod composite;
 pub mod gates;
+pub mod instruction;
+pub mod measurement;
 pub mod quantum_components;
 
 pub use circuit::*;
 pub use classical_components::*;
+pub use composite::*;
 pub use gates::*;
+pub use instruction::*;
+pub use measurement::*;
 pub use quantum_components::*;
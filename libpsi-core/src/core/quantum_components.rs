@@ -1,5 +1,11 @@
-use crate::{column_vector, complex, ColumnVector, Complex, Matrix, Vector, VectorMatrix};
+use super::{CircuitError, ClassicalRegister};
+use crate::{
+    ColumnVector, Complex, Float, MathError, Matrix, Numeric, SchurDecomposition, Vector,
+    VectorMatrix,
+};
 use core::{fmt, ops};
+use rand::Rng;
+use std::{borrow::Cow, collections::BTreeMap};
 
 // TODO(Hachem): Redo these macros to work with the new function definition.
 #[macro_export]
@@ -32,63 +38,283 @@ macro_rules! quantum_register {
     };
 }
 
-pub type QuantumState = ColumnVector<Complex<f64>>;
-impl QuantumState {
-    pub fn state_0() -> QuantumState {
-        column_vector![complex!(1.0, 0.0), complex!(0.0, 0.0)]
+/// State vector over the scalar `T`. `f32` halves the memory of the default `f64`, while
+/// [`DoubleDouble`](crate::DoubleDouble) trades memory and speed for accuracy.
+pub type QuantumState<T = f64> = ColumnVector<Complex<T>>;
+
+impl<T: Float> QuantumState<T>
+where
+    Complex<T>: Float,
+{
+    pub fn state_0() -> QuantumState<T> {
+        ColumnVector::new(vec![Complex::one(), Complex::zero()])
     }
 
-    pub fn state_1() -> QuantumState {
-        column_vector![complex!(0.0, 0.0), complex!(1.0, 0.0)]
+    pub fn state_1() -> QuantumState<T> {
+        ColumnVector::new(vec![Complex::zero(), Complex::one()])
     }
 }
 
 #[derive(Clone)]
-pub struct QuantumBit<'a> {
-    state: QuantumState,
-    name: &'a str,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QuantumBit<T: Float = f64>
+where
+    Complex<T>: Float,
+{
+    state: QuantumState<T>,
+    name: String,
 }
 
 #[derive(Clone)]
-pub struct QuantumRegister<'a> {
-    state_vector: QuantumState,
-    name: &'a str,
-    qubits: Vec<QuantumBit<'a>>,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        try_from = "RawQuantumRegister<T>",
+        bound(deserialize = "T: serde::Deserialize<'de>")
+    )
+)]
+pub struct QuantumRegister<T: Float = f64>
+where
+    Complex<T>: Float,
+{
+    state_vector: QuantumState<T>,
+    name: String,
+    qubits: Vec<QuantumBit<T>>,
 }
 
-#[derive(Clone)]
-pub struct QuantumGate<'a> {
-    pub name: &'a str,
-    pub matrix: Matrix<Complex<f64>>,
+/// Unchecked form of [`QuantumRegister`], validated before it is accepted by deserialization.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(bound(deserialize = "T: serde::Deserialize<'de>"))]
+struct RawQuantumRegister<T: Float>
+where
+    Complex<T>: Float,
+{
+    state_vector: QuantumState<T>,
+    name: String,
+    qubits: Vec<QuantumBit<T>>,
+}
+
+#[cfg(feature = "serde")]
+impl<T: Float> TryFrom<RawQuantumRegister<T>> for QuantumRegister<T>
+where
+    Complex<T>: Float,
+{
+    type Error = MathError;
+
+    fn try_from(raw: RawQuantumRegister<T>) -> Result<Self, Self::Error> {
+        check_state_size(raw.qubits.len(), &raw.state_vector)?;
+        Ok(QuantumRegister {
+            state_vector: raw.state_vector,
+            name: raw.name,
+            qubits: raw.qubits,
+        })
+    }
+}
+
+/// Checks that `state` holds the `2^qubits` amplitudes of a register of `qubits` qubits.
+fn check_state_size<T: Float>(qubits: usize, state: &QuantumState<T>) -> Result<(), MathError>
+where
+    Complex<T>: Float,
+{
+    if qubits >= usize::BITS as usize || state.size() != 1 << qubits {
+        return Err(MathError::DimensionMismatch {
+            left: (1usize.checked_shl(qubits as u32).unwrap_or(0), 1),
+            right: state.shape(),
+        });
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GateError {
+    UnknownGate(String),
+    /// The gate takes `expected` angles but was given `actual`.
+    ParameterCount {
+        gate: String,
+        expected: usize,
+        actual: usize,
+    },
+    NotSquare {
+        rows: usize,
+        cols: usize,
+    },
+    /// The matrix dimension is not `2^n` for a number of qubits `n`.
+    NotPowerOfTwo(usize),
+    NotUnitary,
+    /// The recorded inverse does not invert the gate.
+    InverseMismatch,
+    /// Composite gates may only contain gates and barriers.
+    UnsupportedInstruction,
+    /// A composite gate acts on too many qubits for its matrix to be addressed.
+    TooManyQubits(usize),
+    /// A composite body instruction addresses qubits the gate does not have.
+    InvalidBody(CircuitError),
+}
+
+impl fmt::Display for GateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GateError::UnknownGate(name) => write!(f, "unknown gate '{}'", name),
+            GateError::ParameterCount {
+                gate,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "gate {} takes {} parameters but was given {}",
+                gate, expected, actual
+            ),
+            GateError::NotSquare { rows, cols } => {
+                write!(f, "gate matrix is {}x{}, not square", rows, cols)
+            }
+            GateError::NotPowerOfTwo(dimension) => {
+                write!(f, "gate dimension {} is not a power of two", dimension)
+            }
+            GateError::NotUnitary => write!(f, "gate matrix is not unitary"),
+            GateError::InverseMismatch => write!(f, "recorded inverse does not invert the gate"),
+            GateError::UnsupportedInstruction => {
+                write!(f, "composite gates may only contain gates and barriers")
+            }
+            GateError::TooManyQubits(qubits) => {
+                write!(f, "a gate on {} qubits is too large for a matrix", qubits)
+            }
+            GateError::InvalidBody(error) => write!(f, "invalid composite body: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for GateError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegisterError {
+    QubitOutOfRange {
+        qubit: usize,
+        qubits: usize,
+    },
+    DuplicateQubit(usize),
+    /// The gate acts on `expected` qubits but was given `actual` targets.
+    ArityMismatch {
+        expected: usize,
+        actual: usize,
+    },
+    BitOutOfRange {
+        bit: usize,
+        bits: usize,
+    },
+    /// A bitstring that is not one `0` or `1` per qubit of the register.
+    InvalidBitstring(String),
+    OutcomeOutOfRange {
+        outcome: usize,
+        outcomes: usize,
+    },
+    /// The outcome has probability zero, so there is no state to collapse onto.
+    ImpossibleOutcome(usize),
+}
+
+impl fmt::Display for RegisterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RegisterError::QubitOutOfRange { qubit, qubits } => write!(
+                f,
+                "qubit {} is out of range for a register of {} qubits",
+                qubit, qubits
+            ),
+            RegisterError::DuplicateQubit(qubit) => write!(f, "qubit {} is targeted twice", qubit),
+            RegisterError::ArityMismatch { expected, actual } => write!(
+                f,
+                "gate acts on {} qubits but was given {}",
+                expected, actual
+            ),
+            RegisterError::BitOutOfRange { bit, bits } => write!(
+                f,
+                "bit {} is out of range for a register of {} bits",
+                bit, bits
+            ),
+            RegisterError::InvalidBitstring(bitstring) => {
+                write!(f, "'{}' is not a bitstring of the register", bitstring)
+            }
+            RegisterError::OutcomeOutOfRange { outcome, outcomes } => write!(
+                f,
+                "outcome {} is out of range for a measurement of {} outcomes",
+                outcome, outcomes
+            ),
+            RegisterError::ImpossibleOutcome(outcome) => {
+                write!(f, "outcome {} has probability zero", outcome)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RegisterError {}
+
+/// A modifier applied to a gate, like OpenQASM 3's `ctrl @`, `negctrl @`, `inv @` and
+/// `pow(k) @`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GateModifier {
+    /// Adds control qubits that must be `|1⟩`.
+    Control(usize),
+    /// Adds control qubits that must be `|0⟩`.
+    NegativeControl(usize),
+    Inverse,
+    Power(f64),
 }
 
-impl<'a> QuantumBit<'a> {
-    pub fn new(name: &'a str, state: QuantumState) -> QuantumBit<'a> {
-        QuantumBit { name, state }
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QuantumGate<T: Float = f64>
+where
+    Complex<T>: Float,
+{
+    pub name: Cow<'static, str>,
+    pub matrix: Matrix<Complex<T>>,
+    /// Angles the matrix was built from, empty for fixed gates.
+    pub parameters: Vec<f64>,
+    /// Inverse supplied by the gate's author, used instead of the computed adjoint.
+    pub inverse: Option<Matrix<Complex<T>>>,
+    /// Modifiers applied on top of the gate called `name`, innermost first; `matrix` already
+    /// includes them.
+    pub modifiers: Vec<GateModifier>,
+}
+
+impl<T: Float> QuantumBit<T>
+where
+    Complex<T>: Float,
+{
+    pub fn new(name: impl Into<String>, state: QuantumState<T>) -> QuantumBit<T> {
+        QuantumBit {
+            name: name.into(),
+            state,
+        }
     }
 
-    pub fn get_state(&self) -> QuantumState {
+    pub fn get_state(&self) -> QuantumState<T> {
         self.state.clone()
     }
 
-    pub fn get_name(&self) -> &'a str {
-        self.name
+    pub fn get_name(&self) -> &str {
+        &self.name
     }
 }
 
-impl<'a> QuantumRegister<'a> {
-    pub fn new(name: &'a str, names: &[&'a str]) -> QuantumRegister<'a> {
-        let mut bits: Vec<QuantumBit<'a>> = Vec::new();
-        for i in 0..names.len() {
-            bits.push(QuantumBit::new(names[i], QuantumState::state_0()))
+impl<T: Float> QuantumRegister<T>
+where
+    Complex<T>: Float,
+{
+    pub fn new(name: impl Into<String>, names: &[impl AsRef<str>]) -> QuantumRegister<T> {
+        let mut bits: Vec<QuantumBit<T>> = Vec::new();
+        for name in names {
+            bits.push(QuantumBit::new(name.as_ref(), QuantumState::state_0()))
         }
 
         QuantumRegister::from(name, &mut bits)
     }
 
-    pub fn from(name: &'a str, bits: &mut [QuantumBit<'a>]) -> QuantumRegister<'a> {
+    pub fn from(name: impl Into<String>, bits: &mut [QuantumBit<T>]) -> QuantumRegister<T> {
         let mut register = QuantumRegister {
-            name,
+            name: name.into(),
             qubits: bits.to_vec(),
             state_vector: ColumnVector::new(vec![]),
         };
@@ -97,8 +323,28 @@ impl<'a> QuantumRegister<'a> {
         register
     }
 
+    /// Builds a register around an existing, possibly entangled, state vector of
+    /// `2^names.len()` amplitudes. The qubits only carry their names; the state vector is
+    /// authoritative.
+    pub fn from_state(
+        name: impl Into<String>,
+        names: Vec<String>,
+        state: QuantumState<T>,
+    ) -> Result<QuantumRegister<T>, MathError> {
+        check_state_size(names.len(), &state)?;
+
+        Ok(QuantumRegister {
+            name: name.into(),
+            qubits: names
+                .into_iter()
+                .map(|name| QuantumBit::new(name, QuantumState::state_0()))
+                .collect(),
+            state_vector: state,
+        })
+    }
+
     fn update(&mut self) {
-        let matrices: Vec<Matrix<Complex<f64>>> = self
+        let matrices: Vec<Matrix<Complex<T>>> = self
             .qubits
             .iter()
             .map(|qubit| qubit.state.to_matrix())
@@ -111,35 +357,1013 @@ impl<'a> QuantumRegister<'a> {
         self.state_vector = ColumnVector::from_matrix(&new_result);
     }
 
-    pub fn get_bits(&self) -> Vec<QuantumBit> {
+    /// Applies `gate` to the qubits `targets`, in the order of the gate's kronecker factors, by
+    /// updating the amplitudes in place. Each call costs `O(2^n · 2^k)` for a `k`-qubit gate on
+    /// `n` qubits, without building an operator on the whole register.
+    ///
+    /// The per-qubit states of [`QuantumRegister::get_bits`] are not updated; afterwards the
+    /// state vector is the only description of the register.
+    pub fn apply(&mut self, gate: &QuantumGate<T>, targets: &[usize]) -> Result<(), RegisterError> {
+        self.apply_matrix(&gate.matrix, targets)
+    }
+
+    /// [`QuantumRegister::apply`] for any square operator, unitary or not.
+    pub(crate) fn apply_matrix(
+        &mut self,
+        matrix: &Matrix<Complex<T>>,
+        targets: &[usize],
+    ) -> Result<(), RegisterError> {
+        let expected = matrix.rows.trailing_zeros() as usize;
+        if !matrix.is_square() || !matrix.rows.is_power_of_two() {
+            return Err(RegisterError::ArityMismatch {
+                expected,
+                actual: targets.len(),
+            });
+        }
+        self.check_targets(targets, expected)?;
+
+        let qubits = self.len();
+        let bits: Vec<usize> = targets
+            .iter()
+            .map(|target| 1 << (qubits - 1 - target))
+            .collect();
+        apply_operator(matrix, &bits, self.state_vector.as_mut_slice());
+        Ok(())
+    }
+
+    /// Checks that `targets` are `expected` distinct qubits of the register.
+    pub(crate) fn check_targets(
+        &self,
+        targets: &[usize],
+        expected: usize,
+    ) -> Result<(), RegisterError> {
+        if targets.len() != expected {
+            return Err(RegisterError::ArityMismatch {
+                expected,
+                actual: targets.len(),
+            });
+        }
+        let qubits = self.len();
+        for (i, target) in targets.iter().enumerate() {
+            if *target >= qubits {
+                return Err(RegisterError::QubitOutOfRange {
+                    qubit: *target,
+                    qubits,
+                });
+            }
+            if targets[..i].contains(target) {
+                return Err(RegisterError::DuplicateQubit(*target));
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn amplitudes_mut(&mut self) -> &mut [Complex<T>] {
+        self.state_vector.as_mut_slice()
+    }
+
+    /// Born-rule probabilities of the computational basis states, indexed like the state vector.
+    pub fn probabilities(&self) -> Vec<f64> {
+        self.state_vector
+            .as_slice()
+            .iter()
+            .map(|amplitude| amplitude.norm2().to_f64())
+            .collect()
+    }
+
+    /// Probability of measuring `bitstring`, which lists one outcome per qubit with qubit 0
+    /// first, as in the keys of [`QuantumRegister::sample`].
+    pub fn probability_of(&self, bitstring: &str) -> Result<f64, RegisterError> {
+        let invalid = || RegisterError::InvalidBitstring(bitstring.to_string());
+        if bitstring.len() != self.len() || !bitstring.bytes().all(|b| b == b'0' || b == b'1') {
+            return Err(invalid());
+        }
+        let index = usize::from_str_radix(bitstring, 2).map_err(|_| invalid())?;
+        Ok(self.state_vector.get(index).norm2().to_f64())
+    }
+
+    /// Measures `qubit` in the computational basis, collapsing the state onto the outcome and
+    /// renormalising it. Returns `true` for `|1⟩`.
+    pub fn measure<R: Rng + ?Sized>(
+        &mut self,
+        qubit: usize,
+        rng: &mut R,
+    ) -> Result<bool, RegisterError> {
+        let qubits = self.len();
+        if qubit >= qubits {
+            return Err(RegisterError::QubitOutOfRange { qubit, qubits });
+        }
+
+        let bit = 1 << (qubits - 1 - qubit);
+        let one: f64 = self
+            .probabilities()
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| index & bit != 0)
+            .map(|(_, probability)| probability)
+            .sum();
+        let outcome = rng.gen::<f64>() < one;
+
+        let probability = if outcome { one } else { 1.0 - one };
+        let scale = Complex::from(T::from_f64(probability.sqrt().recip()));
+        for (index, amplitude) in self.state_vector.as_mut_slice().iter_mut().enumerate() {
+            *amplitude = if (index & bit != 0) == outcome {
+                *amplitude * scale
+            } else {
+                Complex::zero()
+            };
+        }
+        Ok(outcome)
+    }
+
+    /// [`QuantumRegister::measure`], storing the outcome in bit `bit` of `register`.
+    pub fn measure_into<R: Rng + ?Sized>(
+        &mut self,
+        qubit: usize,
+        register: &mut ClassicalRegister,
+        bit: usize,
+        rng: &mut R,
+    ) -> Result<bool, RegisterError> {
+        if bit >= register.len() {
+            return Err(RegisterError::BitOutOfRange {
+                bit,
+                bits: register.len(),
+            });
+        }
+
+        let outcome = self.measure(qubit, rng)?;
+        register[bit].set_state(outcome);
+        Ok(outcome)
+    }
+
+    /// Measures every qubit, collapsing the register onto a single basis state. The outcomes are
+    /// listed with qubit 0 first.
+    pub fn measure_all<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Vec<bool> {
+        let index = sample_index(&cumulative(&self.probabilities()), rng);
+        let amplitude = self.state_vector.get(index);
+        let phase = amplitude / Complex::from(amplitude.abs());
+
+        let amplitudes = self.state_vector.as_mut_slice();
+        amplitudes.fill(Complex::zero());
+        amplitudes[index] = phase;
+        (0..self.len())
+            .map(|qubit| index & (1 << (self.len() - 1 - qubit)) != 0)
+            .collect()
+    }
+
+    /// Histogram of `shots` computational-basis measurements of the whole register, keyed by
+    /// bitstrings with qubit 0 first. The state is left untouched.
+    pub fn sample<R: Rng + ?Sized>(&self, shots: usize, rng: &mut R) -> BTreeMap<String, usize> {
+        let cumulative = cumulative(&self.probabilities());
+        let mut histogram = BTreeMap::new();
+        for _ in 0..shots {
+            let index = sample_index(&cumulative, rng);
+            let bitstring = format!("{:0width$b}", index, width = self.len());
+            *histogram.entry(bitstring).or_insert(0) += 1;
+        }
+        histogram
+    }
+
+    pub fn get_bits(&self) -> Vec<QuantumBit<T>> {
         self.qubits.clone()
     }
 
-    pub fn get_state(&self) -> QuantumState {
+    /// Number of qubits.
+    pub fn len(&self) -> usize {
+        self.qubits.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.qubits.is_empty()
+    }
+
+    pub fn get_state(&self) -> QuantumState<T> {
         self.state_vector.clone()
     }
 
-    pub fn get_name(&self) -> &'a str {
-        self.name
+    /// The state vector, borrowed rather than cloned as by [`QuantumRegister::get_state`].
+    pub(crate) fn state(&self) -> &QuantumState<T> {
+        &self.state_vector
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
     }
 }
 
-impl<'a> ops::Index<usize> for QuantumRegister<'a> {
-    type Output = QuantumBit<'a>;
+impl<T: Float> ops::Index<usize> for QuantumRegister<T>
+where
+    Complex<T>: Float,
+{
+    type Output = QuantumBit<T>;
 
     fn index(&self, index: usize) -> &Self::Output {
         &self.qubits[index]
     }
 }
 
-impl<'a> ops::IndexMut<usize> for QuantumRegister<'a> {
+impl<T: Float> ops::IndexMut<usize> for QuantumRegister<T>
+where
+    Complex<T>: Float,
+{
     fn index_mut(&mut self, index: usize) -> &mut Self::Output {
         &mut self.qubits[index]
     }
 }
 
-impl<'a> fmt::Display for QuantumGate<'a> {
+pub(crate) fn cast_matrix<T: Float, U: Float>(matrix: &Matrix<Complex<T>>) -> Matrix<Complex<U>>
+where
+    Complex<T>: Float,
+    Complex<U>: Float,
+{
+    let data = matrix
+        .data
+        .iter()
+        .map(|z| {
+            Complex::new(
+                U::from_f64(z.real.to_f64()),
+                U::from_f64(z.imaginary.to_f64()),
+            )
+        })
+        .collect();
+
+    Matrix::new(matrix.rows, matrix.cols, data)
+}
+
+impl<T: Float> QuantumGate<T>
+where
+    Complex<T>: Float,
+{
+    /// Wraps a matrix without validating it; see [`QuantumGate::from_matrix`].
+    pub fn new(name: impl Into<Cow<'static, str>>, matrix: Matrix<Complex<T>>) -> QuantumGate<T> {
+        QuantumGate {
+            name: name.into(),
+            matrix,
+            parameters: Vec::new(),
+            inverse: None,
+            modifiers: Vec::new(),
+        }
+    }
+
+    /// Builds a gate from a square, unitary matrix whose dimension is a power of two.
+    pub fn from_matrix(
+        name: impl Into<Cow<'static, str>>,
+        matrix: Matrix<Complex<T>>,
+        tolerance: f64,
+    ) -> Result<QuantumGate<T>, GateError> {
+        if !matrix.is_square() {
+            return Err(GateError::NotSquare {
+                rows: matrix.rows,
+                cols: matrix.cols,
+            });
+        }
+        if !matrix.rows.is_power_of_two() {
+            return Err(GateError::NotPowerOfTwo(matrix.rows));
+        }
+        if !matrix.is_unitary(tolerance) {
+            return Err(GateError::NotUnitary);
+        }
+
+        Ok(QuantumGate::new(name, matrix))
+    }
+
+    pub fn with_parameters(mut self, parameters: Vec<f64>) -> QuantumGate<T> {
+        self.parameters = parameters;
+        self
+    }
+
+    /// Records an exact inverse, e.g. one known in closed form, after checking that it
+    /// inverts the gate.
+    pub fn with_inverse(
+        mut self,
+        inverse: Matrix<Complex<T>>,
+        tolerance: f64,
+    ) -> Result<QuantumGate<T>, GateError> {
+        let inverts = self
+            .matrix
+            .dot(&inverse)
+            .is_ok_and(|product| product.approx_eq(&Matrix::identity(self.matrix.rows), tolerance));
+        if !inverts {
+            return Err(GateError::InverseMismatch);
+        }
+
+        self.inverse = Some(inverse);
+        Ok(self)
+    }
+
+    /// Number of qubits the gate acts on, controls included.
+    pub fn qubits(&self) -> usize {
+        self.matrix.rows.trailing_zeros() as usize
+    }
+
+    /// Polarity of each control qubit added by modifiers, in qubit order: `true` for a control
+    /// on `|1⟩`, `false` for one on `|0⟩`.
+    pub fn controls(&self) -> Vec<bool> {
+        let mut controls = Vec::new();
+        for modifier in self.modifiers.iter().rev() {
+            match *modifier {
+                GateModifier::Control(count) => controls.extend(std::iter::repeat_n(true, count)),
+                GateModifier::NegativeControl(count) => {
+                    controls.extend(std::iter::repeat_n(false, count))
+                }
+                _ => {}
+            }
+        }
+        controls
+    }
+
+    fn modified(&self, matrix: Matrix<Complex<T>>, modifier: GateModifier) -> QuantumGate<T> {
+        let mut modifiers = self.modifiers.clone();
+        match (modifiers.last_mut(), modifier) {
+            (Some(GateModifier::Control(count)), GateModifier::Control(added))
+            | (Some(GateModifier::NegativeControl(count)), GateModifier::NegativeControl(added)) => {
+                *count += added
+            }
+            (Some(GateModifier::Inverse), GateModifier::Inverse) => {
+                modifiers.pop();
+            }
+            _ => modifiers.push(modifier),
+        }
+
+        QuantumGate {
+            name: self.name.clone(),
+            matrix,
+            parameters: self.parameters.clone(),
+            inverse: None,
+            modifiers,
+        }
+    }
+
+    /// The gate controlled by `count` extra leading qubits, applied when they are all `|1⟩`.
+    pub fn controlled(&self, count: usize) -> QuantumGate<T> {
+        let mut gate = self.modified(
+            controlled_matrix(&self.matrix, count, true),
+            GateModifier::Control(count),
+        );
+        gate.inverse = self
+            .inverse
+            .as_ref()
+            .map(|inverse| controlled_matrix(inverse, count, true));
+        gate
+    }
+
+    /// The gate controlled by `count` extra leading qubits, applied when they are all `|0⟩`.
+    pub fn negative_controlled(&self, count: usize) -> QuantumGate<T> {
+        let mut gate = self.modified(
+            controlled_matrix(&self.matrix, count, false),
+            GateModifier::NegativeControl(count),
+        );
+        gate.inverse = self
+            .inverse
+            .as_ref()
+            .map(|inverse| controlled_matrix(inverse, count, false));
+        gate
+    }
+
+    /// The inverse gate: the recorded inverse if there is one, otherwise the adjoint.
+    pub fn inverse(&self) -> QuantumGate<T> {
+        let matrix = self
+            .inverse
+            .clone()
+            .unwrap_or_else(|| self.matrix.adjoint());
+        let mut gate = self.modified(matrix, GateModifier::Inverse);
+        gate.inverse = Some(self.matrix.clone());
+        gate
+    }
+
+    /// The gate raised to `exponent`. Integer powers are exact products; other powers take the
+    /// principal branch of each eigenvalue's phase, computed in `f64` through the Schur form.
+    pub fn power(&self, exponent: f64) -> Result<QuantumGate<T>, MathError> {
+        self.matrix.require_square()?;
+
+        let matrix = if exponent.fract() == 0.0 && exponent.abs() <= u32::MAX as f64 {
+            let base = if exponent < 0.0 {
+                self.inverse().matrix
+            } else {
+                self.matrix.clone()
+            };
+            integer_power(base, exponent.abs() as u32)
+        } else {
+            let SchurDecomposition { q, t } = cast_matrix::<T, f64>(&self.matrix).schur()?;
+            let mut diagonal = Matrix::zeros(t.rows, t.cols);
+            for i in 0..t.rows {
+                let (radius, mut theta) = t.get(i, i).to_polar();
+                // Eigenvalues at -1 take the phase +π whichever side rounding left them on.
+                if theta <= -core::f64::consts::PI + 1e-12 {
+                    theta = core::f64::consts::PI;
+                }
+                diagonal.set(
+                    i,
+                    i,
+                    Complex::from_polar(radius.powf(exponent), theta * exponent),
+                );
+            }
+            cast_matrix::<f64, T>(&q.dot_unchecked(&diagonal).dot_unchecked(&q.adjoint()))
+        };
+
+        Ok(self.modified(matrix, GateModifier::Power(exponent)))
+    }
+
+    /// Converts the matrix to another scalar type, e.g. to apply the `f64` gates of
+    /// [`gates`](crate::gates) to an `f32` register. Entries keep only the precision of the
+    /// source, so gates meant for double-double studies should be built in that type.
+    pub fn cast<U: Float>(&self) -> QuantumGate<U>
+    where
+        Complex<U>: Float,
+    {
+        QuantumGate {
+            name: self.name.clone(),
+            matrix: cast_matrix(&self.matrix),
+            parameters: self.parameters.clone(),
+            inverse: self.inverse.as_ref().map(cast_matrix),
+            modifiers: self.modifiers.clone(),
+        }
+    }
+}
+
+/// `matrix` with `count` leading controls, active on `|1...1⟩` or, if `!positive`, `|0...0⟩`.
+pub(crate) fn controlled_matrix<T: Float>(
+    matrix: &Matrix<T>,
+    count: usize,
+    positive: bool,
+) -> Matrix<T> {
+    let size = matrix.rows;
+    let offset = if positive {
+        size * ((1 << count) - 1)
+    } else {
+        0
+    };
+    let mut result = Matrix::identity(size << count);
+    for row in 0..size {
+        for col in 0..size {
+            result.set(offset + row, offset + col, matrix.get(row, col));
+        }
+    }
+    result
+}
+
+/// Running sums of `probabilities`, rescaled so the last is exactly one.
+pub(crate) fn cumulative(probabilities: &[f64]) -> Vec<f64> {
+    let mut sum = 0.0;
+    let mut cumulative: Vec<f64> = probabilities
+        .iter()
+        .map(|probability| {
+            sum += probability;
+            sum
+        })
+        .collect();
+    cumulative.iter_mut().for_each(|value| *value /= sum);
+    cumulative
+}
+
+/// Draws an index from a distribution given by its [`cumulative`] sums, skipping indices of
+/// probability zero.
+pub(crate) fn sample_index<R: Rng + ?Sized>(cumulative: &[f64], rng: &mut R) -> usize {
+    let draw = rng.gen::<f64>();
+    cumulative
+        .partition_point(|value| *value <= draw)
+        .min(cumulative.len() - 1)
+}
+
+/// Indices with a zero at every bit of `mask`, in increasing order, for a state of `length`
+/// amplitudes.
+fn cleared_indices(mask: usize, length: usize) -> impl Iterator<Item = usize> {
+    (0..length >> mask.count_ones()).map(move |mut index| {
+        let mut bits = mask;
+        while bits != 0 {
+            let low = bits & bits.wrapping_neg();
+            index = (index & (low - 1)) | ((index & !(low - 1)) << 1);
+            bits &= bits - 1;
+        }
+        index
+    })
+}
+
+/// A `k`-qubit operator whose `i`-th kronecker factor acts on the index bit `bits[i]` of
+/// `amplitudes`, applied in place in `O(2^n · 2^k)`.
+pub(crate) fn apply_operator<T: Float>(matrix: &Matrix<T>, bits: &[usize], amplitudes: &mut [T]) {
+    match bits {
+        [] => {
+            let phase = matrix.get(0, 0);
+            amplitudes
+                .iter_mut()
+                .for_each(|amplitude| *amplitude = phase * *amplitude);
+        }
+        [bit] => apply_one(matrix, *bit, amplitudes),
+        _ => apply_many(matrix, bits, amplitudes),
+    }
+}
+
+/// A single-qubit gate on the qubit with index bit `bit`: each pair of amplitudes differing only
+/// in that bit is multiplied by the 2x2 matrix.
+fn apply_one<T: Float>(matrix: &Matrix<T>, bit: usize, amplitudes: &mut [T]) {
+    let [a, b, c, d] = [
+        matrix.get(0, 0),
+        matrix.get(0, 1),
+        matrix.get(1, 0),
+        matrix.get(1, 1),
+    ];
+    for zero in cleared_indices(bit, amplitudes.len()) {
+        let one = zero | bit;
+        let (x, y) = (amplitudes[zero], amplitudes[one]);
+        amplitudes[zero] = a * x + b * y;
+        amplitudes[one] = c * x + d * y;
+    }
+}
+
+/// A `k`-qubit gate whose `i`-th kronecker factor acts on the index bit `bits[i]`: the `2^k`
+/// amplitudes sharing all other bits are gathered, multiplied and scattered back.
+fn apply_many<T: Float>(matrix: &Matrix<T>, bits: &[usize], amplitudes: &mut [T]) {
+    let size = 1 << bits.len();
+    let offsets: Vec<usize> = (0..size)
+        .map(|local| {
+            bits.iter()
+                .enumerate()
+                .filter(|(k, _)| local & (1 << (bits.len() - 1 - k)) != 0)
+                .map(|(_, bit)| bit)
+                .sum()
+        })
+        .collect();
+
+    let mask = bits.iter().sum();
+    let mut gathered = vec![T::zero(); size];
+    for base in cleared_indices(mask, amplitudes.len()) {
+        for (value, offset) in gathered.iter_mut().zip(&offsets) {
+            *value = amplitudes[base + offset];
+        }
+        for (row, offset) in offsets.iter().enumerate() {
+            let mut sum = T::zero();
+            for (col, value) in gathered.iter().enumerate() {
+                sum += matrix.get(row, col) * *value;
+            }
+            amplitudes[base + offset] = sum;
+        }
+    }
+}
+
+/// `matrix^exponent` by repeated squaring.
+fn integer_power<T: Float>(mut matrix: Matrix<T>, mut exponent: u32) -> Matrix<T> {
+    let mut result = Matrix::identity(matrix.rows);
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = result.dot_unchecked(&matrix);
+        }
+        exponent >>= 1;
+        if exponent > 0 {
+            matrix = matrix.dot_unchecked(&matrix);
+        }
+    }
+    result
+}
+
+impl fmt::Display for GateModifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GateModifier::Control(1) => write!(f, "ctrl"),
+            GateModifier::Control(count) => write!(f, "ctrl({})", count),
+            GateModifier::NegativeControl(1) => write!(f, "negctrl"),
+            GateModifier::NegativeControl(count) => write!(f, "negctrl({})", count),
+            GateModifier::Inverse => write!(f, "inv"),
+            GateModifier::Power(exponent) => write!(f, "pow({})", exponent),
+        }
+    }
+}
+
+/// Prints the gate in OpenQASM 3 form, e.g. `ctrl @ inv @ s` or `rz(0.5)`.
+impl<T: Float> fmt::Display for QuantumGate<T>
+where
+    Complex<T>: Float,
+{
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.name)
+        for modifier in self.modifiers.iter().rev() {
+            write!(f, "{} @ ", modifier)?;
+        }
+        write!(f, "{}", self.name)?;
+        if let Some((first, rest)) = self.parameters.split_first() {
+            write!(f, "({}", first)?;
+            for parameter in rest {
+                write!(f, ", {}", parameter)?;
+            }
+            write!(f, ")")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::random_state;
+    use crate::{gates, DoubleDouble};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    /// Distance from `|0⟩` after applying the Hadamard gate `steps` times.
+    fn hadamard_drift<T: Float>(steps: usize) -> f64
+    where
+        Complex<T>: Float,
+    {
+        let one = Complex::<T>::one();
+        let hadamard =
+            Matrix::new(2, 2, vec![one, one, one, -one]).scale(one / Float::sqrt(one + one));
+        let mut state = QuantumState::<T>::state_0();
+        for _ in 0..steps {
+            state = state.mul_matrix(&hadamard).unwrap();
+        }
+        state.distance(&QuantumState::state_0()).unwrap().to_f64()
+    }
+
+    #[test]
+    fn registers_over_other_scalars() {
+        let register: QuantumRegister<f32> = QuantumRegister::new("q", &["a", "b", "c"]);
+        assert_eq!(register.get_state().size(), 8);
+        assert!(gates::HADAMARD.cast::<f32>().matrix.is_unitary(1e-6));
+        assert_eq!(register.get_state().get(0), Complex::new(1.0_f32, 0.0));
+
+        let register: QuantumRegister<DoubleDouble> = QuantumRegister::new("q", &["a", "b"]);
+        assert_eq!(register.get_state().norm_sqr(), DoubleDouble::from(1.0));
+        assert_eq!(register[1].get_name(), "b");
+    }
+
+    #[test]
+    fn gates_from_checked_matrices() {
+        let one = Complex::new(1.0, 0.0);
+        let swap = gates::SWAP_MATRIX.to_matrix();
+        let gate = QuantumGate::from_matrix("swap", swap.clone(), 1e-12).unwrap();
+        assert_eq!(gate.qubits(), 2);
+        assert!(gate
+            .clone()
+            .with_inverse(swap, 0.0)
+            .unwrap()
+            .inverse
+            .is_some());
+        assert_eq!(
+            gate.with_inverse(Matrix::identity(4), 1e-12).err(),
+            Some(GateError::InverseMismatch)
+        );
+
+        assert_eq!(
+            QuantumGate::<f64>::from_matrix("r", Matrix::zeros(2, 4), 1e-12).err(),
+            Some(GateError::NotSquare { rows: 2, cols: 4 })
+        );
+        assert_eq!(
+            QuantumGate::<f64>::from_matrix("three", Matrix::identity(3), 1e-12).err(),
+            Some(GateError::NotPowerOfTwo(3))
+        );
+        assert_eq!(
+            QuantumGate::from_matrix("twice", Matrix::identity(2).scale(one + one), 1e-12).err(),
+            Some(GateError::NotUnitary)
+        );
+        assert_eq!(
+            QuantumGate::new("phase", Matrix::identity(1).scale(one)).qubits(),
+            0
+        );
+    }
+
+    #[test]
+    fn controlled_gates() {
+        use crate::gates::StandardGate;
+
+        let cx = gates::PAULI_X.controlled(1);
+        assert!(cx.matrix.approx_eq(&gates::CNOT.matrix, 0.0));
+        assert!(gates::PAULI_X
+            .controlled(1)
+            .controlled(1)
+            .matrix
+            .approx_eq(&StandardGate::Ccx.matrix(), 0.0));
+        assert!(gates::HADAMARD
+            .controlled(1)
+            .matrix
+            .approx_eq(&StandardGate::Ch.matrix(), 0.0));
+
+        let x = gates::PAULI_X.matrix.kronecker(&Matrix::identity(2));
+        let flipped = x.dot(&gates::CNOT.matrix).unwrap().dot(&x).unwrap();
+        let negative = gates::PAULI_X.negative_controlled(1);
+        assert!(negative.matrix.approx_eq(&flipped, 0.0));
+
+        let mixed = gates::HADAMARD.negative_controlled(1).controlled(2);
+        assert_eq!(mixed.controls(), vec![true, true, false]);
+        assert_eq!(mixed.qubits(), 4);
+        assert_eq!(mixed.to_string(), "ctrl(2) @ negctrl @ h");
+    }
+
+    #[test]
+    fn inverse_gates() {
+        use crate::gates::StandardGate;
+
+        let s = StandardGate::S.to_gate();
+        let inverse = s.inverse();
+        assert!(inverse.matrix.approx_eq(&StandardGate::Sdg.matrix(), 0.0));
+        assert_eq!(inverse.to_string(), "inv @ s");
+        assert!(inverse.inverse().modifiers.is_empty());
+        assert!(inverse.inverse().matrix.approx_eq(&s.matrix, 0.0));
+
+        let controlled = s.controlled(1).inverse();
+        assert_eq!(controlled.to_string(), "inv @ ctrl @ s");
+        let product = controlled.matrix.dot(&s.controlled(1).matrix).unwrap();
+        assert!(product.approx_eq(&Matrix::identity(4), 1e-15));
+
+        // A recorded inverse is used as is, and survives control.
+        let recorded = QuantumGate::new("t", StandardGate::T.matrix())
+            .with_inverse(StandardGate::Tdg.matrix(), 1e-12)
+            .unwrap();
+        let inverse = recorded.controlled(1).inverse();
+        assert!(inverse
+            .matrix
+            .approx_eq(&StandardGate::Tdg.to_gate().controlled(1).matrix, 0.0));
+    }
+
+    #[test]
+    fn gate_powers() {
+        use crate::gates::StandardGate;
+
+        let power = |gate: StandardGate, exponent| gate.to_gate().power(exponent).unwrap();
+        assert!(power(StandardGate::S, 0.5)
+            .matrix
+            .approx_eq(&StandardGate::T.matrix(), 1e-12));
+        assert!(power(StandardGate::X, 0.5)
+            .matrix
+            .approx_eq(&StandardGate::Sx.matrix(), 1e-12));
+        assert!(power(StandardGate::Z, 0.5)
+            .matrix
+            .approx_eq(&StandardGate::S.matrix(), 1e-12));
+        assert!(power(StandardGate::Rz(0.8), 1.5)
+            .matrix
+            .approx_eq(&StandardGate::Rz(1.2).matrix(), 1e-12));
+        assert!(power(StandardGate::Cx, 0.5).matrix.approx_eq(
+            &controlled_matrix(&gates::SX_MATRIX.to_matrix(), 1, true),
+            1e-12
+        ));
+
+        assert!(power(StandardGate::H, 2.0)
+            .matrix
+            .approx_eq(&Matrix::identity(2), 1e-15));
+        assert!(power(StandardGate::T, -3.0).matrix.approx_eq(
+            &StandardGate::T
+                .matrix()
+                .adjoint()
+                .dot(&StandardGate::Sdg.matrix())
+                .unwrap(),
+            1e-15
+        ));
+        assert_eq!(power(StandardGate::T, 0.25).to_string(), "pow(0.25) @ t");
+    }
+
+    fn random_register(rng: &mut StdRng, qubits: usize) -> QuantumRegister {
+        let names = (0..qubits).map(|i| format!("q{}", i)).collect();
+        QuantumRegister::from_state("r", names, random_state(rng, qubits)).unwrap()
+    }
+
+    /// Reference: the gate embedded in the full operator, its factors permuted onto `targets`.
+    fn full_operator(
+        gate: &Matrix<Complex<f64>>,
+        targets: &[usize],
+        qubits: usize,
+    ) -> Matrix<Complex<f64>> {
+        let bit = |qubit: usize| 1 << (qubits - 1 - qubit);
+        let target_mask: usize = targets.iter().map(|qubit| bit(*qubit)).sum();
+        // Spreads the bits of a gate index over the target positions of a basis index.
+        let scatter = |local: usize| {
+            targets
+                .iter()
+                .enumerate()
+                .filter(|(k, _)| local & (1 << (targets.len() - 1 - k)) != 0)
+                .map(|(_, qubit)| bit(*qubit))
+                .sum::<usize>()
+        };
+
+        let dimension = 1 << qubits;
+        let mut operator = Matrix::zeros(dimension, dimension);
+        for column in 0..dimension {
+            let local = (0..1 << targets.len())
+                .find(|local| scatter(*local) == column & target_mask)
+                .unwrap();
+            for row in 0..1 << targets.len() {
+                operator.set(
+                    column & !target_mask | scatter(row),
+                    column,
+                    gate.get(row, local),
+                );
+            }
+        }
+        operator
+    }
+
+    #[test]
+    fn gates_apply_in_place_to_any_qubits() {
+        use crate::gates::StandardGate;
+
+        let mut rng = StdRng::seed_from_u64(23);
+        let cases: Vec<(QuantumGate, Vec<usize>)> = vec![
+            (gates::HADAMARD.clone(), vec![0]),
+            (StandardGate::U3(0.3, 1.2, -0.4).to_gate(), vec![3]),
+            (StandardGate::Ry(0.9).to_gate(), vec![1]),
+            (gates::CNOT.clone(), vec![3, 1]),
+            (StandardGate::ISwap.to_gate(), vec![0, 2]),
+            (StandardGate::Ccx.to_gate(), vec![2, 0, 3]),
+            (
+                StandardGate::Cswap.to_gate().controlled(1),
+                vec![1, 3, 0, 2],
+            ),
+            (StandardGate::GlobalPhase(0.7).to_gate(), vec![]),
+        ];
+
+        for (gate, targets) in cases {
+            let mut register = random_register(&mut rng, 4);
+            let expected = register
+                .get_state()
+                .mul_matrix(&full_operator(&gate.matrix, &targets, 4))
+                .unwrap();
+            register.apply(&gate, &targets).unwrap();
+            assert!(
+                register.get_state().distance(&expected).unwrap() < 1e-12,
+                "{}",
+                gate
+            );
+        }
+    }
+
+    #[test]
+    fn bell_state_from_applied_gates() {
+        let mut register: QuantumRegister = QuantumRegister::new("q", &["a", "b"]);
+        register.apply(&gates::HADAMARD, &[0]).unwrap();
+        register.apply(&gates::CNOT, &[0, 1]).unwrap();
+
+        let half = core::f64::consts::FRAC_1_SQRT_2;
+        let state = register.get_state();
+        assert!((state.get(0) - Complex::new(half, 0.0)).abs() < 1e-15);
+        assert!((state.get(3) - Complex::new(half, 0.0)).abs() < 1e-15);
+        assert_eq!(state.get(1), Complex::zero());
+
+        assert_eq!(
+            register.apply(&gates::CNOT, &[0]),
+            Err(RegisterError::ArityMismatch {
+                expected: 2,
+                actual: 1
+            })
+        );
+        assert_eq!(
+            register.apply(&gates::HADAMARD, &[2]),
+            Err(RegisterError::QubitOutOfRange {
+                qubit: 2,
+                qubits: 2
+            })
+        );
+        assert_eq!(
+            register.apply(&gates::CNOT, &[1, 1]),
+            Err(RegisterError::DuplicateQubit(1))
+        );
+    }
+
+    /// `(|000⟩ + |011⟩ + i|101⟩ - |110⟩) / 2`: every outcome has even parity.
+    fn parity_state() -> QuantumRegister {
+        let mut amplitudes = vec![Complex::zero(); 8];
+        amplitudes[0b000] = Complex::new(0.5, 0.0);
+        amplitudes[0b011] = Complex::new(0.5, 0.0);
+        amplitudes[0b101] = Complex::new(0.0, 0.5);
+        amplitudes[0b110] = Complex::new(-0.5, 0.0);
+        let names = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        QuantumRegister::from_state("r", names, ColumnVector::new(amplitudes)).unwrap()
+    }
+
+    #[test]
+    fn probabilities_follow_the_born_rule() {
+        let register = parity_state();
+        let probabilities = register.probabilities();
+        assert_eq!(probabilities.len(), 8);
+        assert!((probabilities[0b101] - 0.25).abs() < 1e-15);
+        assert_eq!(probabilities[0b001], 0.0);
+
+        assert!((register.probability_of("110").unwrap() - 0.25).abs() < 1e-15);
+        assert_eq!(register.probability_of("100").unwrap(), 0.0);
+        for invalid in ["11", "1100", "1x0", "+1", "+10"] {
+            assert_eq!(
+                register.probability_of(invalid),
+                Err(RegisterError::InvalidBitstring(invalid.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn measurement_collapses_and_renormalises() {
+        let mut rng = StdRng::seed_from_u64(24);
+        let half = core::f64::consts::FRAC_1_SQRT_2;
+        for _ in 0..20 {
+            let mut register = parity_state();
+            let first = register.measure(0, &mut rng).unwrap();
+            assert!((register.get_state().norm().abs() - 1.0).abs() < 1e-12);
+            let kept = if first {
+                [0b101, 0b110]
+            } else {
+                [0b000, 0b011]
+            };
+            for index in kept {
+                assert!((register.get_state().get(index).abs() - half).abs() < 1e-12);
+            }
+
+            // The remaining qubits are correlated by parity.
+            let second = register.measure(1, &mut rng).unwrap();
+            let third = register.measure(2, &mut rng).unwrap();
+            assert!(!(first ^ second ^ third));
+            let bitstring = format!("{}{}{}", first as u8, second as u8, third as u8);
+            assert!((register.probability_of(&bitstring).unwrap() - 1.0).abs() < 1e-12);
+        }
+
+        assert_eq!(
+            parity_state().measure(3, &mut rng),
+            Err(RegisterError::QubitOutOfRange {
+                qubit: 3,
+                qubits: 3
+            })
+        );
+    }
+
+    #[test]
+    fn measure_all_and_classical_results() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut register = parity_state();
+        let outcomes = register.measure_all(&mut rng);
+        assert_eq!(outcomes.len(), 3);
+        assert_eq!(outcomes.iter().filter(|outcome| **outcome).count() % 2, 0);
+        let bitstring: String = outcomes
+            .iter()
+            .map(|o| if *o { '1' } else { '0' })
+            .collect();
+        assert!((register.probability_of(&bitstring).unwrap() - 1.0).abs() < 1e-12);
+
+        let mut register: QuantumRegister = QuantumRegister::new("q", &["a", "b"]);
+        register.apply(&gates::PAULI_X, &[1]).unwrap();
+        let mut classical = ClassicalRegister::new("c", &["c0", "c1"]);
+        assert!(register
+            .measure_into(1, &mut classical, 0, &mut rng)
+            .unwrap());
+        assert!(!register
+            .measure_into(0, &mut classical, 1, &mut rng)
+            .unwrap());
+        assert!(classical[0].get_state());
+        assert!(!classical[1].get_state());
+        assert_eq!(
+            register.measure_into(0, &mut classical, 2, &mut rng),
+            Err(RegisterError::BitOutOfRange { bit: 2, bits: 2 })
+        );
+    }
+
+    #[test]
+    fn sampling_is_reproducible_and_leaves_the_state() {
+        let register = parity_state();
+        let histogram = register.sample(4000, &mut StdRng::seed_from_u64(1));
+        assert_eq!(
+            histogram,
+            register.sample(4000, &mut StdRng::seed_from_u64(1))
+        );
+        assert_eq!(histogram.values().sum::<usize>(), 4000);
+        assert_eq!(
+            histogram.keys().collect::<Vec<_>>(),
+            vec!["000", "011", "101", "110"]
+        );
+        for count in histogram.values() {
+            assert!((*count as f64 - 1000.0).abs() < 100.0, "{:?}", histogram);
+        }
+        assert!(
+            register
+                .get_state()
+                .distance(&parity_state().get_state())
+                .unwrap()
+                < 1e-15
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn registers_reject_mis_sized_states_on_deserialize() {
+        let register: QuantumRegister = QuantumRegister::new("q", &["a", "b"]);
+        let json = serde_json::to_string(&register).unwrap();
+        let decoded: QuantumRegister = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.len(), 2);
+        assert!(decoded.get_state().distance(&register.get_state()).unwrap() < 1e-15);
+
+        // Two qubits but only two amplitudes.
+        let truncated = r#"{"state_vector":[{"real":1.0,"imaginary":0.0},{"real":0.0,"imaginary":0.0}],
+            "name":"q","qubits":[
+            {"state":[{"real":1.0,"imaginary":0.0},{"real":0.0,"imaginary":0.0}],"name":"a"},
+            {"state":[{"real":1.0,"imaginary":0.0},{"real":0.0,"imaginary":0.0}],"name":"b"}]}"#;
+        let error = match serde_json::from_str::<QuantumRegister>(truncated) {
+            Ok(_) => panic!("a mis-sized state vector was accepted"),
+            Err(error) => error,
+        };
+        assert!(
+            error.to_string().contains("dimension mismatch"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn double_double_reduces_round_off_drift() {
+        let double = hadamard_drift::<f64>(1000);
+        let double_double = hadamard_drift::<DoubleDouble>(1000);
+
+        assert!(hadamard_drift::<f32>(1000) < 1e-4);
+        assert!(double < 1e-12);
+        assert!(double_double < 1e-28);
     }
 }
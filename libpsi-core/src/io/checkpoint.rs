@@ -0,0 +1,980 @@
+//! Versioned binary checkpoints of state vectors.
+//!
+//! A checkpoint is a header followed by a body:
+//!
+//! ```text
+//! header  magic      8 bytes   "PSISTATE"
+//!         version    u16       currently 1
+//!         flags      u8        bit 0: big-endian amplitudes, bit 1: zlib-compressed body
+//!         precision  u8        bytes per real component: 4, 8 or 16 (double-double)
+//!         qubits     u32
+//!         name       u32 length + UTF-8 bytes
+//!         qubit      u32 length + UTF-8 bytes, once per qubit
+//!         checksum   u32       CRC-32 of the header bytes above
+//! body    amplitudes 2^qubits × (real, imaginary) in the flagged byte order
+//!         checksum   u32       CRC-32 of the uncompressed amplitude bytes
+//! ```
+//!
+//! Header integers are little-endian. When the body is compressed, both the amplitudes and their
+//! checksum are part of the zlib stream.
+
+use super::crc32::Crc32;
+use crate::{Complex, DoubleDouble, Float, QuantumRegister, QuantumState, Vector};
+use core::{fmt, marker::PhantomData};
+use std::io::{self, Read, Write};
+
+const MAGIC: &[u8; 8] = b"PSISTATE";
+const VERSION: u16 = 1;
+const FLAG_BIG_ENDIAN: u8 = 1;
+const FLAG_COMPRESSED: u8 = 1 << 1;
+/// Upper bound on a single name, so that a corrupt length cannot trigger a huge allocation.
+const MAX_NAME_LENGTH: u32 = 1 << 16;
+/// Amplitudes decoded per read by the convenience readers.
+const CHUNK: usize = 1 << 12;
+
+#[derive(Debug)]
+pub enum CheckpointError {
+    Io(io::Error),
+    /// The input does not start with the checkpoint magic bytes.
+    NotACheckpoint,
+    UnsupportedVersion(u16),
+    InvalidHeader(&'static str),
+    HeaderChecksum,
+    DataChecksum,
+    PrecisionMismatch {
+        stored: Precision,
+        requested: Precision,
+    },
+    /// The number of amplitudes written or supplied does not match the header.
+    LengthMismatch {
+        expected: u64,
+        actual: u64,
+    },
+    /// The body is compressed but the `compression` feature is disabled, or a compressed file was
+    /// opened with the memory-mapped reader.
+    CompressionUnsupported,
+}
+
+impl fmt::Display for CheckpointError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CheckpointError::Io(error) => write!(f, "checkpoint i/o error: {}", error),
+            CheckpointError::NotACheckpoint => write!(f, "not a state checkpoint"),
+            CheckpointError::UnsupportedVersion(version) => {
+                write!(f, "unsupported checkpoint version {}", version)
+            }
+            CheckpointError::InvalidHeader(reason) => {
+                write!(f, "invalid checkpoint header: {}", reason)
+            }
+            CheckpointError::HeaderChecksum => write!(f, "checkpoint header checksum mismatch"),
+            CheckpointError::DataChecksum => write!(f, "checkpoint amplitude checksum mismatch"),
+            CheckpointError::PrecisionMismatch { stored, requested } => write!(
+                f,
+                "checkpoint stores {:?} amplitudes but {:?} were requested",
+                stored, requested
+            ),
+            CheckpointError::LengthMismatch { expected, actual } => write!(
+                f,
+                "checkpoint expects {} amplitudes, got {}",
+                expected, actual
+            ),
+            CheckpointError::CompressionUnsupported => {
+                write!(f, "compressed checkpoints are not supported here")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CheckpointError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CheckpointError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for CheckpointError {
+    fn from(error: io::Error) -> Self {
+        CheckpointError::Io(error)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Precision {
+    Single,
+    Double,
+    DoubleDouble,
+}
+
+impl Precision {
+    /// Bytes per real component.
+    pub fn bytes(self) -> usize {
+        match self {
+            Precision::Single => 4,
+            Precision::Double => 8,
+            Precision::DoubleDouble => 16,
+        }
+    }
+
+    fn from_bytes(bytes: u8) -> Option<Precision> {
+        match bytes {
+            4 => Some(Precision::Single),
+            8 => Some(Precision::Double),
+            16 => Some(Precision::DoubleDouble),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ByteOrder {
+    Little,
+    Big,
+}
+
+impl ByteOrder {
+    pub fn native() -> ByteOrder {
+        if cfg!(target_endian = "big") {
+            ByteOrder::Big
+        } else {
+            ByteOrder::Little
+        }
+    }
+}
+
+/// Scalars that can be stored in a checkpoint.
+pub trait CheckpointScalar: Float {
+    const PRECISION: Precision;
+
+    fn encode(self, order: ByteOrder, output: &mut Vec<u8>);
+    /// Decodes `PRECISION.bytes()` bytes.
+    fn decode(bytes: &[u8], order: ByteOrder) -> Self;
+}
+
+macro_rules! impl_checkpoint_scalar {
+    ($($t:ty => $precision:ident),*) => {
+        $(
+            impl CheckpointScalar for $t {
+                const PRECISION: Precision = Precision::$precision;
+
+                fn encode(self, order: ByteOrder, output: &mut Vec<u8>) {
+                    match order {
+                        ByteOrder::Little => output.extend_from_slice(&self.to_le_bytes()),
+                        ByteOrder::Big => output.extend_from_slice(&self.to_be_bytes()),
+                    }
+                }
+
+                fn decode(bytes: &[u8], order: ByteOrder) -> Self {
+                    let bytes = bytes.try_into().expect("component width matches the precision");
+                    match order {
+                        ByteOrder::Little => <$t>::from_le_bytes(bytes),
+                        ByteOrder::Big => <$t>::from_be_bytes(bytes),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_checkpoint_scalar!(f32 => Single, f64 => Double);
+
+impl CheckpointScalar for DoubleDouble {
+    const PRECISION: Precision = Precision::DoubleDouble;
+
+    fn encode(self, order: ByteOrder, output: &mut Vec<u8>) {
+        self.hi().encode(order, output);
+        self.lo().encode(order, output);
+    }
+
+    fn decode(bytes: &[u8], order: ByteOrder) -> Self {
+        DoubleDouble::new(
+            f64::decode(&bytes[..8], order),
+            f64::decode(&bytes[8..], order),
+        )
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CheckpointHeader {
+    pub version: u16,
+    pub name: String,
+    pub qubit_names: Vec<String>,
+    /// Overwritten by [`CheckpointWriter`] with the precision of its scalar type.
+    pub precision: Precision,
+    pub byte_order: ByteOrder,
+    pub compressed: bool,
+}
+
+impl CheckpointHeader {
+    /// Header for a state of `qubit_names.len()` qubits, stored uncompressed in native byte order.
+    pub fn new(name: impl Into<String>, qubit_names: Vec<String>) -> CheckpointHeader {
+        CheckpointHeader {
+            version: VERSION,
+            name: name.into(),
+            qubit_names,
+            precision: Precision::Double,
+            byte_order: ByteOrder::native(),
+            compressed: false,
+        }
+    }
+
+    pub fn for_register<T: Float>(register: &QuantumRegister<T>) -> CheckpointHeader
+    where
+        Complex<T>: Float,
+    {
+        let names = register
+            .get_bits()
+            .iter()
+            .map(|bit| bit.get_name().to_string())
+            .collect();
+        CheckpointHeader::new(register.get_name(), names)
+    }
+
+    pub fn with_byte_order(mut self, byte_order: ByteOrder) -> CheckpointHeader {
+        self.byte_order = byte_order;
+        self
+    }
+
+    /// Compresses the body with zlib. Writing fails unless the `compression` feature is enabled.
+    pub fn with_compression(mut self, compressed: bool) -> CheckpointHeader {
+        self.compressed = compressed;
+        self
+    }
+
+    pub fn qubits(&self) -> usize {
+        self.qubit_names.len()
+    }
+
+    pub fn amplitudes(&self) -> u64 {
+        1 << self.qubits()
+    }
+
+    fn amplitude_width(&self) -> usize {
+        2 * self.precision.bytes()
+    }
+
+    fn encode(&self) -> Result<Vec<u8>, CheckpointError> {
+        if self.qubits() >= 64 {
+            return Err(CheckpointError::InvalidHeader("too many qubits"));
+        }
+
+        let mut flags = 0;
+        if self.byte_order == ByteOrder::Big {
+            flags |= FLAG_BIG_ENDIAN;
+        }
+        if self.compressed {
+            flags |= FLAG_COMPRESSED;
+        }
+
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&VERSION.to_le_bytes());
+        bytes.push(flags);
+        bytes.push(self.precision.bytes() as u8);
+        bytes.extend_from_slice(&(self.qubits() as u32).to_le_bytes());
+        for name in std::iter::once(&self.name).chain(&self.qubit_names) {
+            if name.len() > MAX_NAME_LENGTH as usize {
+                return Err(CheckpointError::InvalidHeader("name too long"));
+            }
+            bytes.extend_from_slice(&(name.len() as u32).to_le_bytes());
+            bytes.extend_from_slice(name.as_bytes());
+        }
+
+        let checksum = Crc32::checksum(&bytes);
+        bytes.extend_from_slice(&checksum.to_le_bytes());
+        Ok(bytes)
+    }
+
+    /// Parses and verifies a header, returning it with its length in bytes.
+    fn decode<R: Read>(reader: &mut R) -> Result<(CheckpointHeader, usize), CheckpointError> {
+        let mut reader = ChecksumReader {
+            inner: reader,
+            crc: Crc32::new(),
+            length: 0,
+        };
+
+        let mut magic = [0; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(CheckpointError::NotACheckpoint);
+        }
+
+        let version = u16::from_le_bytes(read_array(&mut reader)?);
+        if version == 0 || version > VERSION {
+            return Err(CheckpointError::UnsupportedVersion(version));
+        }
+
+        let [flags, precision] = read_array(&mut reader)?;
+        let precision = Precision::from_bytes(precision)
+            .ok_or(CheckpointError::InvalidHeader("unknown precision"))?;
+        let qubits = u32::from_le_bytes(read_array(&mut reader)?);
+        if qubits >= 64 {
+            return Err(CheckpointError::InvalidHeader("too many qubits"));
+        }
+
+        let name = read_name(&mut reader)?;
+        let qubit_names = (0..qubits)
+            .map(|_| read_name(&mut reader))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let expected = reader.crc.finish();
+        let length = reader.length + 4;
+        if u32::from_le_bytes(read_array(reader.inner)?) != expected {
+            return Err(CheckpointError::HeaderChecksum);
+        }
+
+        let header = CheckpointHeader {
+            version,
+            name,
+            qubit_names,
+            precision,
+            byte_order: if flags & FLAG_BIG_ENDIAN != 0 {
+                ByteOrder::Big
+            } else {
+                ByteOrder::Little
+            },
+            compressed: flags & FLAG_COMPRESSED != 0,
+        };
+        Ok((header, length))
+    }
+}
+
+/// Passes reads through while checksumming and counting the bytes.
+struct ChecksumReader<'r, R: Read> {
+    inner: &'r mut R,
+    crc: Crc32,
+    length: usize,
+}
+
+impl<R: Read> Read for ChecksumReader<'_, R> {
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buffer)?;
+        self.crc.update(&buffer[..read]);
+        self.length += read;
+        Ok(read)
+    }
+}
+
+fn read_array<const N: usize, R: Read>(reader: &mut R) -> io::Result<[u8; N]> {
+    let mut bytes = [0; N];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+fn read_name<R: Read>(reader: &mut R) -> Result<String, CheckpointError> {
+    let length = u32::from_le_bytes(read_array(reader)?);
+    if length > MAX_NAME_LENGTH {
+        return Err(CheckpointError::InvalidHeader("name too long"));
+    }
+
+    let mut bytes = vec![0; length as usize];
+    reader.read_exact(&mut bytes)?;
+    String::from_utf8(bytes).map_err(|_| CheckpointError::InvalidHeader("name is not UTF-8"))
+}
+
+enum Sink<W: Write> {
+    Plain(W),
+    #[cfg(feature = "compression")]
+    Deflate(flate2::write::ZlibEncoder<W>),
+}
+
+impl<W: Write> Sink<W> {
+    fn new(writer: W, compressed: bool) -> Result<Sink<W>, CheckpointError> {
+        if compressed {
+            Sink::deflate(writer)
+        } else {
+            Ok(Sink::Plain(writer))
+        }
+    }
+
+    #[cfg(feature = "compression")]
+    fn deflate(writer: W) -> Result<Sink<W>, CheckpointError> {
+        let encoder = flate2::write::ZlibEncoder::new(writer, flate2::Compression::default());
+        Ok(Sink::Deflate(encoder))
+    }
+
+    #[cfg(not(feature = "compression"))]
+    fn deflate(_: W) -> Result<Sink<W>, CheckpointError> {
+        Err(CheckpointError::CompressionUnsupported)
+    }
+
+    /// Ends the compressed stream, if any, and returns the underlying writer.
+    fn finish(self) -> io::Result<W> {
+        match self {
+            Sink::Plain(writer) => Ok(writer),
+            #[cfg(feature = "compression")]
+            Sink::Deflate(encoder) => encoder.finish(),
+        }
+    }
+}
+
+impl<W: Write> Write for Sink<W> {
+    fn write(&mut self, buffer: &[u8]) -> io::Result<usize> {
+        match self {
+            Sink::Plain(writer) => writer.write(buffer),
+            #[cfg(feature = "compression")]
+            Sink::Deflate(writer) => writer.write(buffer),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Sink::Plain(writer) => writer.flush(),
+            #[cfg(feature = "compression")]
+            Sink::Deflate(writer) => writer.flush(),
+        }
+    }
+}
+
+enum Source<R: Read> {
+    Plain(R),
+    #[cfg(feature = "compression")]
+    Deflate(flate2::read::ZlibDecoder<R>),
+}
+
+impl<R: Read> Source<R> {
+    fn new(reader: R, compressed: bool) -> Result<Source<R>, CheckpointError> {
+        if compressed {
+            Source::inflate(reader)
+        } else {
+            Ok(Source::Plain(reader))
+        }
+    }
+
+    #[cfg(feature = "compression")]
+    fn inflate(reader: R) -> Result<Source<R>, CheckpointError> {
+        Ok(Source::Deflate(flate2::read::ZlibDecoder::new(reader)))
+    }
+
+    #[cfg(not(feature = "compression"))]
+    fn inflate(_: R) -> Result<Source<R>, CheckpointError> {
+        Err(CheckpointError::CompressionUnsupported)
+    }
+}
+
+impl<R: Read> Read for Source<R> {
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Source::Plain(reader) => reader.read(buffer),
+            #[cfg(feature = "compression")]
+            Source::Deflate(reader) => reader.read(buffer),
+        }
+    }
+}
+
+/// Streams amplitudes into a checkpoint without holding the state in memory.
+pub struct CheckpointWriter<W: Write, T: CheckpointScalar> {
+    sink: Sink<W>,
+    header: CheckpointHeader,
+    crc: Crc32,
+    written: u64,
+    buffer: Vec<u8>,
+    scalar: PhantomData<T>,
+}
+
+impl<W: Write, T: CheckpointScalar> CheckpointWriter<W, T> {
+    /// Writes the header; the amplitudes follow through [`CheckpointWriter::write_amplitudes`].
+    pub fn new(
+        mut writer: W,
+        mut header: CheckpointHeader,
+    ) -> Result<CheckpointWriter<W, T>, CheckpointError> {
+        header.version = VERSION;
+        header.precision = T::PRECISION;
+        writer.write_all(&header.encode()?)?;
+
+        Ok(CheckpointWriter {
+            sink: Sink::new(writer, header.compressed)?,
+            header,
+            crc: Crc32::new(),
+            written: 0,
+            buffer: Vec::new(),
+            scalar: PhantomData,
+        })
+    }
+
+    pub fn header(&self) -> &CheckpointHeader {
+        &self.header
+    }
+
+    /// Appends the next amplitudes in basis-state order.
+    pub fn write_amplitudes(&mut self, amplitudes: &[Complex<T>]) -> Result<(), CheckpointError> {
+        let total = self.written + amplitudes.len() as u64;
+        if total > self.header.amplitudes() {
+            return Err(CheckpointError::LengthMismatch {
+                expected: self.header.amplitudes(),
+                actual: total,
+            });
+        }
+
+        self.buffer.clear();
+        for amplitude in amplitudes {
+            amplitude
+                .real
+                .encode(self.header.byte_order, &mut self.buffer);
+            amplitude
+                .imaginary
+                .encode(self.header.byte_order, &mut self.buffer);
+        }
+        self.crc.update(&self.buffer);
+        self.sink.write_all(&self.buffer)?;
+        self.written = total;
+        Ok(())
+    }
+
+    /// Writes the trailing checksum once every amplitude has been written, and returns the
+    /// underlying writer.
+    pub fn finish(mut self) -> Result<W, CheckpointError> {
+        if self.written != self.header.amplitudes() {
+            return Err(CheckpointError::LengthMismatch {
+                expected: self.header.amplitudes(),
+                actual: self.written,
+            });
+        }
+
+        self.sink.write_all(&self.crc.finish().to_le_bytes())?;
+        let mut writer = self.sink.finish()?;
+        writer.flush()?;
+        Ok(writer)
+    }
+}
+
+/// Streams amplitudes out of a checkpoint, verifying the checksum once the last one is read.
+pub struct CheckpointReader<R: Read, T: CheckpointScalar> {
+    source: Source<R>,
+    header: CheckpointHeader,
+    crc: Crc32,
+    remaining: u64,
+    buffer: Vec<u8>,
+    scalar: PhantomData<T>,
+}
+
+impl<R: Read, T: CheckpointScalar> CheckpointReader<R, T> {
+    /// Reads and verifies the header.
+    pub fn new(mut reader: R) -> Result<CheckpointReader<R, T>, CheckpointError> {
+        let (header, _) = CheckpointHeader::decode(&mut reader)?;
+        if header.precision != T::PRECISION {
+            return Err(CheckpointError::PrecisionMismatch {
+                stored: header.precision,
+                requested: T::PRECISION,
+            });
+        }
+
+        Ok(CheckpointReader {
+            source: Source::new(reader, header.compressed)?,
+            remaining: header.amplitudes(),
+            header,
+            crc: Crc32::new(),
+            buffer: Vec::new(),
+            scalar: PhantomData,
+        })
+    }
+
+    pub fn header(&self) -> &CheckpointHeader {
+        &self.header
+    }
+
+    /// Amplitudes not read yet.
+    pub fn remaining(&self) -> u64 {
+        self.remaining
+    }
+
+    /// Fills the front of `amplitudes` with the next amplitudes and returns how many were read,
+    /// which is zero once the state is exhausted.
+    pub fn read_amplitudes(
+        &mut self,
+        amplitudes: &mut [Complex<T>],
+    ) -> Result<usize, CheckpointError> {
+        let count = amplitudes.len().min(self.remaining as usize);
+        if count == 0 {
+            return Ok(0);
+        }
+
+        let width = T::PRECISION.bytes();
+        self.buffer.resize(count * self.header.amplitude_width(), 0);
+        self.source.read_exact(&mut self.buffer)?;
+        self.crc.update(&self.buffer);
+
+        let order = self.header.byte_order;
+        for (amplitude, bytes) in amplitudes.iter_mut().zip(self.buffer.chunks(2 * width)) {
+            *amplitude = Complex::new(
+                T::decode(&bytes[..width], order),
+                T::decode(&bytes[width..], order),
+            );
+        }
+
+        self.remaining -= count as u64;
+        if self.remaining == 0 {
+            let stored = u32::from_le_bytes(read_array(&mut self.source)?);
+            if stored != self.crc.finish() {
+                return Err(CheckpointError::DataChecksum);
+            }
+        }
+        Ok(count)
+    }
+
+    /// Reads every remaining amplitude. The state grows one chunk at a time, so a header
+    /// claiming more amplitudes than the stream holds fails with an i/o error rather than
+    /// allocating the whole state up front.
+    pub fn read_state(mut self) -> Result<QuantumState<T>, CheckpointError>
+    where
+        Complex<T>: Float,
+    {
+        let mut amplitudes = Vec::new();
+        let mut chunk = vec![Complex::new(T::zero(), T::zero()); CHUNK];
+        while self.remaining > 0 {
+            let count = self.read_amplitudes(&mut chunk)?;
+            amplitudes.extend_from_slice(&chunk[..count]);
+        }
+        Ok(QuantumState::new(amplitudes))
+    }
+}
+
+/// Writes `state` under `header`, which must describe `log2(state.size())` qubits.
+pub fn write_state<W: Write, T: CheckpointScalar>(
+    writer: W,
+    header: CheckpointHeader,
+    state: &QuantumState<T>,
+) -> Result<W, CheckpointError>
+where
+    Complex<T>: Float,
+{
+    if state.size() as u64 != header.amplitudes() {
+        return Err(CheckpointError::LengthMismatch {
+            expected: header.amplitudes(),
+            actual: state.size() as u64,
+        });
+    }
+
+    let mut writer = CheckpointWriter::new(writer, header)?;
+    for chunk in state.as_slice().chunks(CHUNK) {
+        writer.write_amplitudes(chunk)?;
+    }
+    writer.finish()
+}
+
+pub fn write_register<W: Write, T: CheckpointScalar>(
+    writer: W,
+    register: &QuantumRegister<T>,
+) -> Result<W, CheckpointError>
+where
+    Complex<T>: Float,
+{
+    write_state(
+        writer,
+        CheckpointHeader::for_register(register),
+        register.state(),
+    )
+}
+
+pub fn read_register<R: Read, T: CheckpointScalar>(
+    reader: R,
+) -> Result<QuantumRegister<T>, CheckpointError>
+where
+    Complex<T>: Float,
+{
+    let reader = CheckpointReader::<R, T>::new(reader)?;
+    let header = reader.header().clone();
+    let state = reader.read_state()?;
+    QuantumRegister::from_state(header.name, header.qubit_names, state)
+        .map_err(|_| CheckpointError::InvalidHeader("qubit count does not match the state"))
+}
+
+/// Random access to an uncompressed checkpoint through a read-only memory map, for states too
+/// large to load. Amplitudes are decoded on access.
+#[cfg(feature = "mmap")]
+pub struct MappedCheckpoint<T: CheckpointScalar> {
+    map: memmap2::Mmap,
+    header: CheckpointHeader,
+    offset: usize,
+    scalar: PhantomData<T>,
+}
+
+#[cfg(feature = "mmap")]
+impl<T: CheckpointScalar> MappedCheckpoint<T> {
+    pub fn open(path: impl AsRef<std::path::Path>) -> Result<MappedCheckpoint<T>, CheckpointError> {
+        let file = std::fs::File::open(path)?;
+        // SAFETY: the map is read-only; like any memory-mapped reader, results are unspecified if
+        // another process truncates or rewrites the file while it is mapped.
+        let map = unsafe { memmap2::Mmap::map(&file)? };
+
+        let (header, offset) = CheckpointHeader::decode(&mut &map[..])?;
+        if header.compressed {
+            return Err(CheckpointError::CompressionUnsupported);
+        }
+        if header.precision != T::PRECISION {
+            return Err(CheckpointError::PrecisionMismatch {
+                stored: header.precision,
+                requested: T::PRECISION,
+            });
+        }
+
+        let body = (header.amplitudes() as usize)
+            .checked_mul(header.amplitude_width())
+            .and_then(|length| length.checked_add(4));
+        let actual = (map.len() - offset) as u64;
+        if body != Some(map.len() - offset) {
+            return Err(CheckpointError::LengthMismatch {
+                expected: header.amplitudes(),
+                actual: actual.saturating_sub(4) / header.amplitude_width() as u64,
+            });
+        }
+
+        Ok(MappedCheckpoint {
+            map,
+            header,
+            offset,
+            scalar: PhantomData,
+        })
+    }
+
+    pub fn header(&self) -> &CheckpointHeader {
+        &self.header
+    }
+
+    pub fn len(&self) -> usize {
+        self.header.amplitudes() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn data(&self) -> &[u8] {
+        let end = self.offset + self.len() * self.header.amplitude_width();
+        &self.map[self.offset..end]
+    }
+
+    pub fn amplitude(&self, index: usize) -> Complex<T> {
+        let width = T::PRECISION.bytes();
+        let start = index * 2 * width;
+        let bytes = &self.data()[start..start + 2 * width];
+        let order = self.header.byte_order;
+        Complex::new(
+            T::decode(&bytes[..width], order),
+            T::decode(&bytes[width..], order),
+        )
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Complex<T>> + '_ {
+        (0..self.len()).map(|index| self.amplitude(index))
+    }
+
+    /// Checks the amplitude checksum, which requires touching every page of the file.
+    pub fn verify(&self) -> Result<(), CheckpointError> {
+        let data = self.data();
+        let trailer = &self.map[self.offset + data.len()..];
+        let stored = u32::from_le_bytes(trailer.try_into().expect("the trailer is four bytes"));
+        if stored != Crc32::checksum(data) {
+            return Err(CheckpointError::DataChecksum);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::random_state;
+    use crate::ColumnVector;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn names(qubits: usize) -> Vec<String> {
+        (0..qubits).map(|i| format!("q{}", i)).collect()
+    }
+
+    fn assert_states_equal<T: Float>(left: &QuantumState<T>, right: &QuantumState<T>)
+    where
+        Complex<T>: Float,
+    {
+        assert_eq!(left.size(), right.size());
+        for i in 0..left.size() {
+            assert!(left.get(i) == right.get(i), "amplitude {} differs", i);
+        }
+    }
+
+    #[test]
+    fn states_round_trip_in_both_byte_orders() {
+        let state = random_state(&mut StdRng::seed_from_u64(1), 5);
+        for order in [ByteOrder::Little, ByteOrder::Big] {
+            let header = CheckpointHeader::new("psi", names(5)).with_byte_order(order);
+            let bytes = write_state(Vec::new(), header, &state).unwrap();
+
+            let reader = CheckpointReader::<_, f64>::new(bytes.as_slice()).unwrap();
+            assert_eq!(reader.header().byte_order, order);
+            assert_eq!(reader.header().qubit_names, names(5));
+            assert_states_equal(&reader.read_state().unwrap(), &state);
+        }
+    }
+
+    #[test]
+    fn registers_round_trip() {
+        let register: QuantumRegister = QuantumRegister::new("qr", &["a", "b", "c"]);
+        let bytes = write_register(Vec::new(), &register).unwrap();
+        let decoded: QuantumRegister = read_register(bytes.as_slice()).unwrap();
+
+        assert_eq!(decoded.get_name(), "qr");
+        assert_eq!(decoded[2].get_name(), "c");
+        assert_states_equal(&decoded.get_state(), &register.get_state());
+    }
+
+    #[test]
+    fn other_precisions_round_trip() {
+        let state: QuantumState<f32> =
+            ColumnVector::new(vec![Complex::new(0.6, 0.0), Complex::new(0.0, -0.8)]);
+        let bytes = write_state(Vec::new(), CheckpointHeader::new("", names(1)), &state).unwrap();
+        let decoded = CheckpointReader::<_, f32>::new(bytes.as_slice()).unwrap();
+        assert_eq!(decoded.header().precision, Precision::Single);
+        assert_states_equal(&decoded.read_state().unwrap(), &state);
+
+        let third = DoubleDouble::from(1.0) / DoubleDouble::from(3.0);
+        let state: QuantumState<DoubleDouble> = ColumnVector::new(vec![
+            Complex::new(third, DoubleDouble::from(0.0)),
+            Complex::new(DoubleDouble::from(0.0), third),
+        ]);
+        let bytes = write_state(Vec::new(), CheckpointHeader::new("", names(1)), &state).unwrap();
+        let decoded = CheckpointReader::<_, DoubleDouble>::new(bytes.as_slice()).unwrap();
+        assert_states_equal(&decoded.read_state().unwrap(), &state);
+
+        assert!(matches!(
+            CheckpointReader::<_, f64>::new(bytes.as_slice()),
+            Err(CheckpointError::PrecisionMismatch {
+                stored: Precision::DoubleDouble,
+                requested: Precision::Double
+            })
+        ));
+    }
+
+    #[test]
+    fn streaming_writes_and_reads_in_chunks() {
+        let state = random_state(&mut StdRng::seed_from_u64(2), 6);
+        let mut writer =
+            CheckpointWriter::<_, f64>::new(Vec::new(), CheckpointHeader::new("s", names(6)))
+                .unwrap();
+        let amplitudes: Vec<_> = (0..state.size()).map(|i| state.get(i)).collect();
+        for chunk in amplitudes.chunks(7) {
+            writer.write_amplitudes(chunk).unwrap();
+        }
+        let bytes = writer.finish().unwrap();
+
+        let mut reader = CheckpointReader::<_, f64>::new(bytes.as_slice()).unwrap();
+        let mut buffer = [Complex::new(0.0, 0.0); 5];
+        let mut decoded = Vec::new();
+        loop {
+            let read = reader.read_amplitudes(&mut buffer).unwrap();
+            if read == 0 {
+                break;
+            }
+            decoded.extend_from_slice(&buffer[..read]);
+        }
+        assert!(decoded == amplitudes);
+    }
+
+    #[test]
+    fn corruption_and_length_errors_are_detected() {
+        let state = random_state(&mut StdRng::seed_from_u64(3), 3);
+        let bytes = write_state(Vec::new(), CheckpointHeader::new("s", names(3)), &state).unwrap();
+        let header_length = bytes.len() - 8 * 16 - 4;
+
+        let mut corrupt = bytes.clone();
+        corrupt[20] ^= 1;
+        assert!(matches!(
+            CheckpointReader::<_, f64>::new(corrupt.as_slice()),
+            Err(CheckpointError::HeaderChecksum)
+        ));
+
+        let mut corrupt = bytes.clone();
+        corrupt[header_length + 20] ^= 1;
+        assert!(matches!(
+            CheckpointReader::<_, f64>::new(corrupt.as_slice())
+                .unwrap()
+                .read_state(),
+            Err(CheckpointError::DataChecksum)
+        ));
+
+        assert!(matches!(
+            CheckpointReader::<_, f64>::new(&bytes[..bytes.len() - 10])
+                .unwrap()
+                .read_state(),
+            Err(CheckpointError::Io(_))
+        ));
+        assert!(matches!(
+            CheckpointReader::<_, f64>::new(&b"PSISTATX"[..]),
+            Err(CheckpointError::NotACheckpoint)
+        ));
+
+        let mut writer =
+            CheckpointWriter::<_, f64>::new(Vec::new(), CheckpointHeader::new("s", names(1)))
+                .unwrap();
+        writer.write_amplitudes(&[Complex::new(1.0, 0.0)]).unwrap();
+        assert!(matches!(
+            writer.finish(),
+            Err(CheckpointError::LengthMismatch {
+                expected: 2,
+                actual: 1
+            })
+        ));
+    }
+
+    #[test]
+    fn oversized_headers_fail_without_allocating_the_state() {
+        // A header for 60 qubits followed by a single amplitude.
+        let mut bytes = CheckpointHeader::new("huge", names(60)).encode().unwrap();
+        bytes.extend_from_slice(&[0; 16]);
+
+        let reader = CheckpointReader::<_, f64>::new(bytes.as_slice()).unwrap();
+        assert_eq!(reader.remaining(), 1 << 60);
+        match reader.read_state() {
+            Err(CheckpointError::Io(error)) => {
+                assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof)
+            }
+            other => panic!("expected an i/o error, got {:?}", other.map(|s| s.size())),
+        }
+    }
+
+    #[cfg(feature = "compression")]
+    #[test]
+    fn compressed_states_round_trip() {
+        let register: QuantumRegister = QuantumRegister::new("qr", &["a"; 10]);
+        let header = CheckpointHeader::for_register(&register).with_compression(true);
+        let compressed = write_state(Vec::new(), header, &register.get_state()).unwrap();
+        let plain = write_register(Vec::new(), &register).unwrap();
+        assert!(compressed.len() < plain.len() / 10);
+
+        let decoded: QuantumRegister = read_register(compressed.as_slice()).unwrap();
+        assert_states_equal(&decoded.get_state(), &register.get_state());
+    }
+
+    #[cfg(not(feature = "compression"))]
+    #[test]
+    fn compression_requires_the_feature() {
+        let header = CheckpointHeader::new("s", names(1)).with_compression(true);
+        assert!(matches!(
+            CheckpointWriter::<_, f64>::new(Vec::new(), header),
+            Err(CheckpointError::CompressionUnsupported)
+        ));
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn mapped_checkpoints_give_random_access() {
+        let state = random_state(&mut StdRng::seed_from_u64(4), 8);
+        let path = std::env::temp_dir().join(format!("psi-checkpoint-{}.bin", std::process::id()));
+        let file = std::fs::File::create(&path).unwrap();
+        write_state(
+            io::BufWriter::new(file),
+            CheckpointHeader::new("big", names(8)).with_byte_order(ByteOrder::Big),
+            &state,
+        )
+        .unwrap();
+
+        let mapped = MappedCheckpoint::<f64>::open(&path).unwrap();
+        assert_eq!(mapped.len(), 256);
+        assert_eq!(mapped.header().name, "big");
+        assert!(mapped.amplitude(200) == state.get(200));
+        assert!(mapped.iter().enumerate().all(|(i, z)| z == state.get(i)));
+        mapped.verify().unwrap();
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
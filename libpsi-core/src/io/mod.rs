@@ -0,0 +1,6 @@
+pub mod checkpoint;
+mod crc32;
+pub mod npy;
+
+pub use checkpoint::*;
+pub use npy::*;
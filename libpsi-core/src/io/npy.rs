@@ -0,0 +1,915 @@
+//! NumPy `.npy` arrays and `.npz` archives, for moving matrices, states and measurement
+//! histograms to and from Python.
+//!
+//! Arrays are written in format version 1.0 (2.0 when the header does not fit), little-endian and
+//! in C order, which is what `numpy.save` produces. Reading also accepts big-endian data, Fortran
+//! order and format versions 2.0 and 3.0. Archives are written uncompressed like `numpy.savez`;
+//! archives from `numpy.savez_compressed` can be read with the `compression` feature.
+
+use super::crc32::Crc32;
+use crate::{ColumnVector, Complex, Float, Matrix, Vector};
+use core::fmt;
+use std::collections::BTreeMap;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+
+const MAGIC: &[u8; 6] = b"\x93NUMPY";
+/// Header alignment used by NumPy since 1.x.
+const ALIGNMENT: usize = 64;
+const LOCAL_HEADER: u32 = 0x0403_4B50;
+const CENTRAL_HEADER: u32 = 0x0201_4B50;
+const END_OF_CENTRAL_DIRECTORY: u32 = 0x0605_4B50;
+/// Last-modified date of archive entries, 1980-01-01 in MS-DOS format.
+const DOS_EPOCH: u16 = 0x21;
+const STORED: u16 = 0;
+const DEFLATED: u16 = 8;
+
+#[derive(Debug)]
+pub enum NpyError {
+    Io(io::Error),
+    InvalidHeader(&'static str),
+    /// The array's `descr` does not match the requested element type.
+    UnsupportedDtype(String),
+    UnsupportedShape(Vec<usize>),
+    InvalidArchive(&'static str),
+    /// An archive entry does not match its stored checksum.
+    Checksum(String),
+    MissingArray(String),
+    /// The archive entry is deflated but the `compression` feature is disabled.
+    CompressionUnsupported,
+    /// The archive would need zip64 extensions, which are not written.
+    TooLarge,
+    /// The archive holds more than the 65535 entries of a zip without zip64 extensions.
+    TooManyEntries,
+}
+
+impl fmt::Display for NpyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NpyError::Io(error) => write!(f, "npy i/o error: {}", error),
+            NpyError::InvalidHeader(reason) => write!(f, "invalid npy header: {}", reason),
+            NpyError::UnsupportedDtype(descr) => write!(f, "unsupported npy dtype '{}'", descr),
+            NpyError::UnsupportedShape(shape) => write!(f, "unsupported npy shape {:?}", shape),
+            NpyError::InvalidArchive(reason) => write!(f, "invalid npz archive: {}", reason),
+            NpyError::Checksum(name) => write!(f, "npz entry '{}' is corrupt", name),
+            NpyError::MissingArray(name) => write!(f, "npz archive has no array '{}'", name),
+            NpyError::CompressionUnsupported => {
+                write!(f, "compressed npz entries need the `compression` feature")
+            }
+            NpyError::TooLarge => write!(f, "npz archive exceeds 4 GiB"),
+            NpyError::TooManyEntries => write!(f, "npz archive exceeds 65535 arrays"),
+        }
+    }
+}
+
+impl std::error::Error for NpyError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            NpyError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for NpyError {
+    fn from(error: io::Error) -> Self {
+        NpyError::Io(error)
+    }
+}
+
+/// Element types with a fixed-width NumPy dtype.
+pub trait NpyElement: Copy {
+    /// Type code without the byte-order character, e.g. `f8`.
+    const TYPE: &'static str;
+    const SIZE: usize;
+
+    /// Appends the little-endian encoding.
+    fn encode(self, output: &mut Vec<u8>);
+    fn decode(bytes: &[u8], big_endian: bool) -> Self;
+}
+
+macro_rules! impl_npy_element {
+    ($($t:ty => $code:literal),*) => {
+        $(
+            impl NpyElement for $t {
+                const TYPE: &'static str = $code;
+                const SIZE: usize = core::mem::size_of::<$t>();
+
+                fn encode(self, output: &mut Vec<u8>) {
+                    output.extend_from_slice(&self.to_le_bytes());
+                }
+
+                fn decode(bytes: &[u8], big_endian: bool) -> Self {
+                    let bytes = bytes.try_into().expect("element width matches the dtype");
+                    if big_endian {
+                        <$t>::from_be_bytes(bytes)
+                    } else {
+                        <$t>::from_le_bytes(bytes)
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_npy_element!(f64 => "f8", i64 => "i8");
+
+impl NpyElement for Complex<f64> {
+    const TYPE: &'static str = "c16";
+    const SIZE: usize = 16;
+
+    fn encode(self, output: &mut Vec<u8>) {
+        self.real.encode(output);
+        self.imaginary.encode(output);
+    }
+
+    fn decode(bytes: &[u8], big_endian: bool) -> Self {
+        Complex::new(
+            f64::decode(&bytes[..8], big_endian),
+            f64::decode(&bytes[8..], big_endian),
+        )
+    }
+}
+
+struct NpyHeader {
+    descr: String,
+    fortran_order: bool,
+    shape: Vec<usize>,
+}
+
+impl NpyHeader {
+    /// Splits the `descr` into its byte order and type code.
+    fn dtype(&self) -> (bool, &str) {
+        match self.descr.split_at(1.min(self.descr.len())) {
+            (">", code) => (true, code),
+            ("=", code) => (cfg!(target_endian = "big"), code),
+            ("<" | "|", code) => (false, code),
+            _ => (false, self.descr.as_str()),
+        }
+    }
+
+    fn elements(&self) -> Result<usize, NpyError> {
+        self.shape
+            .iter()
+            .try_fold(1usize, |total, dimension| total.checked_mul(*dimension))
+            .ok_or(NpyError::InvalidHeader("shape overflows"))
+    }
+}
+
+fn write_header<W: Write>(writer: &mut W, descr: &str, shape: &[usize]) -> io::Result<()> {
+    let shape = match shape {
+        [length] => format!("({},)", length),
+        _ => format!(
+            "({})",
+            shape
+                .iter()
+                .map(|dimension| dimension.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    };
+    let mut header = format!(
+        "{{'descr': '{}', 'fortran_order': False, 'shape': {}, }}",
+        descr, shape
+    );
+
+    let version_1 = header.len() + 1 + MAGIC.len() + 4 < u16::MAX as usize;
+    let prefix = MAGIC.len() + 2 + if version_1 { 2 } else { 4 };
+    let padding = ALIGNMENT - (prefix + header.len() + 1) % ALIGNMENT;
+    header.extend(std::iter::repeat_n(' ', padding % ALIGNMENT));
+    header.push('\n');
+
+    writer.write_all(MAGIC)?;
+    if version_1 {
+        writer.write_all(&[1, 0])?;
+        writer.write_all(&(header.len() as u16).to_le_bytes())?;
+    } else {
+        writer.write_all(&[2, 0])?;
+        writer.write_all(&(header.len() as u32).to_le_bytes())?;
+    }
+    writer.write_all(header.as_bytes())
+}
+
+fn read_header<R: Read>(reader: &mut R) -> Result<NpyHeader, NpyError> {
+    let mut prefix = [0; 8];
+    reader.read_exact(&mut prefix)?;
+    if &prefix[..6] != MAGIC {
+        return Err(NpyError::InvalidHeader("missing magic string"));
+    }
+
+    let length = match prefix[6] {
+        1 => {
+            let mut length = [0; 2];
+            reader.read_exact(&mut length)?;
+            u16::from_le_bytes(length) as usize
+        }
+        2 | 3 => {
+            let mut length = [0; 4];
+            reader.read_exact(&mut length)?;
+            u32::from_le_bytes(length) as usize
+        }
+        _ => return Err(NpyError::InvalidHeader("unsupported format version")),
+    };
+
+    let mut header = Vec::new();
+    reader.take(length as u64).read_to_end(&mut header)?;
+    if header.len() != length {
+        return Err(NpyError::InvalidHeader("truncated header"));
+    }
+    let header =
+        String::from_utf8(header).map_err(|_| NpyError::InvalidHeader("header is not text"))?;
+
+    let descr = field(&header, "descr")
+        .and_then(|value| value.strip_prefix('\''))
+        .and_then(|value| value.split('\'').next())
+        .ok_or(NpyError::InvalidHeader("missing descr"))?;
+    let fortran_order = match field(&header, "fortran_order") {
+        Some(value) if value.starts_with("True") => true,
+        Some(value) if value.starts_with("False") => false,
+        _ => return Err(NpyError::InvalidHeader("missing fortran_order")),
+    };
+    let shape = field(&header, "shape")
+        .and_then(|value| value.strip_prefix('('))
+        .and_then(|value| value.split(')').next())
+        .ok_or(NpyError::InvalidHeader("missing shape"))?
+        .split(',')
+        .map(str::trim)
+        .filter(|dimension| !dimension.is_empty())
+        .map(|dimension| dimension.parse::<usize>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| NpyError::InvalidHeader("invalid shape"))?;
+
+    Ok(NpyHeader {
+        descr: descr.to_string(),
+        fortran_order,
+        shape,
+    })
+}
+
+/// The text following `'key':` in a header dictionary.
+fn field<'h>(header: &'h str, key: &str) -> Option<&'h str> {
+    let start = header.find(&format!("'{}'", key))? + key.len() + 2;
+    Some(header[start..].trim_start().strip_prefix(':')?.trim_start())
+}
+
+fn write_array<W: Write, T: NpyElement>(
+    writer: &mut W,
+    shape: &[usize],
+    data: &[T],
+) -> io::Result<()> {
+    write_header(writer, &format!("<{}", T::TYPE), shape)?;
+
+    let mut bytes = Vec::with_capacity(data.len() * T::SIZE);
+    for value in data {
+        value.encode(&mut bytes);
+    }
+    writer.write_all(&bytes)
+}
+
+/// Reads an array, returning its shape and its elements in C order.
+fn read_array<R: Read, T: NpyElement>(reader: &mut R) -> Result<(Vec<usize>, Vec<T>), NpyError> {
+    let header = read_header(reader)?;
+    let (big_endian, code) = header.dtype();
+    if code != T::TYPE {
+        return Err(NpyError::UnsupportedDtype(header.descr));
+    }
+
+    let size = header
+        .elements()?
+        .checked_mul(T::SIZE)
+        .ok_or(NpyError::InvalidHeader("shape overflows"))?;
+    let mut bytes = Vec::new();
+    reader.take(size as u64).read_to_end(&mut bytes)?;
+    if bytes.len() != size {
+        return Err(NpyError::Io(io::ErrorKind::UnexpectedEof.into()));
+    }
+
+    let data: Vec<T> = bytes
+        .chunks(T::SIZE)
+        .map(|bytes| T::decode(bytes, big_endian))
+        .collect();
+    if header.fortran_order {
+        return Ok((header.shape.clone(), to_c_order(&header.shape, data)));
+    }
+    Ok((header.shape, data))
+}
+
+/// Reorders column-major elements into row-major order.
+fn to_c_order<T: Copy>(shape: &[usize], data: Vec<T>) -> Vec<T> {
+    if shape.len() < 2 {
+        return data;
+    }
+
+    let mut index = vec![0; shape.len()];
+    let mut result = Vec::with_capacity(data.len());
+    for _ in 0..data.len() {
+        let mut offset = 0;
+        for (axis, position) in index.iter().enumerate().rev() {
+            offset = offset * shape[axis] + position;
+        }
+        result.push(data[offset]);
+
+        for axis in (0..shape.len()).rev() {
+            index[axis] += 1;
+            if index[axis] < shape[axis] {
+                break;
+            }
+            index[axis] = 0;
+        }
+    }
+    result
+}
+
+fn matrix_from_array<T: Float>(shape: Vec<usize>, data: Vec<T>) -> Result<Matrix<T>, NpyError> {
+    match shape[..] {
+        [rows, cols] => Ok(Matrix::new(rows, cols, data)),
+        [rows] => Ok(Matrix::new(rows, 1, data)),
+        _ => Err(NpyError::UnsupportedShape(shape)),
+    }
+}
+
+fn vector_from_array<T: Float>(
+    shape: Vec<usize>,
+    data: Vec<T>,
+) -> Result<ColumnVector<T>, NpyError> {
+    match shape[..] {
+        [_] | [_, 1] => Ok(ColumnVector::new(data)),
+        _ => Err(NpyError::UnsupportedShape(shape)),
+    }
+}
+
+fn vector_data<T: Float>(vector: &ColumnVector<T>) -> Vec<T> {
+    (0..vector.size()).map(|i| vector.get(i)).collect()
+}
+
+/// Writes a 2-D array of shape `(rows, cols)`.
+pub fn write_matrix<W: Write, T: NpyElement + Float>(
+    mut writer: W,
+    matrix: &Matrix<T>,
+) -> Result<W, NpyError> {
+    write_array(&mut writer, &[matrix.rows, matrix.cols], &matrix.data)?;
+    Ok(writer)
+}
+
+/// Reads a 2-D array; a 1-D array becomes a single column.
+pub fn read_matrix<R: Read, T: NpyElement + Float>(mut reader: R) -> Result<Matrix<T>, NpyError> {
+    let (shape, data) = read_array(&mut reader)?;
+    matrix_from_array(shape, data)
+}
+
+/// Writes a 1-D array.
+pub fn write_vector<W: Write, T: NpyElement + Float>(
+    mut writer: W,
+    vector: &ColumnVector<T>,
+) -> Result<W, NpyError> {
+    write_array(&mut writer, &[vector.size()], &vector_data(vector))?;
+    Ok(writer)
+}
+
+/// Reads a 1-D array or a single column.
+pub fn read_vector<R: Read, T: NpyElement + Float>(
+    mut reader: R,
+) -> Result<ColumnVector<T>, NpyError> {
+    let (shape, data) = read_array(&mut reader)?;
+    vector_from_array(shape, data)
+}
+
+struct CentralEntry {
+    name: String,
+    crc: u32,
+    size: u32,
+    offset: u32,
+}
+
+/// Writes an `.npz` archive entry by entry; each array is named `<key>.npy` inside the archive,
+/// so `numpy.load(path)[key]` returns it.
+pub struct NpzWriter<W: Write> {
+    writer: W,
+    offset: u64,
+    entries: Vec<CentralEntry>,
+}
+
+impl<W: Write> NpzWriter<W> {
+    pub fn new(writer: W) -> NpzWriter<W> {
+        NpzWriter {
+            writer,
+            offset: 0,
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn add_matrix<T: NpyElement + Float>(
+        &mut self,
+        key: &str,
+        matrix: &Matrix<T>,
+    ) -> Result<(), NpyError> {
+        let mut bytes = Vec::new();
+        write_array(&mut bytes, &[matrix.rows, matrix.cols], &matrix.data)?;
+        self.add_entry(key, &bytes)
+    }
+
+    pub fn add_vector<T: NpyElement + Float>(
+        &mut self,
+        key: &str,
+        vector: &ColumnVector<T>,
+    ) -> Result<(), NpyError> {
+        let mut bytes = Vec::new();
+        write_array(&mut bytes, &[vector.size()], &vector_data(vector))?;
+        self.add_entry(key, &bytes)
+    }
+
+    /// Adds a histogram as the string array `<key>_outcomes` and the `int64` array
+    /// `<key>_counts`, both sorted by outcome.
+    pub fn add_histogram(
+        &mut self,
+        key: &str,
+        histogram: &BTreeMap<String, usize>,
+    ) -> Result<(), NpyError> {
+        self.add_histogram_as(
+            &format!("{}_outcomes", key),
+            &format!("{}_counts", key),
+            histogram,
+        )
+    }
+
+    fn add_histogram_as(
+        &mut self,
+        outcomes_key: &str,
+        counts_key: &str,
+        histogram: &BTreeMap<String, usize>,
+    ) -> Result<(), NpyError> {
+        let width = histogram
+            .keys()
+            .map(|outcome| outcome.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max(1);
+
+        let mut bytes = Vec::new();
+        write_header(&mut bytes, &format!("<U{}", width), &[histogram.len()])?;
+        for outcome in histogram.keys() {
+            let length = outcome.chars().count();
+            for character in outcome
+                .chars()
+                .chain(std::iter::repeat_n('\0', width - length))
+            {
+                bytes.extend_from_slice(&(character as u32).to_le_bytes());
+            }
+        }
+        self.add_entry(outcomes_key, &bytes)?;
+
+        let counts: Vec<i64> = histogram.values().map(|count| *count as i64).collect();
+        let mut bytes = Vec::new();
+        write_array(&mut bytes, &[counts.len()], &counts)?;
+        self.add_entry(counts_key, &bytes)
+    }
+
+    fn add_entry(&mut self, key: &str, bytes: &[u8]) -> Result<(), NpyError> {
+        let name = format!("{}.npy", key);
+        let offset = u32::try_from(self.offset).map_err(|_| NpyError::TooLarge)?;
+        let size = u32::try_from(bytes.len()).map_err(|_| NpyError::TooLarge)?;
+        let crc = Crc32::checksum(bytes);
+
+        let mut header = Vec::with_capacity(30 + name.len());
+        header.extend_from_slice(&LOCAL_HEADER.to_le_bytes());
+        for field in [20, 0, STORED, 0, DOS_EPOCH] {
+            header.extend_from_slice(&u16::to_le_bytes(field));
+        }
+        for field in [crc, size, size] {
+            header.extend_from_slice(&field.to_le_bytes());
+        }
+        header.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        header.extend_from_slice(&0u16.to_le_bytes());
+        header.extend_from_slice(name.as_bytes());
+
+        self.writer.write_all(&header)?;
+        self.writer.write_all(bytes)?;
+        self.offset += (header.len() + bytes.len()) as u64;
+        self.entries.push(CentralEntry {
+            name,
+            crc,
+            size,
+            offset,
+        });
+        Ok(())
+    }
+
+    /// Writes the central directory and returns the underlying writer.
+    pub fn finish(mut self) -> Result<W, NpyError> {
+        let start = u32::try_from(self.offset).map_err(|_| NpyError::TooLarge)?;
+        let count = u16::try_from(self.entries.len()).map_err(|_| NpyError::TooManyEntries)?;
+
+        let mut directory = Vec::new();
+        for entry in &self.entries {
+            directory.extend_from_slice(&CENTRAL_HEADER.to_le_bytes());
+            for field in [20, 20, 0, STORED, 0, DOS_EPOCH] {
+                directory.extend_from_slice(&u16::to_le_bytes(field));
+            }
+            for field in [entry.crc, entry.size, entry.size] {
+                directory.extend_from_slice(&field.to_le_bytes());
+            }
+            for field in [entry.name.len() as u16, 0, 0, 0, 0] {
+                directory.extend_from_slice(&field.to_le_bytes());
+            }
+            directory.extend_from_slice(&0u32.to_le_bytes());
+            directory.extend_from_slice(&entry.offset.to_le_bytes());
+            directory.extend_from_slice(entry.name.as_bytes());
+        }
+
+        let size = u32::try_from(directory.len()).map_err(|_| NpyError::TooLarge)?;
+        directory.extend_from_slice(&END_OF_CENTRAL_DIRECTORY.to_le_bytes());
+        for field in [0, 0, count, count] {
+            directory.extend_from_slice(&u16::to_le_bytes(field));
+        }
+        directory.extend_from_slice(&size.to_le_bytes());
+        directory.extend_from_slice(&start.to_le_bytes());
+        directory.extend_from_slice(&0u16.to_le_bytes());
+
+        self.writer.write_all(&directory)?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+struct ArchiveEntry {
+    method: u16,
+    crc: u32,
+    compressed_size: u64,
+    size: u64,
+    offset: u64,
+}
+
+/// Reads arrays out of an `.npz` archive by key.
+pub struct NpzReader<R: Read + Seek> {
+    reader: R,
+    entries: BTreeMap<String, ArchiveEntry>,
+}
+
+fn u16_at(bytes: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes([bytes[offset], bytes[offset + 1]])
+}
+
+fn u32_at(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(bytes[offset..offset + 4].try_into().expect("four bytes"))
+}
+
+fn u64_at(bytes: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(bytes[offset..offset + 8].try_into().expect("eight bytes"))
+}
+
+impl<R: Read + Seek> NpzReader<R> {
+    /// Reads the central directory of the archive.
+    pub fn new(mut reader: R) -> Result<NpzReader<R>, NpyError> {
+        let length = reader.seek(SeekFrom::End(0))?;
+        let tail_length = length.min(22 + u16::MAX as u64);
+        reader.seek(SeekFrom::Start(length - tail_length))?;
+        let mut tail = vec![0; tail_length as usize];
+        reader.read_exact(&mut tail)?;
+
+        let end = (0..tail.len().saturating_sub(21))
+            .rev()
+            .find(|&i| u32_at(&tail, i) == END_OF_CENTRAL_DIRECTORY)
+            .ok_or(NpyError::InvalidArchive("missing end of central directory"))?;
+        let count = u16_at(&tail, end + 10);
+        let size = u32_at(&tail, end + 12);
+        let start = u32_at(&tail, end + 16);
+        if start == u32::MAX || count == u16::MAX {
+            return Err(NpyError::InvalidArchive("zip64 archives are not supported"));
+        }
+
+        reader.seek(SeekFrom::Start(start as u64))?;
+        let mut directory = vec![0; size as usize];
+        reader.read_exact(&mut directory)?;
+
+        let mut entries = BTreeMap::new();
+        let mut position = 0;
+        for _ in 0..count {
+            if directory.len() < position + 46 || u32_at(&directory, position) != CENTRAL_HEADER {
+                return Err(NpyError::InvalidArchive("corrupt central directory"));
+            }
+            let record = &directory[position..];
+            let name_length = u16_at(record, 28) as usize;
+            let extra_length = u16_at(record, 30) as usize;
+            let comment_length = u16_at(record, 32) as usize;
+            if record.len() < 46 + name_length + extra_length {
+                return Err(NpyError::InvalidArchive("corrupt central directory"));
+            }
+
+            let name = String::from_utf8_lossy(&record[46..46 + name_length]).into_owned();
+            let mut entry = ArchiveEntry {
+                method: u16_at(record, 10),
+                crc: u32_at(record, 16),
+                compressed_size: u32_at(record, 20) as u64,
+                size: u32_at(record, 24) as u64,
+                offset: u32_at(record, 42) as u64,
+            };
+            read_zip64_extra(
+                &record[46 + name_length..46 + name_length + extra_length],
+                &mut entry,
+            );
+
+            let key = name.strip_suffix(".npy").unwrap_or(&name).to_string();
+            entries.insert(key, entry);
+            position += 46 + name_length + extra_length + comment_length;
+        }
+
+        Ok(NpzReader { reader, entries })
+    }
+
+    /// Keys of the arrays in the archive, in sorted order.
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.entries.keys().map(String::as_str)
+    }
+
+    pub fn matrix<T: NpyElement + Float>(&mut self, key: &str) -> Result<Matrix<T>, NpyError> {
+        let bytes = self.entry(key)?;
+        let (shape, data) = read_array(&mut bytes.as_slice())?;
+        matrix_from_array(shape, data)
+    }
+
+    pub fn vector<T: NpyElement + Float>(
+        &mut self,
+        key: &str,
+    ) -> Result<ColumnVector<T>, NpyError> {
+        let bytes = self.entry(key)?;
+        let (shape, data) = read_array(&mut bytes.as_slice())?;
+        vector_from_array(shape, data)
+    }
+
+    /// Reads a histogram written by [`NpzWriter::add_histogram`].
+    pub fn histogram(&mut self, key: &str) -> Result<BTreeMap<String, usize>, NpyError> {
+        self.histogram_as(&format!("{}_outcomes", key), &format!("{}_counts", key))
+    }
+
+    fn histogram_as(
+        &mut self,
+        outcomes_key: &str,
+        counts_key: &str,
+    ) -> Result<BTreeMap<String, usize>, NpyError> {
+        let bytes = self.entry(outcomes_key)?;
+        let mut reader = bytes.as_slice();
+        let header = read_header(&mut reader)?;
+        let width = match header.dtype() {
+            (false, code) => code
+                .strip_prefix('U')
+                .and_then(|width| width.parse::<usize>().ok())
+                // Zero-width strings cannot tell outcomes apart.
+                .filter(|width| *width > 0),
+            _ => None,
+        }
+        .ok_or_else(|| NpyError::UnsupportedDtype(header.descr.clone()))?;
+
+        let outcomes = reader
+            .chunks(4 * width)
+            .take(header.elements()?)
+            .map(|characters| {
+                characters
+                    .chunks_exact(4)
+                    .map(|bytes| u32_at(bytes, 0))
+                    .take_while(|code| *code != 0)
+                    .map(|code| char::from_u32(code).ok_or(NpyError::InvalidHeader("invalid text")))
+                    .collect::<Result<String, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let bytes = self.entry(counts_key)?;
+        let (_, counts): (_, Vec<i64>) = read_array(&mut bytes.as_slice())?;
+        if outcomes.len() != counts.len() || counts.iter().any(|count| *count < 0) {
+            return Err(NpyError::InvalidArchive("outcomes and counts do not match"));
+        }
+
+        Ok(outcomes
+            .into_iter()
+            .zip(counts.into_iter().map(|count| count as usize))
+            .collect())
+    }
+
+    /// The decompressed and verified contents of an entry.
+    fn entry(&mut self, key: &str) -> Result<Vec<u8>, NpyError> {
+        let entry = self
+            .entries
+            .get(key)
+            .ok_or_else(|| NpyError::MissingArray(key.to_string()))?;
+
+        let mut header = [0; 30];
+        self.reader.seek(SeekFrom::Start(entry.offset))?;
+        self.reader.read_exact(&mut header)?;
+        if u32_at(&header, 0) != LOCAL_HEADER {
+            return Err(NpyError::InvalidArchive("corrupt local header"));
+        }
+        let skip = u16_at(&header, 26) as i64 + u16_at(&header, 28) as i64;
+        self.reader.seek(SeekFrom::Current(skip))?;
+
+        let mut stored = Vec::new();
+        (&mut self.reader)
+            .take(entry.compressed_size)
+            .read_to_end(&mut stored)?;
+        let bytes = match entry.method {
+            STORED => stored,
+            DEFLATED => inflate(&stored)?,
+            _ => return Err(NpyError::InvalidArchive("unknown compression method")),
+        };
+
+        if bytes.len() as u64 != entry.size || Crc32::checksum(&bytes) != entry.crc {
+            return Err(NpyError::Checksum(key.to_string()));
+        }
+        Ok(bytes)
+    }
+}
+
+/// Replaces saturated sizes and offsets with the values of a zip64 extended information field.
+fn read_zip64_extra(mut extra: &[u8], entry: &mut ArchiveEntry) {
+    while extra.len() >= 4 {
+        let (id, length) = (u16_at(extra, 0), u16_at(extra, 2) as usize);
+        let data = &extra[4..(4 + length).min(extra.len())];
+        if id == 1 {
+            let mut values = data.chunks_exact(8).map(|bytes| u64_at(bytes, 0));
+            for field in [
+                &mut entry.size,
+                &mut entry.compressed_size,
+                &mut entry.offset,
+            ] {
+                if *field == u32::MAX as u64 {
+                    if let Some(value) = values.next() {
+                        *field = value;
+                    }
+                }
+            }
+        }
+        extra = &extra[(4 + length).min(extra.len())..];
+    }
+}
+
+#[cfg(feature = "compression")]
+fn inflate(bytes: &[u8]) -> Result<Vec<u8>, NpyError> {
+    let mut inflated = Vec::new();
+    flate2::read::DeflateDecoder::new(bytes).read_to_end(&mut inflated)?;
+    Ok(inflated)
+}
+
+#[cfg(not(feature = "compression"))]
+fn inflate(_: &[u8]) -> Result<Vec<u8>, NpyError> {
+    Err(NpyError::CompressionUnsupported)
+}
+
+/// Writes a measurement histogram as an `.npz` archive with the arrays `outcomes` (strings) and
+/// `counts` (`int64`).
+pub fn write_histogram<W: Write>(
+    writer: W,
+    histogram: &BTreeMap<String, usize>,
+) -> Result<W, NpyError> {
+    let mut archive = NpzWriter::new(writer);
+    archive.add_histogram_as("outcomes", "counts", histogram)?;
+    archive.finish()
+}
+
+/// Reads a histogram written by [`write_histogram`] or by
+/// `numpy.savez(path, outcomes=..., counts=...)`.
+pub fn read_histogram<R: Read + Seek>(reader: R) -> Result<BTreeMap<String, usize>, NpyError> {
+    NpzReader::new(reader)?.histogram_as("outcomes", "counts")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    const MATRIX_F64: &[u8] = include_bytes!("fixtures/matrix_f64.npy");
+    const MATRIX_C16_FORTRAN: &[u8] = include_bytes!("fixtures/matrix_c16_fortran.npy");
+    const VECTOR_C16_BIG_ENDIAN: &[u8] = include_bytes!("fixtures/vector_c16_big_endian.npy");
+    const HISTOGRAM: &[u8] = include_bytes!("fixtures/histogram.npz");
+    const HISTOGRAM_EMPTY_OUTCOMES: &[u8] = include_bytes!("fixtures/histogram_empty_outcomes.npz");
+    #[cfg(feature = "compression")]
+    const HISTOGRAM_COMPRESSED: &[u8] = include_bytes!("fixtures/histogram_compressed.npz");
+
+    fn histogram() -> BTreeMap<String, usize> {
+        [("000", 10), ("011", 3), ("111", 7)]
+            .into_iter()
+            .map(|(outcome, count)| (outcome.to_string(), count))
+            .collect()
+    }
+
+    #[test]
+    fn real_matrices_match_numpy_bytes() {
+        let matrix: Matrix<f64> = read_matrix(MATRIX_F64).unwrap();
+        assert_eq!((matrix.rows, matrix.cols), (2, 3));
+        assert_eq!(matrix.data, vec![1.0, 2.5, -3.0, 0.125, 1e300, -0.0]);
+
+        assert_eq!(write_matrix(Vec::new(), &matrix).unwrap(), MATRIX_F64);
+    }
+
+    #[test]
+    fn complex_matrices_in_fortran_order() {
+        let matrix: Matrix<Complex<f64>> = read_matrix(MATRIX_C16_FORTRAN).unwrap();
+        assert_eq!(matrix.get(0, 1), Complex::new(3.0, -4.0));
+        assert_eq!(matrix.get(1, 0), Complex::new(0.0, 0.5));
+
+        let bytes = write_matrix(Vec::new(), &matrix).unwrap();
+        let decoded: Matrix<Complex<f64>> = read_matrix(bytes.as_slice()).unwrap();
+        assert_eq!(decoded.data, matrix.data);
+        assert_eq!(bytes.len(), MATRIX_C16_FORTRAN.len());
+    }
+
+    #[test]
+    fn big_endian_vectors_in_format_two() {
+        let state: ColumnVector<Complex<f64>> = read_vector(VECTOR_C16_BIG_ENDIAN).unwrap();
+        assert_eq!(state.get(0), Complex::new(0.6, 0.0));
+        assert_eq!(state.get(1), Complex::new(0.0, -0.8));
+
+        let bytes = write_vector(Vec::new(), &state).unwrap();
+        assert_eq!(&bytes[6..8], &[1, 0]);
+        assert_eq!(bytes.len() % ALIGNMENT, 2 * 16);
+        let decoded: ColumnVector<Complex<f64>> = read_vector(bytes.as_slice()).unwrap();
+        assert_eq!(decoded.get(1), state.get(1));
+    }
+
+    #[test]
+    fn dtype_and_shape_are_checked() {
+        assert!(matches!(
+            read_matrix::<_, Complex<f64>>(MATRIX_F64),
+            Err(NpyError::UnsupportedDtype(descr)) if descr == "<f8"
+        ));
+        assert!(matches!(
+            read_vector::<_, f64>(MATRIX_F64),
+            Err(NpyError::UnsupportedShape(shape)) if shape == vec![2, 3]
+        ));
+        assert!(matches!(
+            read_matrix::<_, f64>(&MATRIX_F64[..MATRIX_F64.len() - 1]),
+            Err(NpyError::Io(_))
+        ));
+
+        // The element count fits in a usize but its size in bytes does not.
+        let mut overflowing = Vec::new();
+        write_header(&mut overflowing, "<f8", &[usize::MAX / 4]).unwrap();
+        assert!(matches!(
+            read_vector::<_, f64>(overflowing.as_slice()),
+            Err(NpyError::InvalidHeader("shape overflows"))
+        ));
+    }
+
+    #[test]
+    fn histograms_from_numpy_archives() {
+        let mut archive = NpzReader::new(Cursor::new(HISTOGRAM)).unwrap();
+        assert_eq!(
+            archive.keys().collect::<Vec<_>>(),
+            vec!["counts", "outcomes"]
+        );
+
+        assert!(matches!(
+            archive.vector::<f64>("counts"),
+            Err(NpyError::UnsupportedDtype(descr)) if descr == "<i8"
+        ));
+        assert_eq!(read_histogram(Cursor::new(HISTOGRAM)).unwrap(), histogram());
+
+        assert!(matches!(
+            read_histogram(Cursor::new(HISTOGRAM_EMPTY_OUTCOMES)),
+            Err(NpyError::UnsupportedDtype(descr)) if descr == "<U0"
+        ));
+    }
+
+    #[cfg(feature = "compression")]
+    #[test]
+    fn compressed_archives() {
+        assert_eq!(
+            read_histogram(Cursor::new(HISTOGRAM_COMPRESSED)).unwrap(),
+            histogram()
+        );
+    }
+
+    #[test]
+    fn archives_round_trip() {
+        let matrix: Matrix<f64> = read_matrix(MATRIX_F64).unwrap();
+        let state: ColumnVector<Complex<f64>> = read_vector(VECTOR_C16_BIG_ENDIAN).unwrap();
+
+        let mut archive = NpzWriter::new(Vec::new());
+        archive.add_matrix("hamiltonian", &matrix).unwrap();
+        archive.add_vector("psi", &state).unwrap();
+        archive.add_histogram("shots", &histogram()).unwrap();
+        let bytes = archive.finish().unwrap();
+
+        let mut archive = NpzReader::new(Cursor::new(bytes.clone())).unwrap();
+        assert_eq!(
+            archive.matrix::<f64>("hamiltonian").unwrap().data,
+            matrix.data
+        );
+        assert_eq!(
+            archive.vector::<Complex<f64>>("psi").unwrap().get(1),
+            state.get(1)
+        );
+        assert_eq!(archive.histogram("shots").unwrap(), histogram());
+        assert!(matches!(
+            archive.matrix::<f64>("missing"),
+            Err(NpyError::MissingArray(_))
+        ));
+
+        let mut corrupt = bytes;
+        corrupt[100] ^= 1;
+        let mut archive = NpzReader::new(Cursor::new(corrupt)).unwrap();
+        assert!(matches!(
+            archive.matrix::<f64>("hamiltonian"),
+            Err(NpyError::Checksum(key)) if key == "hamiltonian"
+        ));
+
+        let bytes = write_histogram(Vec::new(), &histogram()).unwrap();
+        assert_eq!(read_histogram(Cursor::new(bytes)).unwrap(), histogram());
+    }
+}
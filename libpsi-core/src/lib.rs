@@ -1,12 +1,25 @@
 pub mod core;
+pub mod io;
 pub mod maths;
+#[cfg(test)]
+mod testing;
 
 pub use maths::complex::*;
+pub use maths::decomposition::*;
+pub use maths::double_double::*;
+pub use maths::eigen::*;
+pub use maths::error::*;
 pub use maths::matrix::*;
 pub use maths::numeric::*;
+pub use maths::pauli::*;
+pub use maths::smatrix::*;
+pub use maths::sparse::*;
 pub use maths::vector::*;
 
 pub use core::circuit::*;
 pub use core::classical_components::*;
+pub use core::composite::*;
 pub use core::gates;
+pub use core::instruction::*;
+pub use core::measurement::*;
 pub use core::quantum_components::*;
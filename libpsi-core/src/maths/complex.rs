@@ -1,4 +1,4 @@
-use crate::Float;
+use crate::{DoubleDouble, Float};
 use core::{fmt, ops};
 
 #[macro_export]
@@ -35,25 +35,75 @@ macro_rules! impl_ops {
         }
     };
 
+    ($trait:ident, $method:ident, $op:tt, scale) => {
+        impl<T: Float> ops::$trait<T> for Complex<T> {
+            type Output = Complex<T>;
+
+            fn $method(self, other: T) -> Complex<T> {
+                Complex {
+                    real: self.real $op other,
+                    imaginary: self.imaginary $op other,
+                }
+            }
+        }
+    };
+
     ($trait_assign:ident, $method_assign:ident, $op:tt, assign) => {
         impl<T: Float> ops::$trait_assign for Complex<T> {
             fn $method_assign(&mut self, other: Complex<T>) {
-                self.real = self.real $op other.real;
-                self.imaginary = self.imaginary $op other.imaginary;
+                *self = *self $op other;
             }
         }
-    };
 
-    ($trait_assign:ident, $method_assign:ident, $op:tt, assign_real) => {
         impl<T: Float> ops::$trait_assign<T> for Complex<T> {
             fn $method_assign(&mut self, other: T) {
-                self.real = self.real $op other;
+                *self = *self $op other;
             }
         }
     };
 }
 
+macro_rules! impl_scalar_ops {
+    ($($t:ty),*) => {
+        $(
+            impl ops::Add<Complex<$t>> for $t {
+                type Output = Complex<$t>;
+
+                fn add(self, other: Complex<$t>) -> Complex<$t> {
+                    Complex::from(self) + other
+                }
+            }
+
+            impl ops::Sub<Complex<$t>> for $t {
+                type Output = Complex<$t>;
+
+                fn sub(self, other: Complex<$t>) -> Complex<$t> {
+                    Complex::from(self) - other
+                }
+            }
+
+            impl ops::Mul<Complex<$t>> for $t {
+                type Output = Complex<$t>;
+
+                fn mul(self, other: Complex<$t>) -> Complex<$t> {
+                    other * self
+                }
+            }
+
+            impl ops::Div<Complex<$t>> for $t {
+                type Output = Complex<$t>;
+
+                fn div(self, other: Complex<$t>) -> Complex<$t> {
+                    Complex::from(self) / other
+                }
+            }
+        )*
+    };
+}
+
 #[derive(Copy, Clone, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(C)]
 pub struct Complex<T: Float> {
     pub real: T,
     pub imaginary: T,
@@ -96,7 +146,7 @@ impl<T: Float> From<T> for Complex<T> {
 }
 
 impl<T: Float> Complex<T> {
-    pub fn new(real: T, imaginary: T) -> Complex<T> {
+    pub const fn new(real: T, imaginary: T) -> Complex<T> {
         Complex { real, imaginary }
     }
 
@@ -119,15 +169,145 @@ impl<T: Float> Complex<T> {
         self.real * self.real + self.imaginary * self.imaginary
     }
 
-    pub fn abs(&self) -> T {
+    pub fn abs(self) -> T {
         T::sqrt(self.norm2())
     }
+
+    pub fn from_polar(radius: T, theta: T) -> Complex<T> {
+        Complex {
+            real: radius * T::cos(theta),
+            imaginary: radius * T::sin(theta),
+        }
+    }
+
+    pub fn to_polar(self) -> (T, T) {
+        (self.abs(), self.phase())
+    }
+
+    pub fn cis(theta: T) -> Complex<T> {
+        Complex::from_polar(T::one(), theta)
+    }
+
+    pub fn reciprocal(self) -> Complex<T> {
+        Complex::from(T::one()) / self
+    }
+
+    pub fn exp(self) -> Complex<T> {
+        Complex::from_polar(T::exp(self.real), self.imaginary)
+    }
+
+    /// Principal branch of the natural logarithm, with the imaginary part in (-pi, pi].
+    pub fn ln(self) -> Complex<T> {
+        Complex {
+            real: T::ln(self.abs()),
+            imaginary: self.phase(),
+        }
+    }
+
+    /// `self^exponent` by repeated squaring, with zero bases handled as in [`Complex::powf`].
+    pub fn powi(self, exponent: i32) -> Complex<T> {
+        if exponent != 0 && self.real == T::zero() && self.imaginary == T::zero() {
+            return zero_power(T::from_f64(exponent as f64));
+        }
+
+        let mut base = if exponent < 0 {
+            self.reciprocal()
+        } else {
+            self
+        };
+        let mut exponent = exponent.unsigned_abs();
+        let mut result = Complex::from(T::one());
+
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result *= base;
+            }
+            base *= base;
+            exponent >>= 1;
+        }
+
+        result
+    }
+
+    /// `self^exponent` on the principal branch. Like [`f64::powf`], `0^0` is one and a zero base
+    /// with a negative exponent gives infinity.
+    pub fn powf(self, exponent: T) -> Complex<T> {
+        if exponent == T::zero() {
+            return Complex::from(T::one());
+        }
+        if self.real == T::zero() && self.imaginary == T::zero() {
+            return zero_power(exponent);
+        }
+
+        let (radius, theta) = self.to_polar();
+        Complex::from_polar(T::exp(T::ln(radius) * exponent), theta * exponent)
+    }
+
+    /// `self^exponent` on the principal branch, with zero bases handled as in
+    /// [`Complex::powf`]. `0^z` for a non-zero, purely imaginary `z` is undefined and gives NaN.
+    pub fn powc(self, exponent: Complex<T>) -> Complex<T> {
+        if exponent.real == T::zero() && exponent.imaginary == T::zero() {
+            return Complex::from(T::one());
+        }
+        if self.real == T::zero() && self.imaginary == T::zero() {
+            return zero_power(exponent.real);
+        }
+
+        (self.ln() * exponent).exp()
+    }
+}
+
+/// `0^z` for an exponent `z` with real part `real` and `z ≠ 0`.
+fn zero_power<T: Float>(real: T) -> Complex<T> {
+    let value = if real > T::zero() {
+        T::zero()
+    } else if real < T::zero() {
+        T::one() / T::zero()
+    } else {
+        T::zero() / T::zero()
+    };
+    Complex::from(value)
+}
+
+impl<T: Float> ops::Mul for Complex<T> {
+    type Output = Complex<T>;
+
+    fn mul(self, other: Complex<T>) -> Complex<T> {
+        Complex {
+            real: self.real * other.real - self.imaginary * other.imaginary,
+            imaginary: self.real * other.imaginary + self.imaginary * other.real,
+        }
+    }
+}
+
+impl<T: Float> ops::Div for Complex<T> {
+    type Output = Complex<T>;
+
+    // Smith's algorithm: dividing through by the larger component of the denominator
+    // avoids forming |c|^2 + |d|^2, which overflows long before the quotient does.
+    fn div(self, other: Complex<T>) -> Complex<T> {
+        let abs = |x: T| if x < T::zero() { -x } else { x };
+
+        if abs(other.real) >= abs(other.imaginary) {
+            let ratio = other.imaginary / other.real;
+            let denominator = other.real + other.imaginary * ratio;
+            Complex {
+                real: (self.real + self.imaginary * ratio) / denominator,
+                imaginary: (self.imaginary - self.real * ratio) / denominator,
+            }
+        } else {
+            let ratio = other.real / other.imaginary;
+            let denominator = other.real * ratio + other.imaginary;
+            Complex {
+                real: (self.real * ratio + self.imaginary) / denominator,
+                imaginary: (self.imaginary * ratio - self.real) / denominator,
+            }
+        }
+    }
 }
 
 impl_ops!(Add, add, +);
 impl_ops!(Sub, sub, -);
-impl_ops!(Mul, mul, *);
-impl_ops!(Div, div, /);
 
 impl_ops!(AddAssign, add_assign, +, assign);
 impl_ops!(SubAssign, sub_assign, -, assign);
@@ -136,5 +316,185 @@ impl_ops!(DivAssign, div_assign, /, assign);
 
 impl_ops!(Add, add, +, real);
 impl_ops!(Sub, sub, -, real);
-impl_ops!(Mul, mul, *, real);
-impl_ops!(Div, div, /, real);
+impl_ops!(Mul, mul, *, scale);
+impl_ops!(Div, div, /, scale);
+
+impl_scalar_ops!(f32, f64, DoubleDouble);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::f64::consts::PI;
+    use proptest::prelude::*;
+
+    const TOLERANCE: f64 = 1e-9;
+
+    fn close(a: Complex<f64>, b: Complex<f64>) -> bool {
+        let scale = 1.0_f64.max(a.abs()).max(b.abs());
+        (a - b).abs() <= TOLERANCE * scale
+    }
+
+    fn component() -> impl Strategy<Value = f64> {
+        -1e3..1e3_f64
+    }
+
+    fn complex() -> impl Strategy<Value = Complex<f64>> {
+        (component(), component()).prop_map(|(real, imaginary)| complex!(real, imaginary))
+    }
+
+    fn nonzero_complex() -> impl Strategy<Value = Complex<f64>> {
+        complex().prop_filter("non-zero", |z| z.abs() > 1e-3)
+    }
+
+    #[test]
+    fn imaginary_unit_squares_to_minus_one() {
+        let i = complex!(0.0, 1.0);
+        assert!(close(i * i, complex!(-1.0, 0.0)));
+    }
+
+    #[test]
+    fn euler_identity() {
+        assert!(close(Complex::cis(PI) + 1.0, complex!(0.0, 0.0)));
+        assert!(close(complex!(0.0, PI).exp(), complex!(-1.0, 0.0)));
+    }
+
+    #[test]
+    fn principal_logarithm() {
+        assert!(close(complex!(-1.0, 0.0).ln(), complex!(0.0, PI)));
+        assert!(close(complex!(0.0, 1.0).ln(), complex!(0.0, PI / 2.0)));
+    }
+
+    #[test]
+    fn zero_powers() {
+        let zero = complex!(0.0_f64, 0.0);
+        assert!(close(zero.powf(2.0), zero));
+        assert!(close(zero.powc(complex!(1.0, 1.0)), zero));
+        assert!(close(zero.powi(0), complex!(1.0, 0.0)));
+        assert!(close(zero.powf(0.0), complex!(1.0, 0.0)));
+        assert!(close(zero.powc(complex!(0.0, 0.0)), complex!(1.0, 0.0)));
+        assert_eq!(zero.powf(-1.0).real, f64::INFINITY);
+        assert_eq!(zero.powc(complex!(-1.0, 2.0)).real, f64::INFINITY);
+        assert!(zero.powc(complex!(0.0, 1.0)).real.is_nan());
+    }
+
+    proptest! {
+        #[test]
+        fn addition_is_commutative_and_associative(a in complex(), b in complex(), c in complex()) {
+            prop_assert!(close(a + b, b + a));
+            prop_assert!(close((a + b) + c, a + (b + c)));
+        }
+
+        #[test]
+        fn multiplication_is_commutative_and_associative(a in complex(), b in complex(), c in complex()) {
+            prop_assert!(close(a * b, b * a));
+            prop_assert!(close((a * b) * c, a * (b * c)));
+        }
+
+        #[test]
+        fn multiplication_distributes_over_addition(a in complex(), b in complex(), c in complex()) {
+            prop_assert!(close(a * (b + c), a * b + a * c));
+        }
+
+        #[test]
+        fn identities_and_inverses(a in nonzero_complex()) {
+            prop_assert!(close(a + complex!(0.0, 0.0), a));
+            prop_assert!(close(a * complex!(1.0, 0.0), a));
+            prop_assert!(close(a + -a, complex!(0.0, 0.0)));
+            prop_assert!(close(a * a.reciprocal(), complex!(1.0, 0.0)));
+        }
+
+        #[test]
+        fn division_inverts_multiplication(a in complex(), b in nonzero_complex()) {
+            prop_assert!(close((a * b) / b, a));
+            prop_assert!(close(a / b, a * b.reciprocal()));
+        }
+
+        #[test]
+        fn assign_operators_match_binary_operators(a in complex(), b in nonzero_complex(), x in 1e-3..1e3_f64) {
+            let mut z = a;
+            z *= b;
+            prop_assert!(close(z, a * b));
+            z /= b;
+            prop_assert!(close(z, a));
+            z *= x;
+            prop_assert!(close(z, a * x));
+            prop_assert!(close(z, x * a));
+            z /= x;
+            prop_assert!(close(z, a));
+        }
+
+        #[test]
+        fn mixed_real_operations(a in complex(), x in component()) {
+            prop_assert!(close(a * x, a * complex!(x, 0.0)));
+            prop_assert!(close(a + x, a + complex!(x, 0.0)));
+            prop_assert!(close(x - a, complex!(x, 0.0) - a));
+        }
+
+        #[test]
+        fn conjugate_is_multiplicative(a in complex(), b in complex()) {
+            prop_assert!(close((a * b).get_conjugate(), a.get_conjugate() * b.get_conjugate()));
+            prop_assert!(close(a * a.get_conjugate(), complex!(a.norm2(), 0.0)));
+        }
+
+        #[test]
+        fn polar_round_trip(a in nonzero_complex()) {
+            let (radius, theta) = a.to_polar();
+            prop_assert!(close(Complex::from_polar(radius, theta), a));
+        }
+
+        #[test]
+        fn euler_formula(theta in -10.0..10.0_f64) {
+            let euler = complex!(0.0, theta).exp();
+            prop_assert!(close(euler, complex!(theta.cos(), theta.sin())));
+            prop_assert!(close(euler, Complex::cis(theta)));
+        }
+
+        #[test]
+        fn de_moivre(theta in -PI..PI, n in -12..12_i32) {
+            let expected = Complex::cis(theta * n as f64);
+            prop_assert!(close(Complex::cis(theta).powi(n), expected));
+            prop_assert!(close(Complex::cis(theta).powf(n as f64), expected));
+        }
+
+        #[test]
+        fn exp_is_a_homomorphism(a in (-5.0..5.0_f64, -5.0..5.0_f64), b in (-5.0..5.0_f64, -5.0..5.0_f64)) {
+            let a = complex!(a.0, a.1);
+            let b = complex!(b.0, b.1);
+            prop_assert!(close((a + b).exp(), a.exp() * b.exp()));
+        }
+
+        #[test]
+        fn ln_inverts_exp(a in nonzero_complex()) {
+            prop_assert!(close(a.ln().exp(), a));
+        }
+
+        #[test]
+        fn powc_agrees_with_powf_on_real_exponents(a in nonzero_complex(), x in -3.0..3.0_f64) {
+            prop_assert!(close(a.powc(complex!(x, 0.0)), a.powf(x)));
+        }
+
+        #[test]
+        fn zero_exponents_give_one(a in complex()) {
+            prop_assert_eq!(a.powf(0.0), complex!(1.0, 0.0));
+            prop_assert_eq!(a.powc(complex!(0.0, 0.0)), complex!(1.0, 0.0));
+            prop_assert_eq!(a.powi(0), complex!(1.0, 0.0));
+        }
+
+        #[test]
+        fn zero_bases_follow_the_exponent_sign(
+            x in 1e-3..3.0_f64,
+            y in -3.0..3.0_f64,
+            n in 1..64_i32,
+        ) {
+            let zero = complex!(0.0_f64, 0.0);
+            prop_assert_eq!(zero.powf(x), zero);
+            prop_assert_eq!(zero.powc(complex!(x, y)), zero);
+            prop_assert_eq!(zero.powf(-x).real, f64::INFINITY);
+            prop_assert_eq!(zero.powc(complex!(-x, y)).real, f64::INFINITY);
+            prop_assert_eq!(zero.powf(x), Complex::from(0.0_f64.powf(x)));
+            prop_assert_eq!(zero.powi(n), zero);
+            prop_assert_eq!(zero.powi(-n).real, f64::INFINITY);
+            prop_assert_eq!(zero.powi(-n), zero.powf(-n as f64));
+        }
+    }
+}
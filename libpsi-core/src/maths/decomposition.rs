@@ -0,0 +1,473 @@
+use super::{Float, MathError, Matrix};
+
+const MAX_SVD_SWEEPS: usize = 60;
+
+/// Thin QR factorisation `A = QR` of an `m x n` matrix, with `Q` of size `m x k` having
+/// orthonormal columns and `R` of size `k x n` upper triangular, where `k = min(m, n)`.
+#[derive(Clone, Debug)]
+pub struct QRDecomposition<T: Float> {
+    pub q: Matrix<T>,
+    pub r: Matrix<T>,
+}
+
+/// Thin singular value decomposition `A = U Σ V†`. The singular values are real, non-negative
+/// and sorted in descending order, and the columns of `u` and `v` are orthonormal.
+#[derive(Clone, Debug)]
+pub struct SingularValueDecomposition<T: Float> {
+    pub u: Matrix<T>,
+    pub singular_values: Vec<T>,
+    pub v: Matrix<T>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Truncation {
+    /// Keep at most this many of the largest singular values.
+    Rank(usize),
+    /// Drop every singular value less than or equal to this threshold.
+    Threshold(f64),
+}
+
+impl<T: Float> SingularValueDecomposition<T> {
+    pub fn rank(&self, tolerance: f64) -> usize {
+        self.singular_values
+            .iter()
+            .filter(|value| value.to_f64() > tolerance)
+            .count()
+    }
+
+    pub fn truncate(&self, truncation: Truncation) -> SingularValueDecomposition<T> {
+        let keep = match truncation {
+            Truncation::Rank(rank) => rank.min(self.singular_values.len()),
+            Truncation::Threshold(threshold) => self.rank(threshold),
+        };
+
+        SingularValueDecomposition {
+            u: leading_columns(&self.u, keep),
+            singular_values: self.singular_values[..keep].to_vec(),
+            v: leading_columns(&self.v, keep),
+        }
+    }
+
+    pub fn reconstruct(&self) -> Matrix<T> {
+        let mut scaled = self.u.clone();
+        for i in 0..scaled.rows {
+            for (j, value) in self.singular_values.iter().enumerate() {
+                let entry = scaled.get(i, j) * *value;
+                scaled.set(i, j, entry);
+            }
+        }
+
+        scaled
+            .dot(&self.v.adjoint())
+            .expect("U and V share the number of singular values")
+    }
+}
+
+impl<T: Float> Matrix<T> {
+    /// Householder QR factorisation.
+    pub fn qr(&self) -> QRDecomposition<T> {
+        let (m, n) = (self.rows, self.cols);
+        let k = m.min(n);
+        let mut r = self.clone();
+        let mut q = Matrix::identity(m);
+
+        for column in 0..k.min(m.saturating_sub(1)) {
+            let x: Vec<T> = (column..m).map(|i| r.get(i, column)).collect();
+            if let Some(v) = householder_vector(&x) {
+                reflect_rows(&mut r, &v, column);
+                reflect_columns(&mut q, &v, column);
+            }
+        }
+
+        for i in 1..m {
+            for j in 0..i.min(n) {
+                r.set(i, j, T::zero());
+            }
+        }
+
+        QRDecomposition {
+            q: leading_columns(&q, k),
+            r: leading_rows(&r, k),
+        }
+    }
+
+    /// Thin SVD by one-sided (Hestenes) Jacobi rotations, which orthogonalise the columns of
+    /// `A` directly and give singular values to high relative accuracy.
+    pub fn svd(&self) -> Result<SingularValueDecomposition<T>, MathError> {
+        if self.rows < self.cols {
+            let transposed = self.adjoint().svd()?;
+            return Ok(SingularValueDecomposition {
+                u: transposed.v,
+                singular_values: transposed.singular_values,
+                v: transposed.u,
+            });
+        }
+
+        let n = self.cols;
+        let mut u = self.clone();
+        let mut v = Matrix::identity(n);
+
+        let mut converged = false;
+        for _ in 0..MAX_SVD_SWEEPS {
+            let mut rotated = false;
+            for p in 0..n {
+                for q in p + 1..n {
+                    rotated |= jacobi_orthogonalise(&mut u, &mut v, p, q);
+                }
+            }
+            if !rotated {
+                converged = true;
+                break;
+            }
+        }
+
+        if !converged {
+            return Err(MathError::NoConvergence {
+                iterations: MAX_SVD_SWEEPS,
+            });
+        }
+
+        let norms: Vec<T> = (0..n).map(|j| column_norm(&u, j)).collect();
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by(|a, b| norms[*b].to_f64().total_cmp(&norms[*a].to_f64()));
+
+        let largest = order.first().map_or(0.0, |j| norms[*j].to_f64());
+        let negligible = largest * self.rows.max(n) as f64 * T::epsilon().abs().to_f64();
+
+        let mut left = Matrix::zeros(self.rows, n);
+        let mut right = Matrix::zeros(n, n);
+        let mut singular_values = Vec::with_capacity(n);
+        let mut orthonormal = Vec::with_capacity(n);
+
+        for (new_column, old_column) in order.iter().enumerate() {
+            let norm = norms[*old_column];
+            for i in 0..n {
+                right.set(i, new_column, v.get(i, *old_column));
+            }
+
+            if norm.to_f64() > negligible {
+                for i in 0..self.rows {
+                    left.set(i, new_column, u.get(i, *old_column) / norm);
+                }
+                singular_values.push(norm);
+                orthonormal.push(new_column);
+            } else {
+                singular_values.push(T::zero());
+            }
+        }
+
+        complete_orthonormal_columns(&mut left, &orthonormal);
+
+        Ok(SingularValueDecomposition {
+            u: left,
+            singular_values,
+            v: right,
+        })
+    }
+
+    pub fn svd_truncated(
+        &self,
+        truncation: Truncation,
+    ) -> Result<SingularValueDecomposition<T>, MathError> {
+        Ok(self.svd()?.truncate(truncation))
+    }
+
+    /// Numerical rank: the number of singular values strictly greater than `tolerance`.
+    pub fn rank(&self, tolerance: f64) -> Result<usize, MathError> {
+        Ok(self.svd()?.rank(tolerance))
+    }
+}
+
+/// Unit vector `v` such that `(I - 2vv†) x` is a multiple of the first basis vector, or `None`
+/// when `x` is already zero.
+fn householder_vector<T: Float>(x: &[T]) -> Option<Vec<T>> {
+    let norm = T::sqrt(
+        x.iter()
+            .fold(T::zero(), |acc, value| acc + value.conj() * *value),
+    );
+    if norm.to_f64() == 0.0 {
+        return None;
+    }
+
+    let magnitude = x[0].abs();
+    let phase = if magnitude.to_f64() == 0.0 {
+        T::one()
+    } else {
+        x[0] / magnitude
+    };
+
+    let mut v = x.to_vec();
+    v[0] += phase * norm;
+    let v_norm = T::sqrt(
+        v.iter()
+            .fold(T::zero(), |acc, value| acc + value.conj() * *value),
+    );
+    for value in v.iter_mut() {
+        *value /= v_norm;
+    }
+    Some(v)
+}
+
+/// Applies `I - 2vv†` from the left to the rows `offset..` of `matrix`.
+pub(crate) fn reflect_rows<T: Float>(matrix: &mut Matrix<T>, v: &[T], offset: usize) {
+    let two = T::from_f64(2.0);
+    for j in 0..matrix.cols {
+        let mut projection = T::zero();
+        for (i, v_i) in v.iter().enumerate() {
+            projection += v_i.conj() * matrix.get(offset + i, j);
+        }
+        for (i, v_i) in v.iter().enumerate() {
+            let value = matrix.get(offset + i, j) - two * *v_i * projection;
+            matrix.set(offset + i, j, value);
+        }
+    }
+}
+
+/// Applies `I - 2vv†` from the right to the columns `offset..` of `matrix`.
+pub(crate) fn reflect_columns<T: Float>(matrix: &mut Matrix<T>, v: &[T], offset: usize) {
+    let two = T::from_f64(2.0);
+    for i in 0..matrix.rows {
+        let mut projection = T::zero();
+        for (j, v_j) in v.iter().enumerate() {
+            projection += matrix.get(i, offset + j) * *v_j;
+        }
+        for (j, v_j) in v.iter().enumerate() {
+            let value = matrix.get(i, offset + j) - two * projection * v_j.conj();
+            matrix.set(i, offset + j, value);
+        }
+    }
+}
+
+/// Rotates columns `p` and `q` of `u` (and `v` alongside) so that they become orthogonal,
+/// returning whether they were far enough from orthogonal to need it.
+fn jacobi_orthogonalise<T: Float>(
+    u: &mut Matrix<T>,
+    v: &mut Matrix<T>,
+    p: usize,
+    q: usize,
+) -> bool {
+    let mut alpha = T::zero();
+    let mut beta = T::zero();
+    let mut gamma = T::zero();
+    for i in 0..u.rows {
+        let (up, uq) = (u.get(i, p), u.get(i, q));
+        alpha += up.conj() * up;
+        beta += uq.conj() * uq;
+        gamma += up.conj() * uq;
+    }
+
+    let (alpha, beta) = (alpha.to_f64(), beta.to_f64());
+    let gamma_size = gamma.abs().to_f64();
+    if gamma_size == 0.0 || gamma_size <= T::epsilon().abs().to_f64() * (alpha * beta).sqrt() {
+        return false;
+    }
+
+    let phase = (gamma / T::from_f64(gamma_size)).conj();
+    let tau = (beta - alpha) / (2.0 * gamma_size);
+    let t = if tau >= 0.0 {
+        1.0 / (tau + (1.0 + tau * tau).sqrt())
+    } else {
+        -1.0 / (-tau + (1.0 + tau * tau).sqrt())
+    };
+    let c = 1.0 / (1.0 + t * t).sqrt();
+    let s = t * c;
+
+    let j_pp = T::from_f64(c);
+    let j_pq = T::from_f64(s);
+    let j_qp = phase * T::from_f64(-s);
+    let j_qq = phase * T::from_f64(c);
+
+    for matrix in [u, v] {
+        for i in 0..matrix.rows {
+            let (xp, xq) = (matrix.get(i, p), matrix.get(i, q));
+            matrix.set(i, p, xp * j_pp + xq * j_qp);
+            matrix.set(i, q, xp * j_pq + xq * j_qq);
+        }
+    }
+
+    true
+}
+
+fn column_norm<T: Float>(matrix: &Matrix<T>, column: usize) -> T {
+    T::sqrt((0..matrix.rows).fold(T::zero(), |acc, i| {
+        let value = matrix.get(i, column);
+        acc + value.conj() * value
+    }))
+}
+
+/// Fills every column not listed in `filled` with a unit vector orthogonal to all others, by
+/// Gram-Schmidt over the standard basis.
+fn complete_orthonormal_columns<T: Float>(matrix: &mut Matrix<T>, filled: &[usize]) {
+    let mut basis: Vec<usize> = filled.to_vec();
+    let mut candidate = 0;
+
+    for column in 0..matrix.cols {
+        if filled.contains(&column) {
+            continue;
+        }
+
+        while candidate < matrix.rows {
+            let mut vector: Vec<T> = (0..matrix.rows)
+                .map(|i| if i == candidate { T::one() } else { T::zero() })
+                .collect();
+            candidate += 1;
+
+            for _ in 0..2 {
+                for existing in &basis {
+                    let overlap = (0..matrix.rows).fold(T::zero(), |acc, i| {
+                        acc + matrix.get(i, *existing).conj() * vector[i]
+                    });
+                    for (i, value) in vector.iter_mut().enumerate() {
+                        *value -= overlap * matrix.get(i, *existing);
+                    }
+                }
+            }
+
+            let norm = T::sqrt(vector.iter().fold(T::zero(), |acc, x| acc + x.conj() * *x));
+            if norm.to_f64() > 0.5 {
+                for (i, value) in vector.into_iter().enumerate() {
+                    matrix.set(i, column, value / norm);
+                }
+                basis.push(column);
+                break;
+            }
+        }
+    }
+}
+
+fn leading_columns<T: Float>(matrix: &Matrix<T>, count: usize) -> Matrix<T> {
+    let mut result = Matrix::zeros(matrix.rows, count);
+    for i in 0..matrix.rows {
+        for j in 0..count {
+            result.set(i, j, matrix.get(i, j));
+        }
+    }
+    result
+}
+
+fn leading_rows<T: Float>(matrix: &Matrix<T>, count: usize) -> Matrix<T> {
+    Matrix::new(
+        count,
+        matrix.cols,
+        matrix.data[..count * matrix.cols].to_vec(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{random_matrix, random_real_matrix};
+    use crate::{complex, matrix};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    const TOLERANCE: f64 = 1e-10;
+
+    fn has_orthonormal_columns<T: Float>(matrix: &Matrix<T>) -> bool {
+        matrix
+            .adjoint()
+            .dot(matrix)
+            .unwrap()
+            .approx_eq(&Matrix::identity(matrix.cols), TOLERANCE)
+    }
+
+    fn check_qr<T: Float>(matrix: &Matrix<T>) {
+        let QRDecomposition { q, r } = matrix.qr();
+        let k = matrix.rows.min(matrix.cols);
+
+        assert_eq!((q.rows, q.cols), (matrix.rows, k));
+        assert_eq!((r.rows, r.cols), (k, matrix.cols));
+        assert!(has_orthonormal_columns(&q));
+        for i in 0..r.rows {
+            for j in 0..i.min(r.cols) {
+                assert_eq!(r.get(i, j).to_f64(), 0.0);
+            }
+        }
+        assert!(q.dot(&r).unwrap().approx_eq(matrix, TOLERANCE));
+    }
+
+    fn check_svd<T: Float>(matrix: &Matrix<T>) {
+        let svd = matrix.svd().unwrap();
+        let k = matrix.rows.min(matrix.cols);
+
+        assert_eq!(svd.singular_values.len(), k);
+        assert!(svd
+            .singular_values
+            .windows(2)
+            .all(|pair| pair[0].to_f64() >= pair[1].to_f64()));
+        assert!(has_orthonormal_columns(&svd.u));
+        assert!(has_orthonormal_columns(&svd.v));
+        assert!(svd.reconstruct().approx_eq(matrix, TOLERANCE));
+    }
+
+    #[test]
+    fn qr_of_real_and_complex_matrices() {
+        let mut rng = StdRng::seed_from_u64(5);
+        for (rows, cols) in [(1, 1), (3, 3), (6, 3), (3, 6), (8, 8)] {
+            check_qr(&random_real_matrix(&mut rng, rows, cols));
+            check_qr(&random_matrix(&mut rng, rows, cols));
+        }
+        check_qr(&matrix!([0.0, 1.0]; [0.0, 0.0]));
+    }
+
+    #[test]
+    fn svd_of_real_and_complex_matrices() {
+        let mut rng = StdRng::seed_from_u64(9);
+        for (rows, cols) in [(1, 1), (2, 2), (5, 3), (3, 5), (8, 8)] {
+            check_svd(&random_real_matrix(&mut rng, rows, cols));
+            check_svd(&random_matrix(&mut rng, rows, cols));
+        }
+    }
+
+    #[test]
+    fn singular_values_of_known_matrices() {
+        let diagonal = matrix!([3.0, 0.0]; [0.0, -4.0]);
+        let svd = diagonal.svd().unwrap();
+        assert!((svd.singular_values[0] - 4.0_f64).abs() < TOLERANCE);
+        assert!((svd.singular_values[1] - 3.0_f64).abs() < TOLERANCE);
+
+        let hadamard = matrix!([complex!(1.0, 0.0), complex!( 1.0, 0.0)];
+                               [complex!(1.0, 0.0), complex!(-1.0, 0.0)])
+            * complex!(1.0 / 2.0_f64.sqrt(), 0.0);
+        for value in hadamard.svd().unwrap().singular_values {
+            assert!((value - complex!(1.0, 0.0)).abs() < TOLERANCE);
+        }
+    }
+
+    #[test]
+    fn rank_deficient_matrices() {
+        let mut rng = StdRng::seed_from_u64(13);
+        let left = random_matrix(&mut rng, 6, 2);
+        let right = random_matrix(&mut rng, 2, 5);
+        let product = left.dot(&right).unwrap();
+
+        assert_eq!(product.rank(1e-9).unwrap(), 2);
+        check_svd(&product);
+
+        let zero: Matrix<f64> = Matrix::zeros(3, 2);
+        assert_eq!(zero.rank(1e-9).unwrap(), 0);
+        check_svd(&zero);
+    }
+
+    #[test]
+    fn truncation_by_rank_and_threshold() {
+        let mut rng = StdRng::seed_from_u64(17);
+        let matrix = random_real_matrix(&mut rng, 6, 4);
+        let svd = matrix.svd().unwrap();
+
+        let truncated = matrix.svd_truncated(Truncation::Rank(2)).unwrap();
+        assert_eq!(truncated.singular_values.len(), 2);
+        assert_eq!((truncated.u.cols, truncated.v.cols), (2, 2));
+
+        // Eckart-Young: the Frobenius error of the best rank-2 approximation is the norm of the
+        // discarded singular values.
+        let difference = matrix.subtract(&truncated.reconstruct()).unwrap();
+        let error: f64 = difference.data.iter().map(|x| x * x).sum::<f64>().sqrt();
+        let discarded: f64 = svd.singular_values[2..].iter().map(|x| x * x).sum::<f64>();
+        assert!((error - discarded.sqrt()).abs() < TOLERANCE);
+
+        let threshold = (svd.singular_values[1] + svd.singular_values[2]) / 2.0;
+        let truncated = svd.truncate(Truncation::Threshold(threshold));
+        assert_eq!(truncated.singular_values.len(), 2);
+        assert_eq!(svd.truncate(Truncation::Rank(10)).singular_values.len(), 4);
+    }
+}
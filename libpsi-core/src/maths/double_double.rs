@@ -0,0 +1,644 @@
+use super::{Float, Numeric};
+use core::{f64::consts, fmt, ops};
+
+/// Double-double scalar: the unevaluated sum `hi + lo` of two `f64` with `|lo| <= ulp(hi) / 2`,
+/// giving about 106 bits (32 decimal digits) of precision at a fraction of the speed of `f64`.
+/// Meant for precision studies, e.g. measuring how far an `f64` simulation drifts.
+///
+/// Arithmetic and `sqrt` are accurate to the full precision. The transcendental functions are
+/// refined from their `f64` counterparts and lose accuracy for very large arguments.
+#[derive(Clone, Copy, Default, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DoubleDouble {
+    hi: f64,
+    lo: f64,
+}
+
+const LN_2: DoubleDouble = DoubleDouble::from_parts(consts::LN_2, 2.3190468138462996e-17);
+const LN_10: DoubleDouble = DoubleDouble::from_parts(consts::LN_10, -2.1707562233822494e-16);
+const PI: DoubleDouble = DoubleDouble::from_parts(consts::PI, 1.2246467991473532e-16);
+const FRAC_PI_2: DoubleDouble = DoubleDouble::from_parts(consts::FRAC_PI_2, 6.123233995736766e-17);
+/// `2^-104`, half an ulp of one.
+const EPSILON: f64 = 4.930380657631324e-32;
+
+/// `a + b = s + e` exactly.
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let s = a + b;
+    let bb = s - a;
+    (s, (a - (s - bb)) + (b - bb))
+}
+
+/// [`two_sum`] for `|a| >= |b|`.
+fn quick_two_sum(a: f64, b: f64) -> (f64, f64) {
+    let s = a + b;
+    (s, b - (s - a))
+}
+
+/// `a * b = p + e` exactly.
+fn two_prod(a: f64, b: f64) -> (f64, f64) {
+    let p = a * b;
+    (p, libm::fma(a, b, -p))
+}
+
+impl DoubleDouble {
+    /// Builds `hi + lo` from two components that are already normalised.
+    pub const fn from_parts(hi: f64, lo: f64) -> DoubleDouble {
+        DoubleDouble { hi, lo }
+    }
+
+    /// Normalises the sum `hi + lo`.
+    pub fn new(hi: f64, lo: f64) -> DoubleDouble {
+        DoubleDouble::renormalize(two_sum(hi, lo))
+    }
+
+    pub fn hi(&self) -> f64 {
+        self.hi
+    }
+
+    pub fn lo(&self) -> f64 {
+        self.lo
+    }
+
+    fn renormalize((hi, lo): (f64, f64)) -> DoubleDouble {
+        if hi.is_finite() {
+            DoubleDouble { hi, lo }
+        } else {
+            DoubleDouble { hi, lo: 0.0 }
+        }
+    }
+
+    /// Exact multiplication by `2^exponent`.
+    fn ldexp(self, exponent: i32) -> DoubleDouble {
+        DoubleDouble {
+            hi: libm::scalbn(self.hi, exponent),
+            lo: libm::scalbn(self.lo, exponent),
+        }
+    }
+
+    fn square(self) -> DoubleDouble {
+        self * self
+    }
+
+    /// Sums the series `Σ terms` until the terms stop contributing.
+    fn series(
+        first: DoubleDouble,
+        mut next: impl FnMut(DoubleDouble, u32) -> DoubleDouble,
+    ) -> Self {
+        let mut sum = first;
+        let mut term = first;
+        for n in 1..64 {
+            term = next(term, n);
+            sum += term;
+            if libm::fabs(term.hi) <= EPSILON * libm::fabs(sum.hi) * 1e-2 {
+                break;
+            }
+        }
+        sum
+    }
+
+    /// Sine and cosine of `|x| <= π/4` by their Taylor series.
+    fn sin_cos_reduced(self) -> (DoubleDouble, DoubleDouble) {
+        let square = self.square();
+        let sin = DoubleDouble::series(self, |term, n| {
+            -term * square / DoubleDouble::from((2 * n * (2 * n + 1)) as f64)
+        });
+        let cos = DoubleDouble::series(DoubleDouble::one(), |term, n| {
+            -term * square / DoubleDouble::from((2 * n * (2 * n - 1)) as f64)
+        });
+        (sin, cos)
+    }
+
+    pub fn sin_cos(self) -> (DoubleDouble, DoubleDouble) {
+        if !self.is_finite() {
+            return (DoubleDouble::from(f64::NAN), DoubleDouble::from(f64::NAN));
+        }
+
+        let quadrant = libm::round(self.hi / FRAC_PI_2.hi);
+        let reduced = self - FRAC_PI_2 * DoubleDouble::from(quadrant);
+        let (sin, cos) = reduced.sin_cos_reduced();
+
+        match (quadrant as i64).rem_euclid(4) {
+            0 => (sin, cos),
+            1 => (cos, -sin),
+            2 => (-sin, -cos),
+            _ => (-cos, sin),
+        }
+    }
+}
+
+impl From<f64> for DoubleDouble {
+    fn from(value: f64) -> Self {
+        DoubleDouble { hi: value, lo: 0.0 }
+    }
+}
+
+impl ops::Add for DoubleDouble {
+    type Output = DoubleDouble;
+
+    fn add(self, other: DoubleDouble) -> DoubleDouble {
+        let (s1, s2) = two_sum(self.hi, other.hi);
+        let (t1, t2) = two_sum(self.lo, other.lo);
+        if !s1.is_finite() {
+            return DoubleDouble::from(s1);
+        }
+
+        let (s1, s2) = quick_two_sum(s1, s2 + t1);
+        DoubleDouble::renormalize(quick_two_sum(s1, s2 + t2))
+    }
+}
+
+impl ops::Sub for DoubleDouble {
+    type Output = DoubleDouble;
+
+    fn sub(self, other: DoubleDouble) -> DoubleDouble {
+        self + -other
+    }
+}
+
+impl ops::Mul for DoubleDouble {
+    type Output = DoubleDouble;
+
+    fn mul(self, other: DoubleDouble) -> DoubleDouble {
+        let (p, e) = two_prod(self.hi, other.hi);
+        if !p.is_finite() {
+            return DoubleDouble::from(p);
+        }
+
+        let e = e + (self.hi * other.lo + self.lo * other.hi);
+        DoubleDouble::renormalize(quick_two_sum(p, e))
+    }
+}
+
+impl ops::Div for DoubleDouble {
+    type Output = DoubleDouble;
+
+    /// Long division, one `f64` quotient digit at a time.
+    fn div(self, other: DoubleDouble) -> DoubleDouble {
+        let q1 = self.hi / other.hi;
+        if !q1.is_finite() || other.hi == 0.0 {
+            return DoubleDouble::from(q1);
+        }
+
+        let remainder = self - other * DoubleDouble::from(q1);
+        let q2 = remainder.hi / other.hi;
+        let remainder = remainder - other * DoubleDouble::from(q2);
+        let q3 = remainder.hi / other.hi;
+
+        let (q1, q2) = quick_two_sum(q1, q2);
+        DoubleDouble { hi: q1, lo: q2 } + DoubleDouble::from(q3)
+    }
+}
+
+impl ops::Neg for DoubleDouble {
+    type Output = DoubleDouble;
+
+    fn neg(self) -> DoubleDouble {
+        DoubleDouble {
+            hi: -self.hi,
+            lo: -self.lo,
+        }
+    }
+}
+
+macro_rules! impl_assign_ops {
+    ($($trait:ident, $method:ident, $op:tt);*) => {
+        $(
+            impl ops::$trait for DoubleDouble {
+                fn $method(&mut self, other: DoubleDouble) {
+                    *self = *self $op other;
+                }
+            }
+        )*
+    };
+}
+
+impl_assign_ops!(
+    AddAssign, add_assign, +;
+    SubAssign, sub_assign, -;
+    MulAssign, mul_assign, *;
+    DivAssign, div_assign, /
+);
+
+impl Numeric for DoubleDouble {
+    fn zero() -> Self {
+        DoubleDouble::from(0.0)
+    }
+
+    fn one() -> Self {
+        DoubleDouble::from(1.0)
+    }
+}
+
+impl Float for DoubleDouble {
+    /// One Newton step from the `f64` root, which doubles the number of correct bits.
+    fn sqrt(self) -> Self {
+        if self.hi <= 0.0 || !self.is_finite() {
+            return DoubleDouble::from(libm::sqrt(self.hi));
+        }
+
+        let inverse = 1.0 / libm::sqrt(self.hi);
+        let root = self.hi * inverse;
+        let (square, error) = two_prod(root, root);
+        let correction = (self
+            - DoubleDouble {
+                hi: square,
+                lo: error,
+            })
+        .hi * (inverse * 0.5);
+        DoubleDouble::renormalize(two_sum(root, correction))
+    }
+
+    /// One Newton step on the angle from the `f64` estimate.
+    fn atan2(y: Self, x: Self) -> Self {
+        let estimate = DoubleDouble::from(libm::atan2(y.hi, x.hi));
+        if (x.hi == 0.0 && y.hi == 0.0) || !x.is_finite() || !y.is_finite() {
+            return estimate;
+        }
+
+        let radius = (x * x + y * y).sqrt();
+        let (x, y) = (x / radius, y / radius);
+        let (sin, cos) = estimate.sin_cos();
+        if libm::fabs(x.hi) > libm::fabs(y.hi) {
+            estimate + (y - sin) / cos
+        } else {
+            estimate - (x - cos) / sin
+        }
+    }
+
+    fn hypot(self, other: Self) -> Self {
+        (self * self + other * other).sqrt()
+    }
+
+    /// `exp(x) = 2^k exp(r)` with `|r| <= ln(2) / 2048`; `exp(r) - 1` is summed as a Taylor
+    /// series and then squared back up ten times.
+    fn exp(self) -> Self {
+        if self.hi > 709.8 {
+            return DoubleDouble::from(f64::INFINITY);
+        }
+        if self.hi < -745.2 {
+            return DoubleDouble::zero();
+        }
+        if self.is_nan() {
+            return self;
+        }
+
+        let k = libm::round(self.hi / LN_2.hi);
+        let reduced = (self - LN_2 * DoubleDouble::from(k)).ldexp(-10);
+        let mut sum = DoubleDouble::series(reduced, |term, n| {
+            term * reduced / DoubleDouble::from((n + 1) as f64)
+        });
+        for _ in 0..10 {
+            sum = sum.ldexp(1) + sum.square();
+        }
+
+        (sum + DoubleDouble::one()).ldexp(k as i32)
+    }
+
+    fn exp2(self) -> Self {
+        (self * LN_2).exp()
+    }
+
+    /// One Newton step `y + x exp(-y) - 1` from the `f64` logarithm.
+    fn ln(self) -> Self {
+        if self.hi <= 0.0 || !self.is_finite() {
+            return DoubleDouble::from(libm::log(self.hi));
+        }
+
+        let estimate = DoubleDouble::from(libm::log(self.hi));
+        estimate + self * (-estimate).exp() - DoubleDouble::one()
+    }
+
+    fn log2(self) -> Self {
+        self.ln() / LN_2
+    }
+
+    fn log10(self) -> Self {
+        self.ln() / LN_10
+    }
+
+    fn cos(self) -> Self {
+        self.sin_cos().1
+    }
+
+    fn sin(self) -> Self {
+        self.sin_cos().0
+    }
+
+    fn tan(self) -> Self {
+        let (sin, cos) = self.sin_cos();
+        sin / cos
+    }
+
+    fn acos(self) -> Self {
+        let one = DoubleDouble::one();
+        DoubleDouble::atan2(((one - self) * (one + self)).sqrt(), self)
+    }
+
+    fn asin(self) -> Self {
+        let one = DoubleDouble::one();
+        DoubleDouble::atan2(self, ((one - self) * (one + self)).sqrt())
+    }
+
+    fn atan(self) -> Self {
+        DoubleDouble::atan2(self, DoubleDouble::one())
+    }
+
+    fn cosh(self) -> Self {
+        let exp = self.exp();
+        (exp + DoubleDouble::one() / exp).ldexp(-1)
+    }
+
+    /// Taylor series near zero, where `(e^x - e^-x) / 2` would cancel.
+    fn sinh(self) -> Self {
+        if libm::fabs(self.hi) < 0.5 {
+            let square = self.square();
+            return DoubleDouble::series(self, |term, n| {
+                term * square / DoubleDouble::from((2 * n * (2 * n + 1)) as f64)
+            });
+        }
+
+        let exp = self.exp();
+        (exp - DoubleDouble::one() / exp).ldexp(-1)
+    }
+
+    fn tanh(self) -> Self {
+        if libm::fabs(self.hi) > 40.0 {
+            return DoubleDouble::from(libm::copysign(1.0, self.hi));
+        }
+        self.sinh() / self.cosh()
+    }
+
+    fn acosh(self) -> Self {
+        (self + (self * self - DoubleDouble::one()).sqrt()).ln()
+    }
+
+    fn asinh(self) -> Self {
+        if self.hi < 0.0 {
+            return -(-self).asinh();
+        }
+        (self + (self * self + DoubleDouble::one()).sqrt()).ln()
+    }
+
+    fn atanh(self) -> Self {
+        let one = DoubleDouble::one();
+        ((one + self) / (one - self)).ln().ldexp(-1)
+    }
+
+    fn abs(self) -> Self {
+        if self.hi < 0.0 {
+            -self
+        } else {
+            self
+        }
+    }
+
+    fn powi(self, exponent: i32) -> Self {
+        let mut result = DoubleDouble::one();
+        let mut base = self;
+        let mut remaining = exponent.unsigned_abs();
+        while remaining > 0 {
+            if remaining & 1 == 1 {
+                result *= base;
+            }
+            base = base.square();
+            remaining >>= 1;
+        }
+
+        if exponent < 0 {
+            DoubleDouble::one() / result
+        } else {
+            result
+        }
+    }
+
+    /// Follows `f64::powf`: `0^0 = 1`, zero bases give zero or infinity by the sign of the
+    /// exponent, and negative bases only have integral powers.
+    fn powf(self, exponent: Self) -> Self {
+        if exponent == DoubleDouble::zero() {
+            return DoubleDouble::one();
+        }
+        if self == DoubleDouble::zero() {
+            return if exponent.hi > 0.0 {
+                DoubleDouble::zero()
+            } else if exponent.hi < 0.0 {
+                DoubleDouble::from(f64::INFINITY)
+            } else {
+                DoubleDouble::from(f64::NAN)
+            };
+        }
+        // An integral `hi` within `i32` leaves no room for a fractional `lo`.
+        if exponent.lo == 0.0 && exponent.hi.fract() == 0.0 && exponent.hi.abs() <= i32::MAX as f64
+        {
+            return self.powi(exponent.hi as i32);
+        }
+        (exponent * self.ln()).exp()
+    }
+
+    fn epsilon() -> Self {
+        DoubleDouble::from(EPSILON)
+    }
+
+    fn pi() -> Self {
+        PI
+    }
+
+    fn is_nan(self) -> bool {
+        self.hi.is_nan() || self.lo.is_nan()
+    }
+
+    fn is_finite(self) -> bool {
+        self.hi.is_finite()
+    }
+
+    fn from_f64(value: f64) -> Self {
+        DoubleDouble::from(value)
+    }
+
+    fn to_f64(self) -> f64 {
+        self.hi + self.lo
+    }
+
+    fn conj(self) -> Self {
+        self
+    }
+}
+
+impl fmt::Debug for DoubleDouble {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "DoubleDouble({:e} + {:e})", self.hi, self.lo)
+    }
+}
+
+impl fmt::Display for DoubleDouble {
+    /// Scientific notation with 32 significant digits, or `precision` digits after the point.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.is_finite() || self.hi == 0.0 {
+            return fmt::Display::fmt(&self.hi, f);
+        }
+
+        let digits = f.precision().unwrap_or(31) + 1;
+        let mut remainder = self.abs();
+        let mut exponent = libm::floor(libm::log10(remainder.hi)) as i32;
+        remainder /= DoubleDouble::from(10.0).powi(exponent);
+        if remainder.hi >= 10.0 {
+            remainder /= DoubleDouble::from(10.0);
+            exponent += 1;
+        } else if remainder.hi < 1.0 {
+            remainder *= DoubleDouble::from(10.0);
+            exponent -= 1;
+        }
+
+        let mut mantissa: Vec<u8> = Vec::with_capacity(digits + 1);
+        for _ in 0..=digits {
+            let digit = libm::floor(remainder.hi).clamp(0.0, 9.0);
+            mantissa.push(digit as u8);
+            remainder = (remainder - DoubleDouble::from(digit)) * DoubleDouble::from(10.0);
+        }
+
+        // Round half up on the extra digit, carrying into the leading digits.
+        if mantissa.pop().is_some_and(|digit| digit >= 5) {
+            let mut index = mantissa.len();
+            while index > 0 {
+                index -= 1;
+                if mantissa[index] < 9 {
+                    mantissa[index] += 1;
+                    break;
+                }
+                mantissa[index] = 0;
+                if index == 0 {
+                    mantissa.insert(0, 1);
+                    mantissa.pop();
+                    exponent += 1;
+                }
+            }
+        }
+
+        let sign = if self.hi < 0.0 { "-" } else { "" };
+        let text: String = mantissa
+            .iter()
+            .map(|digit| char::from(b'0' + digit))
+            .collect();
+        if text.len() > 1 {
+            write!(f, "{}{}.{}e{}", sign, &text[..1], &text[1..], exponent)
+        } else {
+            write!(f, "{}{}e{}", sign, text, exponent)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Complex;
+
+    const TOLERANCE: f64 = 1e-30;
+
+    fn dd(value: f64) -> DoubleDouble {
+        DoubleDouble::from(value)
+    }
+
+    fn assert_close(actual: DoubleDouble, expected: DoubleDouble) {
+        let error = (actual - expected).abs().to_f64();
+        let scale = expected.abs().to_f64().max(1.0);
+        assert!(
+            error <= TOLERANCE * scale,
+            "{} != {} (error {:e})",
+            actual,
+            expected,
+            error
+        );
+    }
+
+    #[test]
+    fn arithmetic_keeps_the_low_word() {
+        let tenth = dd(1.0) / dd(10.0);
+        assert_close(tenth * dd(10.0), dd(1.0));
+        assert!(tenth.lo() != 0.0);
+
+        let third = dd(1.0) / dd(3.0);
+        assert_close(third + third + third, dd(1.0));
+
+        // 1 + 2^-80 is not representable as f64.
+        let tiny = dd(2.0).powi(-80);
+        assert_eq!((dd(1.0) + tiny - dd(1.0)).to_f64(), 2.0_f64.powi(-80));
+    }
+
+    #[test]
+    fn constants_and_roots() {
+        let two = dd(2.0);
+        assert_close(two.sqrt() * two.sqrt(), two);
+        assert_close(DoubleDouble::pi(), dd(4.0) * DoubleDouble::atan(dd(1.0)));
+        assert_close(LN_2.exp(), two);
+        assert_close(two.ln(), LN_2);
+        assert_close(dd(1000.0).log10(), dd(3.0));
+        assert_close(dd(1.0).exp().ln(), dd(1.0));
+    }
+
+    #[test]
+    fn trigonometry() {
+        for x in [-7.5, -1.0, -0.3, 0.0, 0.25, 1.2, 3.0, 9.0] {
+            let x = dd(x) / dd(3.0);
+            let (sin, cos) = x.sin_cos();
+            assert_close(sin * sin + cos * cos, dd(1.0));
+            assert!((sin.to_f64() - libm::sin(x.to_f64())).abs() < 1e-15);
+            assert_close(DoubleDouble::atan2(sin, cos), x);
+        }
+
+        assert_close(DoubleDouble::pi().sin(), dd(0.0));
+        assert_close((DoubleDouble::pi() / dd(6.0)).sin(), dd(0.5));
+        assert_close(dd(0.5).asin() * dd(6.0), DoubleDouble::pi());
+        assert_close(dd(0.5).acos() * dd(3.0), DoubleDouble::pi());
+    }
+
+    #[test]
+    fn hyperbolic_functions_invert() {
+        for x in [-2.0, -0.1, 0.01, 0.7, 3.0] {
+            let x = dd(x);
+            assert_close(x.sinh().asinh(), x);
+            assert_close(x.tanh().atanh(), x);
+            assert_close(x.cosh() + x.sinh(), x.exp());
+        }
+        assert_close(dd(2.0).cosh().acosh(), dd(2.0));
+    }
+
+    #[test]
+    fn powers_follow_f64() {
+        let zero = DoubleDouble::zero();
+        assert_eq!(zero.powf(dd(0.0)), dd(1.0));
+        assert_eq!(zero.powf(dd(2.5)), zero);
+        assert_eq!(zero.powf(dd(-1.0)).to_f64(), f64::INFINITY);
+        assert_eq!(zero.powi(-2).to_f64(), f64::INFINITY);
+        assert!(zero.powf(dd(f64::NAN)).is_nan());
+
+        assert_eq!(dd(-2.0).powf(dd(2.0)), dd(4.0));
+        assert_eq!(dd(-2.0).powf(dd(-3.0)), dd(-0.125));
+        assert_close(dd(-3.0).powf(dd(5.0)), dd(-243.0));
+        assert!(dd(-2.0).powf(dd(0.5)).is_nan());
+
+        let third = dd(1.0) / dd(3.0);
+        assert_close(dd(8.0).powf(third), dd(2.0));
+        assert_close(dd(2.0).powf(dd(10.0)), dd(1024.0));
+    }
+
+    #[test]
+    fn complex_double_double() {
+        let z = Complex::new(dd(0.5), dd(1.0) / dd(3.0));
+        let w = Float::exp(Float::ln(z));
+        assert_close(w.real, z.real);
+        assert_close(w.imaginary, z.imaginary);
+
+        let phase = Complex::from_polar(dd(1.0), DoubleDouble::pi() / dd(4.0));
+        assert_close(phase.norm2(), dd(1.0));
+    }
+
+    #[test]
+    fn display_prints_all_digits() {
+        assert_eq!(
+            (dd(1.0) / dd(3.0)).to_string(),
+            "3.3333333333333333333333333333333e-1"
+        );
+        assert_eq!(format!("{:.4}", DoubleDouble::pi()), "3.1416e0");
+        assert_eq!(format!("{:.2}", dd(-9.999)), "-1.00e1");
+        assert_eq!(dd(0.0).to_string(), "0");
+    }
+}
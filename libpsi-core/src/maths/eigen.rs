@@ -0,0 +1,574 @@
+use super::decomposition::{reflect_columns, reflect_rows};
+use super::{Complex, Float, MathError, Matrix};
+
+const MAX_JACOBI_SWEEPS: usize = 100;
+const MAX_QR_ITERATIONS_PER_EIGENVALUE: usize = 100;
+
+/// Eigenvalues together with their eigenvectors, stored as the columns of `eigenvectors` in
+/// the same order as `eigenvalues`.
+#[derive(Clone, Debug)]
+pub struct EigenDecomposition<E> {
+    pub eigenvalues: Vec<E>,
+    pub eigenvectors: Matrix<Complex<f64>>,
+}
+
+/// Complex Schur decomposition `A = Q T Q†` with `T` upper triangular and `Q` unitary. For
+/// normal matrices such as unitaries `T` is diagonal, so `Q` holds an orthonormal eigenbasis even
+/// when eigenvalues repeat.
+#[derive(Clone, Debug)]
+pub struct SchurDecomposition {
+    pub q: Matrix<Complex<f64>>,
+    pub t: Matrix<Complex<f64>>,
+}
+
+impl<E: Copy> EigenDecomposition<E> {
+    pub fn eigenvector(&self, index: usize) -> Vec<Complex<f64>> {
+        (0..self.eigenvectors.rows)
+            .map(|row| self.eigenvectors.get(row, index))
+            .collect()
+    }
+
+    pub fn eigenpairs(&self) -> impl Iterator<Item = (E, Vec<Complex<f64>>)> + '_ {
+        self.eigenvalues
+            .iter()
+            .enumerate()
+            .map(|(index, value)| (*value, self.eigenvector(index)))
+    }
+}
+
+impl Matrix<Complex<f64>> {
+    /// Eigen-decomposition of a Hermitian matrix by cyclic complex Jacobi rotations. Only the
+    /// Hermitian part of the matrix is meaningful: eigenvalues come back real, sorted in
+    /// ascending order, with orthonormal eigenvectors even inside degenerate eigenspaces.
+    pub fn eigen_hermitian(&self) -> Result<EigenDecomposition<f64>, MathError> {
+        self.require_square()?;
+
+        let n = self.rows;
+        let mut a = self.clone();
+        let mut v = Matrix::identity(n);
+
+        let total = a.data.iter().map(|value| value.norm2()).sum::<f64>();
+        let threshold = f64::EPSILON * f64::EPSILON * total;
+
+        let mut converged = false;
+        for _ in 0..MAX_JACOBI_SWEEPS {
+            let off_diagonal = (0..n)
+                .flat_map(|i| (0..n).filter(move |j| *j != i).map(move |j| (i, j)))
+                .map(|(i, j)| a.get(i, j).norm2())
+                .sum::<f64>();
+            if off_diagonal <= threshold {
+                converged = true;
+                break;
+            }
+
+            for p in 0..n {
+                for q in p + 1..n {
+                    jacobi_rotate(&mut a, &mut v, p, q);
+                }
+            }
+        }
+
+        if !converged {
+            return Err(MathError::NoConvergence {
+                iterations: MAX_JACOBI_SWEEPS,
+            });
+        }
+
+        let eigenvalues: Vec<f64> = (0..n).map(|i| a.get(i, i).real).collect();
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by(|x, y| eigenvalues[*x].total_cmp(&eigenvalues[*y]));
+
+        Ok(EigenDecomposition {
+            eigenvalues: order.iter().map(|i| eigenvalues[*i]).collect(),
+            eigenvectors: permute_columns(&v, &order),
+        })
+    }
+
+    /// Eigen-decomposition of a general square matrix through a Hessenberg reduction followed by
+    /// the shifted QR algorithm, yielding the complex Schur form. Eigenvalues are sorted by real
+    /// then imaginary part and eigenvectors are normalised; for defective matrices the returned
+    /// eigenvectors of a repeated eigenvalue may be (nearly) parallel.
+    pub fn eigen(&self) -> Result<EigenDecomposition<Complex<f64>>, MathError> {
+        let n = self.rows;
+        let SchurDecomposition { q, t } = self.schur()?;
+
+        let eigenvalues: Vec<Complex<f64>> = (0..n).map(|i| t.get(i, i)).collect();
+        let scale = t.norm_one().max(f64::MIN_POSITIVE);
+        let mut eigenvectors = Matrix::zeros(n, n);
+
+        for k in 0..n {
+            let lambda = eigenvalues[k];
+            let mut y = vec![Complex::new(0.0, 0.0); n];
+            y[k] = Complex::new(1.0, 0.0);
+
+            for i in (0..k).rev() {
+                let mut sum = Complex::new(0.0, 0.0);
+                for (j, y_j) in y.iter().enumerate().take(k + 1).skip(i + 1) {
+                    sum += t.get(i, j) * *y_j;
+                }
+
+                let mut denominator = t.get(i, i) - lambda;
+                if denominator.abs() < f64::EPSILON * scale {
+                    denominator = Complex::new(f64::EPSILON * scale, 0.0);
+                }
+                y[i] = -sum / denominator;
+            }
+
+            let mut norm = 0.0;
+            for i in 0..n {
+                let mut value = Complex::new(0.0, 0.0);
+                for (j, y_j) in y.iter().enumerate().take(k + 1) {
+                    value += q.get(i, j) * *y_j;
+                }
+                norm += value.norm2();
+                eigenvectors.set(i, k, value);
+            }
+
+            let norm = norm.sqrt();
+            for i in 0..n {
+                let value = eigenvectors.get(i, k) / norm;
+                eigenvectors.set(i, k, value);
+            }
+        }
+
+        let mut order: Vec<usize> = (0..n).collect();
+        order.sort_by(|x, y| {
+            let (x, y) = (eigenvalues[*x], eigenvalues[*y]);
+            x.real
+                .total_cmp(&y.real)
+                .then(x.imaginary.total_cmp(&y.imaginary))
+        });
+
+        Ok(EigenDecomposition {
+            eigenvalues: order.iter().map(|i| eigenvalues[*i]).collect(),
+            eigenvectors: permute_columns(&eigenvectors, &order),
+        })
+    }
+
+    /// Complex Schur decomposition by the same Hessenberg reduction and shifted QR algorithm as
+    /// [`Matrix::eigen`].
+    pub fn schur(&self) -> Result<SchurDecomposition, MathError> {
+        self.require_square()?;
+
+        let (mut t, mut q) = self.hessenberg();
+        schur_from_hessenberg(&mut t, &mut q)?;
+        Ok(SchurDecomposition { q, t })
+    }
+
+    /// Householder reduction to upper Hessenberg form, returning `(H, Q)` with `A = Q H Q†`.
+    fn hessenberg(&self) -> (Matrix<Complex<f64>>, Matrix<Complex<f64>>) {
+        let n = self.rows;
+        let mut h = self.clone();
+        let mut q = Matrix::identity(n);
+
+        for k in 0..n.saturating_sub(2) {
+            let x: Vec<Complex<f64>> = (k + 1..n).map(|i| h.get(i, k)).collect();
+            let x_norm = x.iter().map(|value| value.norm2()).sum::<f64>().sqrt();
+            if x_norm == 0.0 {
+                continue;
+            }
+
+            let phase = if x[0].abs() == 0.0 {
+                Complex::new(1.0, 0.0)
+            } else {
+                x[0] / x[0].abs()
+            };
+
+            let mut v = x;
+            v[0] += phase * x_norm;
+            let v_norm = v.iter().map(|value| value.norm2()).sum::<f64>().sqrt();
+            for value in v.iter_mut() {
+                *value /= v_norm;
+            }
+
+            reflect_rows(&mut h, &v, k + 1);
+            reflect_columns(&mut h, &v, k + 1);
+            reflect_columns(&mut q, &v, k + 1);
+        }
+
+        (h, q)
+    }
+}
+
+/// One complex Jacobi rotation annihilating `a[p][q]` (and `a[q][p]`). The 2x2 block is first
+/// made real symmetric by the phase of `a[p][q]` and then diagonalised by a real rotation.
+fn jacobi_rotate(a: &mut Matrix<Complex<f64>>, v: &mut Matrix<Complex<f64>>, p: usize, q: usize) {
+    let apq = a.get(p, q);
+    let beta = apq.abs();
+    if beta == 0.0 {
+        return;
+    }
+
+    let phase = (apq / beta).get_conjugate();
+    let tau = (a.get(q, q).real - a.get(p, p).real) / (2.0 * beta);
+    let t = if tau >= 0.0 {
+        1.0 / (tau + (1.0 + tau * tau).sqrt())
+    } else {
+        -1.0 / (-tau + (1.0 + tau * tau).sqrt())
+    };
+    let c = 1.0 / (1.0 + t * t).sqrt();
+    let s = t * c;
+
+    // U = [[c, s], [-s e^{-iφ}, c e^{-iφ}]] acting on coordinates (p, q).
+    let u_pp = Complex::new(c, 0.0);
+    let u_pq = Complex::new(s, 0.0);
+    let u_qp = phase * -s;
+    let u_qq = phase * c;
+
+    for k in 0..a.rows {
+        let (akp, akq) = (a.get(k, p), a.get(k, q));
+        a.set(k, p, akp * u_pp + akq * u_qp);
+        a.set(k, q, akp * u_pq + akq * u_qq);
+
+        let (vkp, vkq) = (v.get(k, p), v.get(k, q));
+        v.set(k, p, vkp * u_pp + vkq * u_qp);
+        v.set(k, q, vkp * u_pq + vkq * u_qq);
+    }
+
+    for k in 0..a.cols {
+        let (apk, aqk) = (a.get(p, k), a.get(q, k));
+        a.set(
+            p,
+            k,
+            u_pp.get_conjugate() * apk + u_qp.get_conjugate() * aqk,
+        );
+        a.set(
+            q,
+            k,
+            u_pq.get_conjugate() * apk + u_qq.get_conjugate() * aqk,
+        );
+    }
+
+    a.set(p, q, Complex::new(0.0, 0.0));
+    a.set(q, p, Complex::new(0.0, 0.0));
+}
+
+/// Reduces an upper Hessenberg matrix to upper triangular Schur form in place with single-shift
+/// QR sweeps, accumulating the unitary similarity transforms into `q`.
+fn schur_from_hessenberg(
+    h: &mut Matrix<Complex<f64>>,
+    q: &mut Matrix<Complex<f64>>,
+) -> Result<(), MathError> {
+    let n = h.rows;
+    if n < 2 {
+        return Ok(());
+    }
+
+    let max_iterations = MAX_QR_ITERATIONS_PER_EIGENVALUE * n;
+    let mut total_iterations = 0;
+    let mut iterations_since_deflation = 0;
+    let mut hi = n - 1;
+
+    while hi > 0 {
+        let mut lo = hi;
+        while lo > 0 {
+            let subdiagonal = h.get(lo, lo - 1).abs();
+            let diagonal = h.get(lo - 1, lo - 1).abs() + h.get(lo, lo).abs();
+            if subdiagonal <= f64::EPSILON * diagonal || subdiagonal < f64::MIN_POSITIVE {
+                h.set(lo, lo - 1, Complex::new(0.0, 0.0));
+                break;
+            }
+            lo -= 1;
+        }
+
+        if lo == hi {
+            hi -= 1;
+            iterations_since_deflation = 0;
+            continue;
+        }
+
+        total_iterations += 1;
+        iterations_since_deflation += 1;
+        if total_iterations > max_iterations {
+            return Err(MathError::NoConvergence {
+                iterations: total_iterations,
+            });
+        }
+
+        let shift = if iterations_since_deflation % 10 == 0 {
+            // Exceptional shift to break cycles the Wilkinson shift can get stuck in.
+            h.get(hi, hi) + Complex::new(h.get(hi, hi - 1).abs(), 0.0)
+        } else {
+            wilkinson_shift(h, hi)
+        };
+
+        qr_sweep(h, q, lo, hi, shift);
+    }
+
+    for i in 1..n {
+        for j in 0..i {
+            h.set(i, j, Complex::new(0.0, 0.0));
+        }
+    }
+
+    Ok(())
+}
+
+/// Eigenvalue of the trailing 2x2 block of the active window closest to its last diagonal entry.
+fn wilkinson_shift(h: &Matrix<Complex<f64>>, hi: usize) -> Complex<f64> {
+    let a = h.get(hi - 1, hi - 1);
+    let b = h.get(hi - 1, hi);
+    let c = h.get(hi, hi - 1);
+    let d = h.get(hi, hi);
+
+    let half_trace = (a + d) * 0.5;
+    let half_difference = (a - d) * 0.5;
+    let discriminant = Float::sqrt(half_difference * half_difference + b * c);
+
+    let first = half_trace + discriminant;
+    let second = half_trace - discriminant;
+    if (first - d).abs() < (second - d).abs() {
+        first
+    } else {
+        second
+    }
+}
+
+/// Explicitly shifted QR step `H - μI = GR`, `H <- RG + μI` on the window `lo..=hi`, realised
+/// with Givens rotations applied to the whole matrix so the full Schur form is maintained.
+fn qr_sweep(
+    h: &mut Matrix<Complex<f64>>,
+    q: &mut Matrix<Complex<f64>>,
+    lo: usize,
+    hi: usize,
+    shift: Complex<f64>,
+) {
+    let n = h.rows;
+    for i in lo..=hi {
+        let value = h.get(i, i) - shift;
+        h.set(i, i, value);
+    }
+
+    let mut rotations = Vec::with_capacity(hi - lo);
+    for k in lo..hi {
+        let x = h.get(k, k);
+        let y = h.get(k + 1, k);
+        let r = (x.norm2() + y.norm2()).sqrt();
+        let (c, s) = if r == 0.0 {
+            (Complex::new(1.0, 0.0), Complex::new(0.0, 0.0))
+        } else {
+            (x / r, y / r)
+        };
+
+        for j in k..n {
+            let (top, bottom) = (h.get(k, j), h.get(k + 1, j));
+            h.set(k, j, c.get_conjugate() * top + s.get_conjugate() * bottom);
+            h.set(k + 1, j, -s * top + c * bottom);
+        }
+        rotations.push((c, s));
+    }
+
+    for (offset, (c, s)) in rotations.into_iter().enumerate() {
+        let k = lo + offset;
+        for i in 0..=(k + 2).min(hi) {
+            let (left, right) = (h.get(i, k), h.get(i, k + 1));
+            h.set(i, k, left * c + right * s);
+            h.set(
+                i,
+                k + 1,
+                -left * s.get_conjugate() + right * c.get_conjugate(),
+            );
+        }
+        for i in 0..n {
+            let (left, right) = (q.get(i, k), q.get(i, k + 1));
+            q.set(i, k, left * c + right * s);
+            q.set(
+                i,
+                k + 1,
+                -left * s.get_conjugate() + right * c.get_conjugate(),
+            );
+        }
+    }
+
+    for i in lo..=hi {
+        let value = h.get(i, i) + shift;
+        h.set(i, i, value);
+    }
+}
+
+fn permute_columns(matrix: &Matrix<Complex<f64>>, order: &[usize]) -> Matrix<Complex<f64>> {
+    let mut result = Matrix::zeros(matrix.rows, matrix.cols);
+    for (new_column, old_column) in order.iter().enumerate() {
+        for row in 0..matrix.rows {
+            result.set(row, new_column, matrix.get(row, *old_column));
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{random_hermitian, random_matrix};
+    use crate::{complex, matrix};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    const TOLERANCE: f64 = 1e-10;
+
+    fn diagonal(values: &[Complex<f64>]) -> Matrix<Complex<f64>> {
+        let mut result = Matrix::zeros(values.len(), values.len());
+        for (i, value) in values.iter().enumerate() {
+            result.set(i, i, *value);
+        }
+        result
+    }
+
+    fn assert_eigenpairs<E: Copy + Into<Complex<f64>>>(
+        matrix: &Matrix<Complex<f64>>,
+        decomposition: &EigenDecomposition<E>,
+    ) {
+        let values: Vec<Complex<f64>> = decomposition
+            .eigenvalues
+            .iter()
+            .map(|value| (*value).into())
+            .collect();
+        let left = matrix.dot(&decomposition.eigenvectors).unwrap();
+        let right = decomposition.eigenvectors.dot(&diagonal(&values)).unwrap();
+        assert!(left.approx_eq(&right, TOLERANCE));
+    }
+
+    fn pauli_matrices() -> [Matrix<Complex<f64>>; 3] {
+        [
+            matrix!([complex!(0.0, 0.0), complex!(1.0, 0.0)];
+                    [complex!(1.0, 0.0), complex!(0.0, 0.0)]),
+            matrix!([complex!(0.0, 0.0), complex!(0.0, -1.0)];
+                    [complex!(0.0, 1.0), complex!(0.0,  0.0)]),
+            matrix!([complex!(1.0, 0.0), complex!( 0.0, 0.0)];
+                    [complex!(0.0, 0.0), complex!(-1.0, 0.0)]),
+        ]
+    }
+
+    #[test]
+    fn pauli_spectra() {
+        for pauli in pauli_matrices() {
+            let hermitian = pauli.eigen_hermitian().unwrap();
+            assert_eq!(hermitian.eigenvalues.len(), 2);
+            assert!((hermitian.eigenvalues[0] + 1.0).abs() < TOLERANCE);
+            assert!((hermitian.eigenvalues[1] - 1.0).abs() < TOLERANCE);
+            assert!(hermitian.eigenvectors.is_unitary(TOLERANCE));
+            assert_eigenpairs(&pauli, &hermitian);
+
+            let general = pauli.eigen().unwrap();
+            assert!((general.eigenvalues[0] - complex!(-1.0, 0.0)).abs() < TOLERANCE);
+            assert!((general.eigenvalues[1] - complex!(1.0, 0.0)).abs() < TOLERANCE);
+            assert_eigenpairs(&pauli, &general);
+        }
+    }
+
+    #[test]
+    fn random_hermitian_matrices() {
+        let mut rng = StdRng::seed_from_u64(7);
+        for n in [1, 2, 3, 5, 8, 16] {
+            let matrix = random_hermitian(&mut rng, n);
+            let decomposition = matrix.eigen_hermitian().unwrap();
+
+            assert!(decomposition.eigenvectors.is_unitary(TOLERANCE));
+            assert!(decomposition.eigenvalues.windows(2).all(|w| w[0] <= w[1]));
+            assert_eigenpairs(&matrix, &decomposition);
+
+            let trace = matrix.trace().unwrap().real;
+            let sum: f64 = decomposition.eigenvalues.iter().sum();
+            assert!((trace - sum).abs() < TOLERANCE);
+
+            let general = matrix.eigen().unwrap();
+            for (a, b) in general.eigenvalues.iter().zip(&decomposition.eigenvalues) {
+                assert!((*a - complex!(*b, 0.0)).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn degenerate_spectrum_keeps_orthonormal_eigenvectors() {
+        let mut rng = StdRng::seed_from_u64(11);
+        let unitary = random_hermitian(&mut rng, 4)
+            .eigen_hermitian()
+            .unwrap()
+            .eigenvectors;
+        let spectrum = diagonal(&[
+            complex!(2.0, 0.0),
+            complex!(-1.0, 0.0),
+            complex!(2.0, 0.0),
+            complex!(2.0, 0.0),
+        ]);
+        let matrix = unitary
+            .dot(&spectrum)
+            .unwrap()
+            .dot(&unitary.adjoint())
+            .unwrap();
+
+        let decomposition = matrix.eigen_hermitian().unwrap();
+        let expected = [-1.0, 2.0, 2.0, 2.0];
+        for (value, expected) in decomposition.eigenvalues.iter().zip(expected) {
+            assert!((value - expected).abs() < TOLERANCE);
+        }
+        assert!(decomposition.eigenvectors.is_unitary(TOLERANCE));
+        assert_eigenpairs(&matrix, &decomposition);
+
+        let identity: Matrix<Complex<f64>> = Matrix::identity(3);
+        let decomposition = identity.eigen_hermitian().unwrap();
+        assert_eq!(decomposition.eigenvalues, vec![1.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn general_matrices() {
+        let rotation = matrix!([complex!(0.0, 0.0), complex!(-1.0, 0.0)];
+                               [complex!(1.0, 0.0), complex!( 0.0, 0.0)]);
+        let decomposition = rotation.eigen().unwrap();
+        assert!((decomposition.eigenvalues[0] - complex!(0.0, -1.0)).abs() < TOLERANCE);
+        assert!((decomposition.eigenvalues[1] - complex!(0.0, 1.0)).abs() < TOLERANCE);
+        assert_eigenpairs(&rotation, &decomposition);
+
+        let mut rng = StdRng::seed_from_u64(3);
+        for n in [1, 2, 3, 6, 10] {
+            let matrix = random_matrix(&mut rng, n, n);
+            let decomposition = matrix.eigen().unwrap();
+            assert_eigenpairs(&matrix, &decomposition);
+
+            let trace = matrix.trace().unwrap();
+            let sum = decomposition
+                .eigenvalues
+                .iter()
+                .fold(complex!(0.0, 0.0), |acc, value| acc + *value);
+            assert!((trace - sum).abs() < TOLERANCE);
+        }
+    }
+
+    #[test]
+    fn schur_form_of_normal_matrices_is_diagonal() {
+        // CNOT: a unitary with a triply repeated eigenvalue.
+        let cnot = Matrix::new(
+            4,
+            4,
+            [1, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 1, 0, 0, 1, 0]
+                .into_iter()
+                .map(|x| complex!(x as f64, 0.0))
+                .collect(),
+        );
+        let SchurDecomposition { q, t } = cnot.schur().unwrap();
+        assert!(q.is_unitary(TOLERANCE));
+        assert!(q
+            .dot(&t)
+            .unwrap()
+            .dot(&q.adjoint())
+            .unwrap()
+            .approx_eq(&cnot, TOLERANCE));
+        for row in 0..4 {
+            for col in 0..4 {
+                if row != col {
+                    assert!(t.get(row, col).abs() < TOLERANCE);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn non_square_matrices_are_rejected() {
+        let matrix: Matrix<Complex<f64>> = Matrix::zeros(2, 3);
+        assert_eq!(
+            matrix.eigen_hermitian().unwrap_err(),
+            MathError::NotSquare { rows: 2, cols: 3 }
+        );
+        assert!(matrix.eigen().is_err());
+        assert!(matrix.schur().is_err());
+    }
+}
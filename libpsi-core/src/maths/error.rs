@@ -0,0 +1,42 @@
+use core::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MathError {
+    /// Operand shapes are incompatible, given as `(rows, cols)` of the left and right operand.
+    DimensionMismatch {
+        left: (usize, usize),
+        right: (usize, usize),
+    },
+    NotSquare {
+        rows: usize,
+        cols: usize,
+    },
+    Singular,
+    NoConvergence {
+        iterations: usize,
+    },
+    /// An operand holds an infinite or NaN entry.
+    NotFinite,
+}
+
+impl fmt::Display for MathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MathError::DimensionMismatch { left, right } => write!(
+                f,
+                "dimension mismatch between {}x{} and {}x{} operands",
+                left.0, left.1, right.0, right.1
+            ),
+            MathError::NotSquare { rows, cols } => {
+                write!(f, "expected a square matrix, found {}x{}", rows, cols)
+            }
+            MathError::Singular => write!(f, "matrix is singular to working precision"),
+            MathError::NoConvergence { iterations } => {
+                write!(f, "iteration did not converge after {} steps", iterations)
+            }
+            MathError::NotFinite => write!(f, "matrix has non-finite entries"),
+        }
+    }
+}
+
+impl std::error::Error for MathError {}
@@ -1,4 +1,4 @@
-use super::Float;
+use super::{parallel, Complex, Float, MathError};
 use core::{fmt, ops};
 
 #[macro_export]
@@ -46,12 +46,45 @@ macro_rules! impl_matrix_ops {
 }
 
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        try_from = "RawMatrix<T>",
+        bound(deserialize = "T: serde::Deserialize<'de>")
+    )
+)]
 pub struct Matrix<T: Float> {
     pub data: Vec<T>,
     pub rows: usize,
     pub cols: usize,
 }
 
+/// Unchecked form of [`Matrix`], validated before it is accepted by deserialization.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct RawMatrix<T> {
+    data: Vec<T>,
+    rows: usize,
+    cols: usize,
+}
+
+#[cfg(feature = "serde")]
+impl<T: Float> TryFrom<RawMatrix<T>> for Matrix<T> {
+    type Error = MathError;
+
+    fn try_from(raw: RawMatrix<T>) -> Result<Self, Self::Error> {
+        if raw.rows.checked_mul(raw.cols) != Some(raw.data.len()) {
+            return Err(MathError::DimensionMismatch {
+                left: (raw.rows, raw.cols),
+                right: (raw.data.len(), 1),
+            });
+        }
+
+        Ok(Matrix::new(raw.rows, raw.cols, raw.data))
+    }
+}
+
 impl<T: Float> Matrix<T> {
     pub fn new(rows: usize, cols: usize, data: Vec<T>) -> Self {
         Matrix { data, rows, cols }
@@ -65,50 +98,107 @@ impl<T: Float> Matrix<T> {
         self.data[row * self.cols + col] = value;
     }
 
-    pub fn dot(&self, other: &Self) -> Option<Matrix<T>> {
+    pub fn dot(&self, other: &Self) -> Result<Matrix<T>, MathError> {
         if self.cols != other.rows {
-            return None;
+            return Err(self.mismatch(other));
         }
 
-        let mut result = Matrix::new(
-            self.rows,
-            other.cols,
-            vec![T::zero(); self.rows * other.cols],
-        );
-        for i in 0..self.rows {
-            for j in 0..other.cols {
-                let mut sum = T::zero();
-                for k in 0..self.cols {
-                    sum = sum + (self.get(i, k) * other.get(k, j));
-                }
-                result.set(i, j, sum);
-            }
-        }
-        Some(result)
+        Ok(self.dot_unchecked(other))
     }
 
-    pub fn kronecker(&self, other: &Self) -> Matrix<T> {
-        let new_rows = self.rows * other.rows;
-        let new_cols = self.cols * other.cols;
+    /// [`Matrix::dot`] without the shape check, which is only asserted in debug builds.
+    pub fn dot_unchecked(&self, other: &Self) -> Matrix<T> {
+        debug_assert_eq!(self.cols, other.rows, "matrix product dimension mismatch");
 
-        let mut result = Matrix::new(new_rows, new_cols, vec![T::zero(); new_rows * new_cols]);
+        let mut result = Matrix::zeros(self.rows, other.cols);
+        self.accumulate_product(other, &mut result);
+        result
+    }
 
-        for i in 0..self.rows {
-            for j in 0..self.cols {
-                let self_val = self.get(i, j);
-                for k in 0..other.rows {
-                    for l in 0..other.cols {
-                        let result_row = i * other.rows + k;
-                        let result_col = j * other.cols + l;
-                        result.set(result_row, result_col, self_val.clone() * other.get(k, l));
+    /// Writes `self * other` into `output`, reusing its allocation. `output` must already have
+    /// the shape of the product.
+    pub fn dot_into(&self, other: &Self, output: &mut Matrix<T>) -> Result<(), MathError> {
+        if self.cols != other.rows {
+            return Err(self.mismatch(other));
+        }
+        if output.rows != self.rows || output.cols != other.cols {
+            return Err(MathError::DimensionMismatch {
+                left: (self.rows, other.cols),
+                right: (output.rows, output.cols),
+            });
+        }
+
+        output.data.fill(T::zero());
+        self.accumulate_product(other, output);
+        Ok(())
+    }
+
+    /// Blocked `output += self * other` in i-k-j order, so the innermost loop streams over
+    /// contiguous rows of `other` and `output` through [`Float::mul_add`], and each block of
+    /// `other` stays in cache while it is reused. Bands of output rows are independent, which is
+    /// how the work is split across threads; every element still accumulates in ascending `k`.
+    fn accumulate_product(&self, other: &Self, output: &mut Matrix<T>) {
+        const BLOCK: usize = 64;
+        const BAND: usize = 16;
+        let (n, depth, m) = (self.rows, self.cols, other.cols);
+
+        parallel::for_each_chunk(&mut output.data, BAND * m, n * depth * m, |band, rows| {
+            let first_row = band * BAND;
+            for k_block in (0..depth).step_by(BLOCK) {
+                let k_end = (k_block + BLOCK).min(depth);
+                for j_block in (0..m).step_by(BLOCK) {
+                    let j_end = (j_block + BLOCK).min(m);
+                    for (offset, output_row) in rows.chunks_mut(m).enumerate() {
+                        let i = first_row + offset;
+                        let output_row = &mut output_row[j_block..j_end];
+                        for k in k_block..k_end {
+                            let alpha = self.data[i * depth + k];
+                            let other_row = &other.data[k * m + j_block..k * m + j_end];
+                            T::mul_add(alpha, other_row, output_row);
+                        }
                     }
                 }
             }
-        }
+        });
+    }
 
+    pub fn kronecker(&self, other: &Self) -> Matrix<T> {
+        let mut result = Matrix::zeros(self.rows * other.rows, self.cols * other.cols);
+        self.write_kronecker(other, &mut result);
         result
     }
 
+    /// Writes `self ⊗ other` into `output`, reusing its allocation. `output` must already have
+    /// the shape of the product.
+    pub fn kronecker_into(&self, other: &Self, output: &mut Matrix<T>) -> Result<(), MathError> {
+        let (rows, cols) = (self.rows * other.rows, self.cols * other.cols);
+        if output.rows != rows || output.cols != cols {
+            return Err(MathError::DimensionMismatch {
+                left: (rows, cols),
+                right: (output.rows, output.cols),
+            });
+        }
+
+        self.write_kronecker(other, output);
+        Ok(())
+    }
+
+    fn write_kronecker(&self, other: &Self, output: &mut Matrix<T>) {
+        let output_cols = self.cols * other.cols;
+        let work = output.data.len();
+
+        parallel::for_each_chunk(&mut output.data, output_cols, work, |row, output_row| {
+            let (i, k) = (row / other.rows, row % other.rows);
+            let other_row = &other.data[k * other.cols..(k + 1) * other.cols];
+            for (j, block) in output_row.chunks_mut(other.cols).enumerate() {
+                let self_value = self.data[i * self.cols + j];
+                for (target, value) in block.iter_mut().zip(other_row) {
+                    *target = self_value * *value;
+                }
+            }
+        });
+    }
+
     pub fn transpose(&self) -> Matrix<T> {
         let mut result = Matrix::new(self.cols, self.rows, vec![T::zero(); self.cols * self.rows]);
 
@@ -122,11 +212,21 @@ impl<T: Float> Matrix<T> {
         result
     }
 
-    pub fn add_to(&self, other: &Self) -> Option<Matrix<T>> {
+    pub fn add_to(&self, other: &Self) -> Result<Matrix<T>, MathError> {
         if self.rows != other.rows || self.cols != other.cols {
-            return None;
+            return Err(self.mismatch(other));
         }
 
+        Ok(self.add_unchecked(other))
+    }
+
+    /// [`Matrix::add_to`] without the shape check, which is only asserted in debug builds.
+    pub fn add_unchecked(&self, other: &Self) -> Matrix<T> {
+        debug_assert!(
+            self.rows == other.rows && self.cols == other.cols,
+            "matrix sum dimension mismatch"
+        );
+
         let mut result = Matrix::new(self.rows, self.cols, vec![T::zero(); self.rows * self.cols]);
 
         for i in 0..self.rows {
@@ -135,14 +235,24 @@ impl<T: Float> Matrix<T> {
                 result.set(i, j, sum);
             }
         }
-        Some(result)
+        result
     }
 
-    pub fn subtract(&self, other: &Self) -> Option<Matrix<T>> {
+    pub fn subtract(&self, other: &Self) -> Result<Matrix<T>, MathError> {
         if self.rows != other.rows || self.cols != other.cols {
-            return None;
+            return Err(self.mismatch(other));
         }
 
+        Ok(self.subtract_unchecked(other))
+    }
+
+    /// [`Matrix::subtract`] without the shape check, which is only asserted in debug builds.
+    pub fn subtract_unchecked(&self, other: &Self) -> Matrix<T> {
+        debug_assert!(
+            self.rows == other.rows && self.cols == other.cols,
+            "matrix difference dimension mismatch"
+        );
+
         let mut result = Matrix::new(self.rows, self.cols, vec![T::zero(); self.rows * self.cols]);
 
         for i in 0..self.rows {
@@ -151,20 +261,349 @@ impl<T: Float> Matrix<T> {
                 result.set(i, j, diff);
             }
         }
-        Some(result)
+        result
     }
 
     pub fn scale(&self, scalar: T) -> Matrix<T> {
-        let mut result = Matrix::new(self.rows, self.cols, vec![T::zero(); self.rows * self.cols]);
+        let mut result = self.clone();
+        let work = result.data.len();
+
+        parallel::for_each_chunk(&mut result.data, parallel::CHUNK, work, |_, chunk| {
+            for value in chunk {
+                *value *= scalar;
+            }
+        });
+        result
+    }
+
+    pub fn zeros(rows: usize, cols: usize) -> Matrix<T> {
+        Matrix::new(rows, cols, vec![T::zero(); rows * cols])
+    }
+
+    pub fn identity(n: usize) -> Matrix<T> {
+        let mut result = Matrix::zeros(n, n);
+        for i in 0..n {
+            result.set(i, i, T::one());
+        }
+        result
+    }
+
+    pub fn is_square(&self) -> bool {
+        self.rows == self.cols
+    }
+
+    pub fn conjugate(&self) -> Matrix<T> {
+        Matrix::new(
+            self.rows,
+            self.cols,
+            self.data.iter().map(|value| value.conj()).collect(),
+        )
+    }
+
+    /// Conjugate transpose (A†), which reduces to the transpose for real matrices.
+    pub fn adjoint(&self) -> Matrix<T> {
+        let mut result = Matrix::zeros(self.cols, self.rows);
 
         for i in 0..self.rows {
             for j in 0..self.cols {
-                let scaled_value = self.get(i, j) * scalar;
-                result.set(i, j, scaled_value);
+                result.set(j, i, self.get(i, j).conj());
+            }
+        }
+
+        result
+    }
+
+    pub fn trace(&self) -> Result<T, MathError> {
+        self.require_square()?;
+        Ok((0..self.rows).fold(T::zero(), |acc, i| acc + self.get(i, i)))
+    }
+
+    /// Product of the LU pivots, which is zero only when a pivot is exactly zero.
+    pub fn determinant(&self) -> Result<T, MathError> {
+        self.require_square()?;
+
+        let decomposition = self.lu_decompose();
+
+        let mut determinant = if decomposition.parity_odd {
+            -T::one()
+        } else {
+            T::one()
+        };
+        for i in 0..self.rows {
+            determinant *= decomposition.lu.get(i, i);
+        }
+        Ok(determinant)
+    }
+
+    /// Inverse through an LU decomposition with partial pivoting, failing with
+    /// [`MathError::Singular`] when a pivot vanishes to working precision relative to the largest
+    /// entry of its row, so that badly scaled but regular matrices still invert.
+    pub fn inverse(&self) -> Result<Matrix<T>, MathError> {
+        self.require_square()?;
+
+        let n = self.rows;
+        let decomposition = self.lu_decompose();
+        let lu = &decomposition.lu;
+        let epsilon = n as f64 * T::epsilon().abs().to_f64();
+        for (k, row) in decomposition.permutation.iter().enumerate() {
+            let scale = (0..n)
+                .map(|j| self.get(*row, j).abs().to_f64())
+                .fold(0.0, f64::max);
+            if lu.get(k, k).abs().to_f64() <= epsilon * scale {
+                return Err(MathError::Singular);
+            }
+        }
+        let mut result = Matrix::zeros(n, n);
+
+        for column in 0..n {
+            let mut x: Vec<T> = (0..n)
+                .map(|i| {
+                    if decomposition.permutation[i] == column {
+                        T::one()
+                    } else {
+                        T::zero()
+                    }
+                })
+                .collect();
+
+            for i in 0..n {
+                for k in 0..i {
+                    let solved = x[k];
+                    x[i] -= lu.get(i, k) * solved;
+                }
+            }
+
+            for i in (0..n).rev() {
+                for k in i + 1..n {
+                    let solved = x[k];
+                    x[i] -= lu.get(i, k) * solved;
+                }
+                x[i] /= lu.get(i, i);
+            }
+
+            for (i, value) in x.into_iter().enumerate() {
+                result.set(i, column, value);
+            }
+        }
+
+        Ok(result)
+    }
+
+    pub fn approx_eq(&self, other: &Self, tolerance: f64) -> bool {
+        self.rows == other.rows
+            && self.cols == other.cols
+            && self
+                .data
+                .iter()
+                .zip(other.data.iter())
+                .all(|(a, b)| (*a - *b).abs().to_f64() <= tolerance)
+    }
+
+    pub fn is_hermitian(&self, tolerance: f64) -> bool {
+        self.is_square() && self.approx_eq(&self.adjoint(), tolerance)
+    }
+
+    pub fn is_unitary(&self, tolerance: f64) -> bool {
+        if !self.is_square() {
+            return false;
+        }
+
+        self.adjoint()
+            .dot_unchecked(self)
+            .approx_eq(&Matrix::identity(self.rows), tolerance)
+    }
+
+    pub fn require_square(&self) -> Result<(), MathError> {
+        if self.is_square() {
+            Ok(())
+        } else {
+            Err(MathError::NotSquare {
+                rows: self.rows,
+                cols: self.cols,
+            })
+        }
+    }
+
+    fn mismatch(&self, other: &Self) -> MathError {
+        MathError::DimensionMismatch {
+            left: (self.rows, self.cols),
+            right: (other.rows, other.cols),
+        }
+    }
+
+    /// Matrix exponential by scaling and squaring with a degree 13 Padé approximant
+    /// (Higham, 2005).
+    pub fn exp(&self) -> Result<Matrix<T>, MathError> {
+        const PADE_COEFFICIENTS: [f64; 14] = [
+            64764752532480000.0,
+            32382376266240000.0,
+            7771770303897600.0,
+            1187353796428800.0,
+            129060195264000.0,
+            10559470521600.0,
+            670442572800.0,
+            33522128640.0,
+            1323241920.0,
+            40840800.0,
+            960960.0,
+            16380.0,
+            182.0,
+            1.0,
+        ];
+        const THETA_13: f64 = 5.371920351148152;
+
+        self.require_square()?;
+
+        // An infinite norm would ask for an unbounded number of squarings.
+        if self.data.iter().any(|value| !value.is_finite()) {
+            return Err(MathError::NotFinite);
+        }
+        let norm = self.norm_one();
+        let squarings = if norm > THETA_13 {
+            (norm / THETA_13).log2().ceil() as i32
+        } else {
+            0
+        };
+
+        let a = self.scale(T::from_f64(0.5_f64.powi(squarings)));
+        let b = PADE_COEFFICIENTS.map(T::from_f64);
+        let identity = Matrix::identity(self.rows);
+
+        let a2 = a.dot(&a)?;
+        let a4 = a2.dot(&a2)?;
+        let a6 = a4.dot(&a2)?;
+
+        let u_high = a6.dot(&Matrix::linear_combination(&[
+            (b[13], &a6),
+            (b[11], &a4),
+            (b[9], &a2),
+        ]))?;
+        let u = a.dot(&Matrix::linear_combination(&[
+            (T::one(), &u_high),
+            (b[7], &a6),
+            (b[5], &a4),
+            (b[3], &a2),
+            (b[1], &identity),
+        ]))?;
+
+        let v_high = a6.dot(&Matrix::linear_combination(&[
+            (b[12], &a6),
+            (b[10], &a4),
+            (b[8], &a2),
+        ]))?;
+        let v = Matrix::linear_combination(&[
+            (T::one(), &v_high),
+            (b[6], &a6),
+            (b[4], &a4),
+            (b[2], &a2),
+            (b[0], &identity),
+        ]);
+
+        let numerator = v.add_to(&u)?;
+        let denominator = v.subtract(&u)?;
+        let mut result = denominator.inverse()?.dot(&numerator)?;
+
+        for _ in 0..squarings {
+            result = result.dot(&result)?;
+        }
+
+        Ok(result)
+    }
+
+    /// Maximum absolute column sum.
+    pub fn norm_one(&self) -> f64 {
+        (0..self.cols)
+            .map(|j| {
+                (0..self.rows)
+                    .map(|i| self.get(i, j).abs().to_f64())
+                    .sum::<f64>()
+            })
+            .fold(0.0, f64::max)
+    }
+
+    fn linear_combination(terms: &[(T, &Matrix<T>)]) -> Matrix<T> {
+        let (rows, cols) = (terms[0].1.rows, terms[0].1.cols);
+        let mut result = Matrix::zeros(rows, cols);
+
+        for (coefficient, matrix) in terms {
+            for (accumulator, value) in result.data.iter_mut().zip(matrix.data.iter()) {
+                *accumulator += *coefficient * *value;
             }
         }
+
         result
     }
+
+    /// LU decomposition with partial pivoting. A column without a non-zero pivot is left as is,
+    /// so singular matrices decompose too, with a zero on the diagonal of `U`.
+    fn lu_decompose(&self) -> LUDecomposition<T> {
+        let n = self.rows;
+        let mut lu = self.clone();
+        let mut permutation: Vec<usize> = (0..n).collect();
+        let mut parity_odd = false;
+
+        for k in 0..n {
+            let (pivot_row, pivot_size) = (k..n).map(|i| (i, lu.get(i, k).abs().to_f64())).fold(
+                (k, -1.0),
+                |best, candidate| {
+                    if candidate.1 > best.1 {
+                        candidate
+                    } else {
+                        best
+                    }
+                },
+            );
+
+            if pivot_size == 0.0 {
+                continue;
+            }
+
+            if pivot_row != k {
+                for j in 0..n {
+                    let value = lu.get(k, j);
+                    lu.set(k, j, lu.get(pivot_row, j));
+                    lu.set(pivot_row, j, value);
+                }
+                permutation.swap(k, pivot_row);
+                parity_odd = !parity_odd;
+            }
+
+            let pivot = lu.get(k, k);
+            for i in k + 1..n {
+                let factor = lu.get(i, k) / pivot;
+                lu.set(i, k, factor);
+                for j in k + 1..n {
+                    let value = lu.get(i, j) - factor * lu.get(k, j);
+                    lu.set(i, j, value);
+                }
+            }
+        }
+
+        LUDecomposition {
+            lu,
+            permutation,
+            parity_odd,
+        }
+    }
+}
+
+impl Matrix<Complex<f64>> {
+    /// Time evolution operator `exp(-i H dt)` generated by the Hamiltonian `H` over a step `dt`
+    /// (in units where ħ = 1). The result is only unitary when `H` is Hermitian.
+    pub fn unitary_from_hamiltonian(
+        hamiltonian: &Matrix<Complex<f64>>,
+        dt: f64,
+    ) -> Result<Matrix<Complex<f64>>, MathError> {
+        hamiltonian.scale(Complex::new(0.0, -dt)).exp()
+    }
+}
+
+/// Packed LU factors of a row-permuted matrix: `P A = L U`, with the unit diagonal of `L`
+/// left implicit. Row `i` of `P A` is row `permutation[i]` of `A`.
+struct LUDecomposition<T: Float> {
+    lu: Matrix<T>,
+    permutation: Vec<usize>,
+    parity_odd: bool,
 }
 
 impl<T: Float> ops::Index<(usize, usize)> for Matrix<T> {
@@ -183,23 +622,23 @@ impl<T: Float> ops::IndexMut<(usize, usize)> for Matrix<T> {
 
 impl<T: Float> ops::AddAssign<&Matrix<T>> for Matrix<T> {
     fn add_assign(&mut self, other: &Matrix<T>) {
-        if let Some(result) = self.add_to(other) {
-            *self = result;
-        }
+        *self = self
+            .add_to(other)
+            .unwrap_or_else(|error| panic!("{}", error));
     }
 }
 
 impl<T: Float> ops::SubAssign<&Matrix<T>> for Matrix<T> {
     fn sub_assign(&mut self, other: &Matrix<T>) {
-        if let Some(result) = self.subtract(other) {
-            *self = result;
-        }
+        *self = self
+            .subtract(other)
+            .unwrap_or_else(|error| panic!("{}", error));
     }
 }
 
 impl_matrix_ops! {
-    Add, add, &Matrix<T>, Option<Matrix<T>>, add_to,
-    Sub, sub, &Matrix<T>, Option<Matrix<T>>, subtract,
+    Add, add, &Matrix<T>, Result<Matrix<T>, MathError>, add_to,
+    Sub, sub, &Matrix<T>, Result<Matrix<T>, MathError>, subtract,
     Mul, mul, T, Matrix<T>, scale,
     Div, div, T, Matrix<T>, scale,
 }
@@ -301,10 +740,281 @@ impl<T: Float + fmt::Display> fmt::Display for Matrix<T> {
             }
 
             if i != self.rows - 1 {
-                write!(f, "\n")?;
+                writeln!(f)?;
             }
         }
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::naive_dot;
+    use crate::{complex, Complex};
+
+    const TOLERANCE: f64 = 1e-12;
+
+    fn pauli_y() -> Matrix<Complex<f64>> {
+        matrix!([complex!(0.0, 0.0), complex!(0.0, -1.0)];
+                [complex!(0.0, 1.0), complex!(0.0,  0.0)])
+    }
+
+    #[test]
+    fn adjoint_conjugates_and_transposes() {
+        let a = matrix!([complex!(1.0, 2.0), complex!(3.0, -1.0)];
+                        [complex!(0.0, 4.0), complex!(5.0,  0.0)]);
+        let expected = matrix!([complex!(1.0, -2.0), complex!(0.0, -4.0)];
+                               [complex!(3.0,  1.0), complex!(5.0,  0.0)]);
+
+        assert!(a.adjoint().approx_eq(&expected, TOLERANCE));
+        assert!(a.conjugate().transpose().approx_eq(&expected, TOLERANCE));
+    }
+
+    #[test]
+    fn pauli_matrices_are_hermitian_and_unitary() {
+        let y = pauli_y();
+        assert!(y.is_hermitian(TOLERANCE));
+        assert!(y.is_unitary(TOLERANCE));
+        assert_eq!(y.trace(), Ok(complex!(0.0, 0.0)));
+        assert!(!(y.clone() * complex!(2.0, 0.0)).is_unitary(TOLERANCE));
+        assert!(!(y * complex!(0.0, 1.0)).is_hermitian(TOLERANCE));
+    }
+
+    #[test]
+    fn determinant_of_real_and_complex_matrices() {
+        let real = matrix!([2.0, -1.0, 0.0]; [-1.0, 2.0, -1.0]; [0.0, -1.0, 2.0]);
+        assert!((real.determinant().unwrap() - 4.0_f64).abs() < TOLERANCE);
+
+        let permuted = matrix!([0.0, 1.0]; [1.0, 0.0]);
+        assert!((permuted.determinant().unwrap() + 1.0_f64).abs() < TOLERANCE);
+
+        let determinant = pauli_y().determinant().unwrap();
+        assert!((determinant - complex!(-1.0, 0.0)).abs() < TOLERANCE);
+
+        assert_eq!(matrix!([1.0, 2.0]; [2.0, 4.0]).determinant(), Ok(0.0));
+        assert_eq!(
+            matrix!([1.0, 2.0, 3.0]).determinant(),
+            Err(MathError::NotSquare { rows: 1, cols: 3 })
+        );
+    }
+
+    #[test]
+    fn inverse_round_trips() {
+        let a = matrix!([complex!(1.0, 1.0), complex!(2.0, 0.0), complex!(0.0, -1.0)];
+                        [complex!(0.0, 0.0), complex!(3.0, 1.0), complex!(1.0,  0.0)];
+                        [complex!(4.0, 0.0), complex!(0.0, 2.0), complex!(1.0,  1.0)]);
+        let inverse = a.inverse().unwrap();
+
+        assert!(a
+            .dot(&inverse)
+            .unwrap()
+            .approx_eq(&Matrix::identity(3), 1e-10));
+        assert!(inverse
+            .dot(&a)
+            .unwrap()
+            .approx_eq(&Matrix::identity(3), 1e-10));
+        assert_eq!(
+            matrix!([1.0, 2.0]; [2.0, 4.0]).inverse().unwrap_err(),
+            MathError::Singular
+        );
+    }
+
+    #[test]
+    fn badly_scaled_matrices_are_not_singular() {
+        let scaled = matrix!([1e10, 0.0, 0.0]; [0.0, 1e-10, 0.0]; [0.0, 0.0, 3.0]);
+        assert!((scaled.determinant().unwrap() - 3.0_f64).abs() < TOLERANCE);
+
+        let inverse = scaled.inverse().unwrap();
+        assert!((inverse.get(0, 0) - 1e-10_f64).abs() < 1e-20);
+        assert!((inverse.get(1, 1) - 1e10_f64).abs() < 1e-6);
+        assert!(scaled
+            .dot(&inverse)
+            .unwrap()
+            .approx_eq(&Matrix::identity(3), 1e-12));
+
+        let singular = matrix!([1e10, 0.0]; [0.0, 0.0]);
+        assert_eq!(singular.determinant(), Ok(0.0));
+        assert_eq!(singular.inverse().unwrap_err(), MathError::Singular);
+    }
+
+    fn pauli_x() -> Matrix<Complex<f64>> {
+        matrix!([complex!(0.0, 0.0), complex!(1.0, 0.0)];
+                [complex!(1.0, 0.0), complex!(0.0, 0.0)])
+    }
+
+    fn pauli_z() -> Matrix<Complex<f64>> {
+        matrix!([complex!(1.0, 0.0), complex!( 0.0, 0.0)];
+                [complex!(0.0, 0.0), complex!(-1.0, 0.0)])
+    }
+
+    // exp(-i θ/2 σ) = cos(θ/2) I - i sin(θ/2) σ for any Pauli matrix σ.
+    fn pauli_rotation(pauli: &Matrix<Complex<f64>>, theta: f64) -> Matrix<Complex<f64>> {
+        let identity = Matrix::identity(2).scale(complex!((theta / 2.0).cos(), 0.0));
+        let generator = pauli.scale(complex!(0.0, -(theta / 2.0).sin()));
+        identity.add_to(&generator).unwrap()
+    }
+
+    #[test]
+    fn exponential_of_zero_and_diagonal_matrices() {
+        let zero: Matrix<f64> = Matrix::zeros(3, 3);
+        assert!(zero
+            .exp()
+            .unwrap()
+            .approx_eq(&Matrix::identity(3), TOLERANCE));
+
+        let diagonal = matrix!([1.0, 0.0]; [0.0, -2.0]);
+        let expected = matrix!([1.0_f64.exp(), 0.0]; [0.0, (-2.0_f64).exp()]);
+        assert!(diagonal.exp().unwrap().approx_eq(&expected, 1e-12));
+
+        assert!(matrix!([1.0, 2.0]).exp().is_err());
+        assert_eq!(
+            matrix!([f64::INFINITY, 0.0]; [0.0, 1.0]).exp().err(),
+            Some(MathError::NotFinite)
+        );
+        assert_eq!(
+            matrix!([f64::NAN, 0.0]; [0.0, 1.0]).exp().err(),
+            Some(MathError::NotFinite)
+        );
+    }
+
+    #[test]
+    fn exponential_of_nilpotent_matrix() {
+        let nilpotent = matrix!([0.0, 1.0]; [0.0, 0.0]);
+        let expected = matrix!([1.0, 1.0]; [0.0, 1.0]);
+        assert!(nilpotent.exp().unwrap().approx_eq(&expected, TOLERANCE));
+    }
+
+    #[test]
+    fn pauli_rotations_match_closed_form() {
+        for pauli in [pauli_x(), pauli_y(), pauli_z()] {
+            for theta in [0.1, 1.0, core::f64::consts::PI, 7.5, 40.0] {
+                let hamiltonian = pauli.scale(complex!(theta / 2.0, 0.0));
+                let unitary = Matrix::unitary_from_hamiltonian(&hamiltonian, 1.0).unwrap();
+
+                assert!(unitary.approx_eq(&pauli_rotation(&pauli, theta), 1e-12));
+                assert!(unitary.is_unitary(1e-12));
+            }
+        }
+    }
+
+    #[test]
+    fn hamiltonian_evolution_composes_over_time_steps() {
+        let hamiltonian = pauli_x()
+            .add_to(&pauli_z().scale(complex!(0.5, 0.0)))
+            .unwrap();
+        let step = Matrix::unitary_from_hamiltonian(&hamiltonian, 0.25).unwrap();
+        let full = Matrix::unitary_from_hamiltonian(&hamiltonian, 1.0).unwrap();
+
+        let mut evolved = Matrix::identity(2);
+        for _ in 0..4 {
+            evolved = step.dot(&evolved).unwrap();
+        }
+        assert!(evolved.approx_eq(&full, 1e-12));
+    }
+
+    #[test]
+    fn dimension_mismatches_are_reported() {
+        let a = matrix!([1.0, 2.0]; [3.0, 4.0]);
+        let b = matrix!([1.0, 2.0, 3.0]);
+        let mismatch = MathError::DimensionMismatch {
+            left: (2, 2),
+            right: (1, 3),
+        };
+
+        assert_eq!(a.dot(&b).unwrap_err(), mismatch);
+        assert_eq!(a.add_to(&b).unwrap_err(), mismatch);
+        assert_eq!((a.clone() - &b).unwrap_err(), mismatch);
+        assert!(b.dot(&a.transpose()).is_err());
+        assert!(a
+            .dot(&a)
+            .unwrap()
+            .approx_eq(&a.dot_unchecked(&a), TOLERANCE));
+    }
+
+    #[test]
+    #[should_panic(expected = "dimension mismatch between 2x2 and 1x3 operands")]
+    fn add_assign_panics_on_mismatch() {
+        let mut a = matrix!([1.0, 2.0]; [3.0, 4.0]);
+        a += &matrix!([1.0, 2.0, 3.0]);
+    }
+
+    fn patterned(rows: usize, cols: usize, seed: f64) -> Matrix<Complex<f64>> {
+        Matrix::new(
+            rows,
+            cols,
+            (0..rows * cols)
+                .map(|i| complex!((i as f64 * seed).sin(), (i as f64 * seed).cos()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn blocked_product_matches_naive_product() {
+        let a = patterned(70, 130, 0.37);
+        let b = patterned(130, 65, 0.11);
+        let expected = naive_dot(&a, &b);
+
+        assert!(a.dot(&b).unwrap().approx_eq(&expected, 1e-10));
+
+        let mut output = patterned(70, 65, 1.0);
+        a.dot_into(&b, &mut output).unwrap();
+        assert!(output.approx_eq(&expected, 1e-10));
+
+        let mut wrong_shape = Matrix::zeros(65, 70);
+        assert_eq!(
+            a.dot_into(&b, &mut wrong_shape).unwrap_err(),
+            MathError::DimensionMismatch {
+                left: (70, 65),
+                right: (65, 70)
+            }
+        );
+    }
+
+    #[test]
+    fn kronecker_into_matches_kronecker() {
+        let a = patterned(2, 3, 0.5);
+        let b = patterned(3, 2, 0.25);
+        let expected = a.kronecker(&b);
+
+        assert_eq!((expected.rows, expected.cols), (6, 6));
+        assert_eq!(expected.get(4, 3), a.get(1, 1) * b.get(1, 1));
+
+        let mut output = Matrix::zeros(6, 6);
+        a.kronecker_into(&b, &mut output).unwrap();
+        assert!(output.approx_eq(&expected, 0.0));
+        assert!(a.kronecker_into(&b, &mut Matrix::zeros(6, 5)).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trips() {
+        use crate::{ColumnVector, Vector};
+
+        let matrix = pauli_y().scale(Complex::new(0.25, -1.5));
+        let json = serde_json::to_string(&matrix).unwrap();
+        let decoded: Matrix<Complex<f64>> = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.data, matrix.data);
+        let bytes = bincode::serialize(&matrix).unwrap();
+        let decoded: Matrix<Complex<f64>> = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(
+            (decoded.rows, decoded.cols, decoded.data),
+            (2, 2, matrix.data)
+        );
+
+        let state: ColumnVector<Complex<f64>> =
+            ColumnVector::new(vec![Complex::new(0.6, 0.0), Complex::new(0.0, -0.8)]);
+        let bytes = bincode::serialize(&state).unwrap();
+        let decoded: ColumnVector<Complex<f64>> = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(decoded.get(1), state.get(1));
+        assert_eq!(
+            serde_json::to_string(&state).unwrap(),
+            r#"[{"real":0.6,"imaginary":0.0},{"real":0.0,"imaginary":-0.8}]"#
+        );
+
+        let malformed = r#"{"data":[1.0,2.0,3.0],"rows":2,"cols":2}"#;
+        let error = serde_json::from_str::<Matrix<f64>>(malformed).unwrap_err();
+        assert!(error.to_string().contains("dimension mismatch"));
+    }
+}
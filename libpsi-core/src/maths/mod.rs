@@ -1,10 +1,26 @@
 pub mod complex;
+pub mod decomposition;
+pub mod double_double;
+pub mod eigen;
+pub mod error;
+mod kernels;
 pub mod matrix;
 pub mod numeric;
+mod parallel;
+pub mod pauli;
+pub mod smatrix;
+pub mod sparse;
 pub mod vector;
 pub mod vector_ops;
 
 pub use complex::*;
+pub use decomposition::*;
+pub use double_double::*;
+pub use eigen::*;
+pub use error::*;
 pub use matrix::*;
 pub use numeric::*;
+pub use pauli::*;
+pub use smatrix::*;
+pub use sparse::*;
 pub use vector::*;
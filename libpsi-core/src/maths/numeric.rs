@@ -1,4 +1,5 @@
-use crate::Complex;
+use super::kernels;
+use crate::{Complex, DoubleDouble};
 use core::ops;
 
 macro_rules! impl_numeric {
@@ -20,51 +21,242 @@ macro_rules! impl_numeric {
 macro_rules! impl_cnumeric {
     ($($t:ty),*) => {
         $(impl Numeric for Complex<$t> {
-            fn zero() -> Self { Complex::new(0.0, 0.0) }
-            fn one() -> Self { Complex::new(1.0, 0.0) }
+            fn zero() -> Self { Complex::new(<$t>::zero(), <$t>::zero()) }
+            fn one() -> Self { Complex::new(<$t>::one(), <$t>::zero()) }
         })*
     };
 }
 
+macro_rules! forward_libm {
+    ($t:ty; $($method:ident => $libm_fn:ident),*) => {
+        $(
+            fn $method(self) -> Self {
+                libm::Libm::<$t>::$libm_fn(self)
+            }
+        )*
+    };
+}
+
 macro_rules! impl_float {
-    ($($t:ty, $sqrt_fn:path, $atan2_fn:path),*) => {
+    ($($t:ty),*) => {
         $(
             impl Float for $t {
-                fn sqrt(self) -> Self {
-                    $sqrt_fn(self)
-                }
+                forward_libm!($t;
+                    sqrt => sqrt,
+                    exp => exp,
+                    exp2 => exp2,
+                    ln => log,
+                    log2 => log2,
+                    log10 => log10,
+                    cos => cos,
+                    sin => sin,
+                    tan => tan,
+                    acos => acos,
+                    asin => asin,
+                    atan => atan,
+                    cosh => cosh,
+                    sinh => sinh,
+                    tanh => tanh,
+                    acosh => acosh,
+                    asinh => asinh,
+                    atanh => atanh,
+                    abs => fabs
+                );
 
                 fn atan2(y: Self, x: Self) -> Self {
-                    $atan2_fn(y, x)
+                    libm::Libm::<$t>::atan2(y, x)
+                }
+
+                fn hypot(self, other: Self) -> Self {
+                    libm::Libm::<$t>::hypot(self, other)
+                }
+
+                fn powi(self, exponent: i32) -> Self {
+                    libm::Libm::<$t>::pow(self, exponent as $t)
+                }
+
+                fn powf(self, exponent: Self) -> Self {
+                    libm::Libm::<$t>::pow(self, exponent)
+                }
+
+                fn epsilon() -> Self {
+                    <$t>::EPSILON
+                }
+
+                fn pi() -> Self {
+                    core::f64::consts::PI as $t
+                }
+
+                fn is_nan(self) -> bool {
+                    <$t>::is_nan(self)
+                }
+
+                fn is_finite(self) -> bool {
+                    <$t>::is_finite(self)
+                }
+
+                fn from_f64(value: f64) -> Self {
+                    value as $t
+                }
+
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+
+                fn conj(self) -> Self {
+                    self
                 }
             }
         )*
     };
 }
 
+// The complex functions below are the principal branches of their real counterparts, so they
+// agree with the real implementations whenever the argument lies on the real axis.
 macro_rules! impl_cfloat {
-    ($($t:ty, $sqrt_fn:path, $atan2_fn:path, $cos_fn:path, $sin_fn:path),*) => {
+    ($($t:ty $(=> $mul_add_fn:path)?),*) => {
         $(
             impl Float for Complex<$t> {
+                $(
+                    fn mul_add(alpha: Self, x: &[Self], y: &mut [Self]) {
+                        $mul_add_fn(alpha, x, y)
+                    }
+                )?
+
                 fn sqrt(self) -> Self {
-                    let r = self.abs();
-                    let theta = self.phase();
+                    let (r, theta) = self.to_polar();
+                    let two = <$t>::one() + <$t>::one();
+                    Complex::from_polar(<$t as Float>::sqrt(r), theta / two)
+                }
 
-                    let sqrt_r = $sqrt_fn(r);
-                    let sqrt_theta = theta / 2.0;
+                fn atan2(y: Self, x: Self) -> Self {
+                    let i = Complex::new(<$t>::zero(), <$t>::one());
+                    let radius = Float::sqrt(x * x + y * y);
+                    if radius == Self::zero() {
+                        return Self::zero();
+                    }
+                    -i * Complex::ln((x + i * y) / radius)
+                }
 
-                    Complex::new(
-                        sqrt_r * $cos_fn(sqrt_theta),
-                        sqrt_r * $sin_fn(sqrt_theta),
-                    )
+                fn exp(self) -> Self {
+                    Complex::exp(self)
                 }
 
-                fn atan2(y: Self, x: Self) -> Self {
-                    Complex::new(
-                        $atan2_fn(y.real, x.real),
-                        $atan2_fn(y.imaginary, x.imaginary),
+                fn exp2(self) -> Self {
+                    Complex::exp(self * <$t as Float>::ln(<$t>::from_f64(2.0)))
+                }
+
+                fn ln(self) -> Self {
+                    Complex::ln(self)
+                }
+
+                fn log2(self) -> Self {
+                    Complex::ln(self) / <$t as Float>::ln(<$t>::from_f64(2.0))
+                }
+
+                fn log10(self) -> Self {
+                    Complex::ln(self) / <$t as Float>::ln(<$t>::from_f64(10.0))
+                }
+
+                fn cos(self) -> Self {
+                    let i = Complex::new(<$t>::zero(), <$t>::one());
+                    ((self * i).exp() + (-self * i).exp()) / <$t>::from_f64(2.0)
+                }
+
+                fn sin(self) -> Self {
+                    let i = Complex::new(<$t>::zero(), <$t>::one());
+                    ((self * i).exp() - (-self * i).exp()) / (i * <$t>::from_f64(2.0))
+                }
+
+                fn tan(self) -> Self {
+                    Float::sin(self) / Float::cos(self)
+                }
+
+                fn acos(self) -> Self {
+                    let i = Complex::new(<$t>::zero(), <$t>::one());
+                    -i * Complex::ln(self + i * Float::sqrt(Self::one() - self * self))
+                }
+
+                fn asin(self) -> Self {
+                    let i = Complex::new(<$t>::zero(), <$t>::one());
+                    -i * Complex::ln(i * self + Float::sqrt(Self::one() - self * self))
+                }
+
+                fn atan(self) -> Self {
+                    let i = Complex::new(<$t>::zero(), <$t>::one());
+                    i / <$t>::from_f64(2.0) * Complex::ln((i + self) / (i - self))
+                }
+
+                fn cosh(self) -> Self {
+                    (self.exp() + (-self).exp()) / <$t>::from_f64(2.0)
+                }
+
+                fn sinh(self) -> Self {
+                    (self.exp() - (-self).exp()) / <$t>::from_f64(2.0)
+                }
+
+                fn tanh(self) -> Self {
+                    Float::sinh(self) / Float::cosh(self)
+                }
+
+                fn acosh(self) -> Self {
+                    Complex::ln(
+                        self + Float::sqrt(self + Self::one()) * Float::sqrt(self - Self::one()),
                     )
                 }
+
+                fn asinh(self) -> Self {
+                    Complex::ln(self + Float::sqrt(self * self + Self::one()))
+                }
+
+                fn atanh(self) -> Self {
+                    Complex::ln((Self::one() + self) / (Self::one() - self)) / <$t>::from_f64(2.0)
+                }
+
+                fn abs(self) -> Self {
+                    Complex::from(Complex::abs(self))
+                }
+
+                fn hypot(self, other: Self) -> Self {
+                    Float::sqrt(self * self + other * other)
+                }
+
+                fn powi(self, exponent: i32) -> Self {
+                    Complex::powi(self, exponent)
+                }
+
+                fn powf(self, exponent: Self) -> Self {
+                    Complex::powc(self, exponent)
+                }
+
+                fn epsilon() -> Self {
+                    Complex::from(<$t>::epsilon())
+                }
+
+                fn pi() -> Self {
+                    Complex::from(<$t>::pi())
+                }
+
+                fn is_nan(self) -> bool {
+                    Float::is_nan(self.real) || Float::is_nan(self.imaginary)
+                }
+
+                fn is_finite(self) -> bool {
+                    Float::is_finite(self.real) && Float::is_finite(self.imaginary)
+                }
+
+                fn from_f64(value: f64) -> Self {
+                    Complex::from(<$t>::from_f64(value))
+                }
+
+                /// Only the real part survives the conversion.
+                fn to_f64(self) -> f64 {
+                    self.real.to_f64()
+                }
+
+                fn conj(self) -> Self {
+                    self.get_conjugate()
+                }
             }
         )*
     };
@@ -72,6 +264,8 @@ macro_rules! impl_cfloat {
 
 pub trait Numeric:
     Copy
+    + Send
+    + Sync
     + PartialOrd
     + ops::Add<Output = Self>
     + ops::Mul<Output = Self>
@@ -88,14 +282,162 @@ pub trait Numeric:
 }
 
 impl_numeric!(i32, i64, f32, f64);
-impl_cnumeric!(f32, f64);
-impl_float!(f32, libm::sqrtf, libm::atan2f);
-impl_float!(f64, libm::sqrt, libm::atan2);
-impl_cfloat!(f32, libm::sqrtf, libm::atan2f, libm::cosf, libm::sinf);
-impl_cfloat!(f64, libm::sqrt, libm::atan2, libm::cos, libm::sin);
+impl_cnumeric!(f32, f64, DoubleDouble);
+impl_float!(f32, f64);
+impl_cfloat!(f32, f64 => kernels::complex_f64_mul_add, DoubleDouble);
 
 pub trait Integer: Numeric {}
 pub trait Float: Numeric {
     fn sqrt(self) -> Self;
     fn atan2(y: Self, x: Self) -> Self;
+    fn hypot(self, other: Self) -> Self;
+
+    fn exp(self) -> Self;
+    fn exp2(self) -> Self;
+    fn ln(self) -> Self;
+    fn log2(self) -> Self;
+    fn log10(self) -> Self;
+
+    fn cos(self) -> Self;
+    fn sin(self) -> Self;
+    fn tan(self) -> Self;
+    fn acos(self) -> Self;
+    fn asin(self) -> Self;
+    fn atan(self) -> Self;
+
+    fn cosh(self) -> Self;
+    fn sinh(self) -> Self;
+    fn tanh(self) -> Self;
+    fn acosh(self) -> Self;
+    fn asinh(self) -> Self;
+    fn atanh(self) -> Self;
+
+    fn abs(self) -> Self;
+    fn powi(self, exponent: i32) -> Self;
+    fn powf(self, exponent: Self) -> Self;
+
+    fn epsilon() -> Self;
+    fn pi() -> Self;
+    fn is_nan(self) -> bool;
+    fn is_finite(self) -> bool;
+
+    fn from_f64(value: f64) -> Self;
+    fn to_f64(self) -> f64;
+
+    /// Complex conjugate, which is the identity on real scalars.
+    fn conj(self) -> Self;
+
+    /// `y += alpha * x`, the inner kernel of the dense matrix products. Scalar types may
+    /// override it with a vectorised implementation.
+    fn mul_add(alpha: Self, x: &[Self], y: &mut [Self]) {
+        for (y, x) in y.iter_mut().zip(x) {
+            *y += alpha * *x;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::complex;
+    use proptest::prelude::*;
+
+    const TOLERANCE: f64 = 1e-9;
+
+    fn close(a: Complex<f64>, b: Complex<f64>) -> bool {
+        let scale = 1.0_f64.max(Complex::abs(a)).max(Complex::abs(b));
+        Complex::abs(a - b) <= TOLERANCE * scale
+    }
+
+    fn on_real_axis(
+        real: fn(f64) -> f64,
+        complex: fn(Complex<f64>) -> Complex<f64>,
+        x: f64,
+    ) -> bool {
+        close(complex(Complex::from(x)), Complex::from(real(x)))
+    }
+
+    #[test]
+    fn constants() {
+        assert_eq!(<f64 as Float>::pi(), core::f64::consts::PI);
+        assert_eq!(<f32 as Float>::epsilon(), f32::EPSILON);
+        assert_eq!(
+            <Complex<f64> as Float>::pi(),
+            complex!(core::f64::consts::PI, 0.0)
+        );
+        assert!(<Complex<f64> as Float>::is_nan(complex!(0.0, f64::NAN)));
+        assert!(!<Complex<f64> as Float>::is_finite(complex!(
+            f64::INFINITY,
+            0.0
+        )));
+        assert_eq!(<f32 as Float>::from_f64(0.5).to_f64(), 0.5);
+    }
+
+    #[test]
+    fn real_functions_match_std() {
+        let x = 0.3_f64;
+        assert!((Float::tanh(x) - x.tanh()).abs() < TOLERANCE);
+        assert!((Float::hypot(3.0_f64, 4.0) - 5.0).abs() < TOLERANCE);
+        assert!((Float::powi(2.0_f64, -2) - 0.25).abs() < TOLERANCE);
+        assert!((Float::log2(8.0_f32) - 3.0).abs() < 1e-6);
+    }
+
+    proptest! {
+        #[test]
+        fn complex_functions_extend_real_ones(x in -0.99..0.99_f64) {
+            prop_assert!(on_real_axis(Float::sin, Float::sin, x));
+            prop_assert!(on_real_axis(Float::cos, Float::cos, x));
+            prop_assert!(on_real_axis(Float::tan, Float::tan, x));
+            prop_assert!(on_real_axis(Float::asin, Float::asin, x));
+            prop_assert!(on_real_axis(Float::acos, Float::acos, x));
+            prop_assert!(on_real_axis(Float::atan, Float::atan, x));
+            prop_assert!(on_real_axis(Float::sinh, Float::sinh, x));
+            prop_assert!(on_real_axis(Float::cosh, Float::cosh, x));
+            prop_assert!(on_real_axis(Float::tanh, Float::tanh, x));
+            prop_assert!(on_real_axis(Float::asinh, Float::asinh, x));
+            prop_assert!(on_real_axis(Float::atanh, Float::atanh, x));
+            prop_assert!(on_real_axis(Float::exp2, Float::exp2, x));
+            prop_assert!(on_real_axis(Float::abs, Float::abs, x));
+            prop_assert!(on_real_axis(Float::acosh, Float::acosh, 1.0 + x.abs()));
+            prop_assert!(on_real_axis(Float::ln, Float::ln, x.abs() + 1e-3));
+            prop_assert!(on_real_axis(Float::log10, Float::log10, x.abs() + 1e-3));
+            prop_assert!(on_real_axis(Float::sqrt, Float::sqrt, x.abs()));
+        }
+
+        #[test]
+        fn complex_atan2_extends_real_atan2(y in -10.0..10.0_f64, x in -10.0..10.0_f64) {
+            prop_assume!(x.abs() > 1e-6 || y.abs() > 1e-6);
+            let expected = Complex::from(Float::atan2(y, x));
+            prop_assert!(close(Float::atan2(Complex::from(y), Complex::from(x)), expected));
+        }
+
+        #[test]
+        fn pythagorean_identities(re in -2.0..2.0_f64, im in -2.0..2.0_f64) {
+            let z = complex!(re, im);
+            let one = Complex::from(1.0);
+            let (sin, cos) = (Float::sin(z), Float::cos(z));
+            let (sinh, cosh) = (Float::sinh(z), Float::cosh(z));
+            prop_assert!(close(sin * sin + cos * cos, one));
+            prop_assert!(close(cosh * cosh - sinh * sinh, one));
+        }
+
+        #[test]
+        fn inverse_functions_round_trip(re in -0.9..0.9_f64, im in -0.9..0.9_f64) {
+            let z = complex!(re, im);
+            prop_assert!(close(Float::sin(Float::asin(z)), z));
+            prop_assert!(close(Float::cos(Float::acos(z)), z));
+            prop_assert!(close(Float::tan(Float::atan(z)), z));
+            prop_assert!(close(Float::sinh(Float::asinh(z)), z));
+            prop_assert!(close(Float::tanh(Float::atanh(z)), z));
+            prop_assert!(close(Float::exp(Float::ln(z)), z));
+        }
+
+        #[test]
+        fn complex_sqrt_squares_back(re in -100.0..100.0_f64, im in -100.0..100.0_f64) {
+            let z = complex!(re, im);
+            let root = Float::sqrt(z);
+            prop_assert!(close(root * root, z));
+            prop_assert!(root.real >= 0.0);
+        }
+    }
 }
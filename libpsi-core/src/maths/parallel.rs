@@ -0,0 +1,139 @@
+//! Work splitting shared by the sequential and `parallel` builds.
+//!
+//! Every kernel is written against these helpers so that both builds partition the work the same
+//! way: each output element is produced by exactly one closure call, and reductions always combine
+//! fixed-size partial sums in index order. Results are therefore bit-for-bit identical whether the
+//! `parallel` feature is enabled or not, and independent of the size of the thread pool.
+
+use super::Float;
+use core::ops::Range;
+
+/// Amount of scalar work (roughly multiply-adds) below which kernels stay on the calling thread.
+#[cfg_attr(not(feature = "parallel"), allow(dead_code))]
+const THRESHOLD: usize = 1 << 15;
+
+/// Length of the ranges reduced independently by [`sum`], and the default chunk length for
+/// element-wise kernels.
+pub(crate) const CHUNK: usize = 4096;
+
+/// Calls `f(index, chunk)` for every consecutive `chunk_len` sized chunk of `data`, on the thread
+/// pool when the `parallel` feature is enabled and `work` reaches [`THRESHOLD`].
+pub(crate) fn for_each_chunk<T, F>(data: &mut [T], chunk_len: usize, work: usize, f: F)
+where
+    T: Send,
+    F: Fn(usize, &mut [T]) + Send + Sync,
+{
+    if chunk_len == 0 {
+        return;
+    }
+
+    #[cfg(feature = "parallel")]
+    if work >= THRESHOLD {
+        use rayon::prelude::*;
+
+        data.par_chunks_mut(chunk_len)
+            .enumerate()
+            .for_each(|(index, chunk)| f(index, chunk));
+        return;
+    }
+
+    let _ = work;
+    data.chunks_mut(chunk_len)
+        .enumerate()
+        .for_each(|(index, chunk)| f(index, chunk));
+}
+
+/// Sums `f(range)` over consecutive [`CHUNK`] sized ranges covering `0..length`. The partial sums
+/// are always added left to right, whichever threads computed them.
+pub(crate) fn sum<T, F>(length: usize, f: F) -> T
+where
+    T: Float,
+    F: Fn(Range<usize>) -> T + Send + Sync,
+{
+    let partial = |chunk: usize| f(chunk * CHUNK..((chunk + 1) * CHUNK).min(length));
+    let chunks = length.div_ceil(CHUNK);
+
+    #[cfg(feature = "parallel")]
+    if length >= THRESHOLD {
+        use rayon::prelude::*;
+
+        let partials: Vec<T> = (0..chunks).into_par_iter().map(partial).collect();
+        return partials.into_iter().fold(T::zero(), |acc, x| acc + x);
+    }
+
+    (0..chunks).map(partial).fold(T::zero(), |acc, x| acc + x)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::{naive_dot, random_matrix};
+    use crate::{ColumnVector, Complex, Matrix, Vector, VectorMatrix};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn large_kernels_match_reference_implementations() {
+        let mut rng = StdRng::seed_from_u64(10);
+        let a = random_matrix(&mut rng, 70, 90);
+        let b = random_matrix(&mut rng, 90, 110);
+        let v = Matrix::new(90, 1, b.data[..90].to_vec());
+
+        assert!(a.dot(&b).unwrap().approx_eq(&naive_dot(&a, &b), 1e-12));
+
+        let applied = ColumnVector::from_matrix(&v).mul_matrix(&a).unwrap();
+        assert!(applied.to_matrix().approx_eq(&naive_dot(&a, &v), 1e-12));
+
+        let small = random_matrix(&mut rng, 6, 7);
+        let scalar = Complex::new(0.0, 2.0);
+        let scaled = small.kronecker(&b).scale(scalar);
+        assert_eq!((scaled.rows, scaled.cols), (6 * 90, 7 * 110));
+        assert_eq!(
+            scaled.get(5 * 90 + 4, 6 * 110 + 7),
+            small.get(5, 6) * b.get(4, 7) * scalar
+        );
+    }
+
+    #[test]
+    fn long_sums_are_chunked_in_order() {
+        let length = 3 * super::CHUNK + 17;
+        let v: ColumnVector<f64> = ColumnVector::new((0..length).map(|x| x as f64).collect());
+        let expected = (0..length).map(|x| (x * x) as f64).sum::<f64>();
+
+        assert_eq!(v.dot(&v), Ok(expected));
+        assert_eq!(v.norm(), expected.sqrt());
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn results_do_not_depend_on_thread_count() {
+        let mut rng = StdRng::seed_from_u64(11);
+        let a = random_matrix(&mut rng, 200, 150);
+        let b = random_matrix(&mut rng, 150, 180);
+        let small = random_matrix(&mut rng, 5, 4);
+        let v = ColumnVector::new(a.data.clone());
+
+        let run = |threads: usize| {
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap()
+                .install(|| {
+                    let product = a.dot(&b).unwrap();
+                    let kronecker = small.kronecker(&b).scale(Complex::new(0.5, -1.0));
+                    let applied = ColumnVector::new(b.data[..150].to_vec())
+                        .mul_matrix(&a)
+                        .unwrap();
+                    (product, kronecker, applied, v.dot(&v), v.norm())
+                })
+        };
+
+        let (product, kronecker, applied, dot, norm) = run(1);
+        for threads in [2, 3, 8] {
+            let other = run(threads);
+            assert_eq!(product.data, other.0.data);
+            assert_eq!(kronecker.data, other.1.data);
+            assert_eq!(applied.to_matrix().data, other.2.to_matrix().data);
+            assert_eq!(dot, other.3);
+            assert_eq!(norm, other.4);
+        }
+    }
+}
@@ -0,0 +1,210 @@
+use super::{Complex, CooMatrix, Float, SparseMatrix};
+use core::{fmt, str};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Pauli {
+    I,
+    X,
+    Y,
+    Z,
+}
+
+/// Tensor product of single-qubit Pauli operators with a complex coefficient, e.g.
+/// `0.5 * X ⊗ Z ⊗ I`. Qubit 0 is the leftmost factor, matching [`crate::Matrix::kronecker`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct PauliString {
+    pub operators: Vec<Pauli>,
+    pub coefficient: Complex<f64>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParsePauliError {
+    pub position: usize,
+    pub character: char,
+}
+
+impl PauliString {
+    pub fn new(operators: Vec<Pauli>) -> PauliString {
+        PauliString {
+            operators,
+            coefficient: Complex::new(1.0, 0.0),
+        }
+    }
+
+    /// Identity on `qubits` qubits, to be filled in with [`PauliString::with`].
+    pub fn identity(qubits: usize) -> PauliString {
+        PauliString::new(vec![Pauli::I; qubits])
+    }
+
+    pub fn with(mut self, qubit: usize, operator: Pauli) -> PauliString {
+        self.operators[qubit] = operator;
+        self
+    }
+
+    pub fn with_coefficient(mut self, coefficient: Complex<f64>) -> PauliString {
+        self.coefficient = coefficient;
+        self
+    }
+
+    pub fn qubits(&self) -> usize {
+        self.operators.len()
+    }
+
+    /// Builds the `2^n x 2^n` operator directly: a Pauli string has exactly one non-zero entry
+    /// per row, found by flipping the bits of the row index acted on by X or Y.
+    pub fn to_sparse<T: Float>(&self) -> SparseMatrix<Complex<T>>
+    where
+        Complex<T>: Float,
+    {
+        let n = self.qubits();
+        let dimension = 1usize << n;
+        let bit = |qubit: usize| 1usize << (n - 1 - qubit);
+
+        let flip_mask = self
+            .operators
+            .iter()
+            .enumerate()
+            .filter(|(_, operator)| matches!(operator, Pauli::X | Pauli::Y))
+            .fold(0, |mask, (qubit, _)| mask | bit(qubit));
+
+        let mut coo = CooMatrix::new(dimension, dimension);
+        for row in 0..dimension {
+            let mut value = self.coefficient;
+            for (qubit, operator) in self.operators.iter().enumerate() {
+                let row_bit_set = row & bit(qubit) != 0;
+                value = match (operator, row_bit_set) {
+                    (Pauli::Y, false) => value * Complex::new(0.0, -1.0),
+                    (Pauli::Y, true) => value * Complex::new(0.0, 1.0),
+                    (Pauli::Z, true) => -value,
+                    _ => value,
+                };
+            }
+
+            coo.push(
+                row,
+                row ^ flip_mask,
+                Complex::new(T::from_f64(value.real), T::from_f64(value.imaginary)),
+            );
+        }
+
+        coo.to_csr()
+    }
+}
+
+impl str::FromStr for PauliString {
+    type Err = ParsePauliError;
+
+    fn from_str(string: &str) -> Result<PauliString, ParsePauliError> {
+        let operators = string
+            .chars()
+            .enumerate()
+            .map(|(position, character)| match character {
+                'I' => Ok(Pauli::I),
+                'X' => Ok(Pauli::X),
+                'Y' => Ok(Pauli::Y),
+                'Z' => Ok(Pauli::Z),
+                _ => Err(ParsePauliError {
+                    position,
+                    character,
+                }),
+            })
+            .collect::<Result<Vec<Pauli>, ParsePauliError>>()?;
+
+        Ok(PauliString::new(operators))
+    }
+}
+
+impl fmt::Display for Pauli {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Pauli::I => "I",
+            Pauli::X => "X",
+            Pauli::Y => "Y",
+            Pauli::Z => "Z",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl fmt::Display for PauliString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}) ", self.coefficient)?;
+        for operator in &self.operators {
+            write!(f, "{}", operator)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ParsePauliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid Pauli operator '{}' at position {}",
+            self.character, self.position
+        )
+    }
+}
+
+impl std::error::Error for ParsePauliError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{complex, matrix, Matrix};
+
+    fn dense(operator: Pauli) -> Matrix<Complex<f64>> {
+        let (zero, one, i) = (complex!(0.0, 0.0), complex!(1.0, 0.0), complex!(0.0, 1.0));
+        match operator {
+            Pauli::I => matrix!([one, zero]; [zero, one]),
+            Pauli::X => matrix!([zero, one]; [one, zero]),
+            Pauli::Y => matrix!([zero, -i]; [i, zero]),
+            Pauli::Z => matrix!([one, zero]; [zero, -one]),
+        }
+    }
+
+    #[test]
+    fn pauli_strings_match_dense_kronecker_products() {
+        for string in ["X", "Y", "Z", "XZ", "YIX", "ZZYI", "IYXZY"] {
+            let pauli: PauliString = string.parse().unwrap();
+            let pauli = pauli.with_coefficient(complex!(0.5, -0.25));
+
+            let mut expected = Matrix::identity(1);
+            for operator in &pauli.operators {
+                expected = expected.kronecker(&dense(*operator));
+            }
+            let expected = expected.scale(pauli.coefficient);
+
+            let sparse = pauli.to_sparse::<f64>();
+            assert_eq!(sparse.nnz(), 1 << pauli.qubits());
+            assert!(sparse.to_dense().approx_eq(&expected, 1e-15));
+        }
+    }
+
+    #[test]
+    fn builder_and_parser_agree() {
+        let built = PauliString::identity(4).with(1, Pauli::X).with(3, Pauli::Z);
+        assert_eq!(built, "IXIZ".parse().unwrap());
+        assert_eq!(
+            "XQ".parse::<PauliString>().unwrap_err(),
+            ParsePauliError {
+                position: 1,
+                character: 'Q'
+            }
+        );
+    }
+
+    #[test]
+    fn hamiltonian_from_pauli_sum_is_hermitian() {
+        let terms = ["XXI", "IYY", "ZIZ"];
+        let mut hamiltonian: SparseMatrix<Complex<f64>> = SparseMatrix::zeros(8, 8);
+        for term in terms {
+            let term: PauliString = term.parse().unwrap();
+            hamiltonian = hamiltonian.add_to(&term.to_sparse()).unwrap();
+        }
+
+        let dense = hamiltonian.to_dense();
+        assert!(dense.is_hermitian(1e-15));
+        assert!(hamiltonian.adjoint().to_dense().approx_eq(&dense, 0.0));
+    }
+}
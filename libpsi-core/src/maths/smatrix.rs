@@ -0,0 +1,366 @@
+use super::{ColumnVector, Float, MathError, Matrix, Vector};
+use core::{fmt, ops};
+
+/// Fixed-size, stack-allocated row-major matrix. Shapes are part of the type, so products and
+/// sums of incompatible matrices are rejected by the compiler instead of returning a
+/// [`MathError`]:
+///
+/// ```compile_fail
+/// use libpsi_core::SMatrix;
+///
+/// let a = SMatrix::<f64, 2, 3>::zeros();
+/// let b = SMatrix::<f64, 2, 3>::zeros();
+/// let _ = a * b;
+/// ```
+#[derive(Clone, Copy, PartialEq)]
+pub struct SMatrix<T: Float, const R: usize, const C: usize> {
+    pub data: [[T; C]; R],
+}
+
+/// Fixed-size column vector, the `N x 1` case of [`SMatrix`].
+pub type SVector<T, const N: usize> = SMatrix<T, N, 1>;
+
+/// Fixed-size row vector, the `1 x N` case of [`SMatrix`].
+pub type SRowVector<T, const N: usize> = SMatrix<T, 1, N>;
+
+impl<T: Float, const R: usize, const C: usize> SMatrix<T, R, C> {
+    pub const fn from_rows(data: [[T; C]; R]) -> Self {
+        SMatrix { data }
+    }
+
+    pub fn zeros() -> Self {
+        SMatrix {
+            data: [[T::zero(); C]; R],
+        }
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> T {
+        self.data[row][col]
+    }
+
+    pub fn set(&mut self, row: usize, col: usize, value: T) {
+        self.data[row][col] = value;
+    }
+
+    pub const fn shape(&self) -> (usize, usize) {
+        (R, C)
+    }
+
+    pub fn dot<const K: usize>(&self, other: &SMatrix<T, C, K>) -> SMatrix<T, R, K> {
+        let mut result = SMatrix::zeros();
+        for i in 0..R {
+            for k in 0..C {
+                let alpha = self.data[i][k];
+                T::mul_add(alpha, &other.data[k], &mut result.data[i]);
+            }
+        }
+        result
+    }
+
+    /// Kronecker product. Stable Rust cannot spell `R * R2` in a return type, so the output shape
+    /// is inferred from the call site and checked when the function is instantiated.
+    pub fn kronecker<const R2: usize, const C2: usize, const RO: usize, const CO: usize>(
+        &self,
+        other: &SMatrix<T, R2, C2>,
+    ) -> SMatrix<T, RO, CO> {
+        const {
+            assert!(
+                RO == R * R2 && CO == C * C2,
+                "kronecker output shape must be the product of the factor shapes"
+            )
+        };
+
+        let mut result = SMatrix::zeros();
+        for i in 0..R {
+            for j in 0..C {
+                for k in 0..R2 {
+                    for l in 0..C2 {
+                        result.data[i * R2 + k][j * C2 + l] = self.data[i][j] * other.data[k][l];
+                    }
+                }
+            }
+        }
+        result
+    }
+
+    pub fn transpose(&self) -> SMatrix<T, C, R> {
+        let mut result = SMatrix::zeros();
+        for i in 0..R {
+            for j in 0..C {
+                result.data[j][i] = self.data[i][j];
+            }
+        }
+        result
+    }
+
+    pub fn adjoint(&self) -> SMatrix<T, C, R> {
+        let mut result = SMatrix::zeros();
+        for i in 0..R {
+            for j in 0..C {
+                result.data[j][i] = self.data[i][j].conj();
+            }
+        }
+        result
+    }
+
+    pub fn scale(&self, scalar: T) -> Self {
+        let mut result = *self;
+        result.data.iter_mut().flatten().for_each(|x| *x *= scalar);
+        result
+    }
+
+    pub fn approx_eq(&self, other: &Self, tolerance: f64) -> bool {
+        self.data
+            .iter()
+            .flatten()
+            .zip(other.data.iter().flatten())
+            .all(|(a, b)| (*a - *b).abs().to_f64() <= tolerance)
+    }
+
+    pub fn to_matrix(&self) -> Matrix<T> {
+        Matrix::new(R, C, self.data.iter().flatten().copied().collect())
+    }
+
+    /// Copies a dynamic matrix of exactly `R x C`.
+    pub fn from_matrix(matrix: &Matrix<T>) -> Result<Self, MathError> {
+        if matrix.rows != R || matrix.cols != C {
+            return Err(MathError::DimensionMismatch {
+                left: (R, C),
+                right: (matrix.rows, matrix.cols),
+            });
+        }
+
+        let mut result = SMatrix::zeros();
+        for (row, values) in result.data.iter_mut().zip(matrix.data.chunks(C.max(1))) {
+            row.copy_from_slice(values);
+        }
+        Ok(result)
+    }
+}
+
+impl<T: Float, const N: usize> SMatrix<T, N, N> {
+    pub fn identity() -> Self {
+        let mut result = SMatrix::zeros();
+        for i in 0..N {
+            result.data[i][i] = T::one();
+        }
+        result
+    }
+
+    pub fn trace(&self) -> T {
+        (0..N).fold(T::zero(), |acc, i| acc + self.data[i][i])
+    }
+
+    pub fn is_unitary(&self, tolerance: f64) -> bool {
+        self.adjoint()
+            .dot(self)
+            .approx_eq(&Self::identity(), tolerance)
+    }
+}
+
+impl<T: Float, const N: usize> SVector<T, N> {
+    pub fn new(data: [T; N]) -> Self {
+        SMatrix {
+            data: data.map(|x| [x]),
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        N
+    }
+
+    /// Bilinear scalar product, the fixed-size counterpart of [`Vector::dot`]. ([`SMatrix::dot`]
+    /// is the matrix product.)
+    pub fn scalar_product(&self, other: &Self) -> T {
+        (0..N).fold(T::zero(), |acc, i| acc + self.data[i][0] * other.data[i][0])
+    }
+
+    pub fn norm(&self) -> T {
+        self.scalar_product(self).sqrt()
+    }
+
+    /// Matrix-vector product `M v`.
+    pub fn mul_matrix<const R: usize>(&self, matrix: &SMatrix<T, R, N>) -> SVector<T, R> {
+        matrix.dot(self)
+    }
+
+    pub fn to_column_vector(&self) -> ColumnVector<T> {
+        ColumnVector::new(self.data.iter().map(|row| row[0]).collect())
+    }
+
+    /// Copies a dynamic column vector of exactly `N` entries.
+    pub fn from_column_vector(vector: &ColumnVector<T>) -> Result<Self, MathError> {
+        if vector.size() != N {
+            return Err(MathError::DimensionMismatch {
+                left: (N, 1),
+                right: vector.shape(),
+            });
+        }
+
+        let mut result = SMatrix::zeros();
+        for (i, row) in result.data.iter_mut().enumerate() {
+            row[0] = vector.get(i);
+        }
+        Ok(result)
+    }
+}
+
+impl<T: Float, const R: usize, const C: usize> From<SMatrix<T, R, C>> for Matrix<T> {
+    fn from(matrix: SMatrix<T, R, C>) -> Self {
+        matrix.to_matrix()
+    }
+}
+
+impl<T: Float, const N: usize> From<SVector<T, N>> for ColumnVector<T> {
+    fn from(vector: SVector<T, N>) -> Self {
+        vector.to_column_vector()
+    }
+}
+
+impl<T: Float, const R: usize, const C: usize> ops::Index<(usize, usize)> for SMatrix<T, R, C> {
+    type Output = T;
+
+    fn index(&self, index: (usize, usize)) -> &Self::Output {
+        &self.data[index.0][index.1]
+    }
+}
+
+impl<T: Float, const R: usize, const C: usize> ops::IndexMut<(usize, usize)> for SMatrix<T, R, C> {
+    fn index_mut(&mut self, index: (usize, usize)) -> &mut Self::Output {
+        &mut self.data[index.0][index.1]
+    }
+}
+
+impl<T: Float, const R: usize, const C: usize> ops::Add for SMatrix<T, R, C> {
+    type Output = Self;
+
+    fn add(mut self, other: Self) -> Self {
+        for (a, b) in self
+            .data
+            .iter_mut()
+            .flatten()
+            .zip(other.data.iter().flatten())
+        {
+            *a += *b;
+        }
+        self
+    }
+}
+
+impl<T: Float, const R: usize, const C: usize> ops::Sub for SMatrix<T, R, C> {
+    type Output = Self;
+
+    fn sub(mut self, other: Self) -> Self {
+        for (a, b) in self
+            .data
+            .iter_mut()
+            .flatten()
+            .zip(other.data.iter().flatten())
+        {
+            *a -= *b;
+        }
+        self
+    }
+}
+
+impl<T: Float, const R: usize, const C: usize, const K: usize> ops::Mul<SMatrix<T, C, K>>
+    for SMatrix<T, R, C>
+{
+    type Output = SMatrix<T, R, K>;
+
+    fn mul(self, other: SMatrix<T, C, K>) -> Self::Output {
+        self.dot(&other)
+    }
+}
+
+impl<T: Float, const R: usize, const C: usize> ops::Mul<T> for SMatrix<T, R, C> {
+    type Output = Self;
+
+    fn mul(self, scalar: T) -> Self {
+        self.scale(scalar)
+    }
+}
+
+impl<T: Float + fmt::Debug, const R: usize, const C: usize> fmt::Debug for SMatrix<T, R, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SMatrix<{}, {}>{:?}", R, C, self.data)
+    }
+}
+
+impl<T: Float + fmt::Display, const R: usize, const C: usize> fmt::Display for SMatrix<T, R, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_matrix())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{complex, Complex};
+
+    #[test]
+    fn products_match_dynamic_matrices() {
+        let a = SMatrix::from_rows([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+        let b = SMatrix::from_rows([[1.0, 0.0], [0.0, 1.0], [2.0, -1.0]]);
+
+        let product: SMatrix<f64, 2, 2> = a * b;
+        assert_eq!(product.data, [[7.0, -1.0], [16.0, -1.0]]);
+        assert_eq!(
+            product.to_matrix().data,
+            a.to_matrix().dot(&b.to_matrix()).unwrap().data
+        );
+
+        let v = SVector::new([1.0, -1.0, 2.0]);
+        assert_eq!(v.mul_matrix(&a).data, [[5.0], [11.0]]);
+        assert_eq!(v.scalar_product(&v), 6.0);
+        assert_eq!(
+            ColumnVector::from(v)
+                .mul_matrix(&a.to_matrix())
+                .unwrap()
+                .get(1),
+            11.0
+        );
+    }
+
+    #[test]
+    fn kronecker_matches_dynamic_matrices() {
+        let x = SMatrix::from_rows([
+            [complex!(0.0, 0.0), complex!(1.0, 0.0)],
+            [complex!(1.0, 0.0), complex!(0.0, 0.0)],
+        ]);
+        let y = SMatrix::from_rows([
+            [complex!(0.0, 0.0), complex!(0.0, -1.0)],
+            [complex!(0.0, 1.0), complex!(0.0, 0.0)],
+        ]);
+
+        let xy: SMatrix<Complex<f64>, 4, 4> = x.kronecker(&y);
+        assert_eq!(
+            xy.to_matrix().data,
+            x.to_matrix().kronecker(&y.to_matrix()).data
+        );
+        assert!(xy.is_unitary(1e-12));
+        assert_eq!(xy.trace(), complex!(0.0, 0.0));
+    }
+
+    #[test]
+    fn conversions_check_dimensions() {
+        let dynamic: Matrix<f64> = Matrix::new(2, 2, vec![1.0, 2.0, 3.0, 4.0]);
+        let fixed = SMatrix::<f64, 2, 2>::from_matrix(&dynamic).unwrap();
+        assert_eq!(fixed.data, [[1.0, 2.0], [3.0, 4.0]]);
+        assert_eq!(Matrix::from(fixed).data, dynamic.data);
+        assert_eq!(
+            SMatrix::<f64, 2, 3>::from_matrix(&dynamic).unwrap_err(),
+            MathError::DimensionMismatch {
+                left: (2, 3),
+                right: (2, 2)
+            }
+        );
+
+        let column = ColumnVector::new(vec![1.0, 2.0]);
+        assert_eq!(
+            SVector::<f64, 2>::from_column_vector(&column).unwrap(),
+            SVector::new([1.0, 2.0])
+        );
+        assert!(SVector::<f64, 3>::from_column_vector(&column).is_err());
+    }
+}
@@ -0,0 +1,331 @@
+use super::{ColumnVector, Float, MathError, Matrix, Vector};
+
+/// Coordinate-format sparse matrix, convenient for assembling a matrix entry by entry before
+/// converting it to [`SparseMatrix`]. Duplicate entries are summed on conversion.
+#[derive(Clone, Debug)]
+pub struct CooMatrix<T: Float> {
+    pub rows: usize,
+    pub cols: usize,
+    pub entries: Vec<(usize, usize, T)>,
+}
+
+/// Compressed sparse row matrix. The non-zero values of row `i` are
+/// `values[row_offsets[i]..row_offsets[i + 1]]`, stored with strictly increasing column indices.
+#[derive(Clone, Debug)]
+pub struct SparseMatrix<T: Float> {
+    rows: usize,
+    cols: usize,
+    row_offsets: Vec<usize>,
+    column_indices: Vec<usize>,
+    values: Vec<T>,
+}
+
+impl<T: Float> CooMatrix<T> {
+    pub fn new(rows: usize, cols: usize) -> CooMatrix<T> {
+        CooMatrix {
+            rows,
+            cols,
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, row: usize, col: usize, value: T) {
+        assert!(
+            row < self.rows && col < self.cols,
+            "entry ({}, {}) is outside a {}x{} matrix",
+            row,
+            col,
+            self.rows,
+            self.cols
+        );
+        self.entries.push((row, col, value));
+    }
+
+    pub fn to_csr(&self) -> SparseMatrix<T> {
+        let mut entries = self.entries.clone();
+        entries.sort_by_key(|(row, col, _)| (*row, *col));
+
+        let mut row_offsets = vec![0; self.rows + 1];
+        let mut column_indices: Vec<usize> = Vec::with_capacity(entries.len());
+        let mut values: Vec<T> = Vec::with_capacity(entries.len());
+        let mut last: Option<(usize, usize)> = None;
+
+        for (row, col, value) in entries {
+            if last == Some((row, col)) {
+                *values.last_mut().expect("a previous entry exists") += value;
+                continue;
+            }
+
+            column_indices.push(col);
+            values.push(value);
+            row_offsets[row + 1] += 1;
+            last = Some((row, col));
+        }
+
+        for i in 0..self.rows {
+            row_offsets[i + 1] += row_offsets[i];
+        }
+
+        SparseMatrix {
+            rows: self.rows,
+            cols: self.cols,
+            row_offsets,
+            column_indices,
+            values,
+        }
+    }
+}
+
+impl<T: Float> SparseMatrix<T> {
+    pub fn zeros(rows: usize, cols: usize) -> SparseMatrix<T> {
+        SparseMatrix {
+            rows,
+            cols,
+            row_offsets: vec![0; rows + 1],
+            column_indices: Vec::new(),
+            values: Vec::new(),
+        }
+    }
+
+    pub fn identity(n: usize) -> SparseMatrix<T> {
+        SparseMatrix {
+            rows: n,
+            cols: n,
+            row_offsets: (0..=n).collect(),
+            column_indices: (0..n).collect(),
+            values: vec![T::one(); n],
+        }
+    }
+
+    pub fn from_dense(matrix: &Matrix<T>) -> SparseMatrix<T> {
+        let mut coo = CooMatrix::new(matrix.rows, matrix.cols);
+        for i in 0..matrix.rows {
+            for j in 0..matrix.cols {
+                let value = matrix.get(i, j);
+                if value != T::zero() {
+                    coo.push(i, j, value);
+                }
+            }
+        }
+        coo.to_csr()
+    }
+
+    pub fn to_dense(&self) -> Matrix<T> {
+        let mut result = Matrix::zeros(self.rows, self.cols);
+        for (row, col, value) in self.iter() {
+            result.set(row, col, value);
+        }
+        result
+    }
+
+    pub fn to_coo(&self) -> CooMatrix<T> {
+        CooMatrix {
+            rows: self.rows,
+            cols: self.cols,
+            entries: self.iter().collect(),
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Number of explicitly stored entries.
+    pub fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> T {
+        let range = self.row_offsets[row]..self.row_offsets[row + 1];
+        match self.column_indices[range.clone()].binary_search(&col) {
+            Ok(offset) => self.values[range.start + offset],
+            Err(_) => T::zero(),
+        }
+    }
+
+    /// Stored entries as `(row, col, value)` in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize, T)> + '_ {
+        (0..self.rows).flat_map(move |row| {
+            (self.row_offsets[row]..self.row_offsets[row + 1])
+                .map(move |k| (row, self.column_indices[k], self.values[k]))
+        })
+    }
+
+    pub fn mul_vector(&self, vector: &ColumnVector<T>) -> Result<ColumnVector<T>, MathError> {
+        if vector.size() != self.cols {
+            return Err(MathError::DimensionMismatch {
+                left: (self.rows, self.cols),
+                right: vector.shape(),
+            });
+        }
+
+        let mut result = ColumnVector::new(vec![T::zero(); self.rows]);
+        for row in 0..self.rows {
+            let mut sum = T::zero();
+            for k in self.row_offsets[row]..self.row_offsets[row + 1] {
+                sum += self.values[k] * vector.get(self.column_indices[k]);
+            }
+            result.set(row, sum);
+        }
+        Ok(result)
+    }
+
+    pub fn kronecker(&self, other: &Self) -> SparseMatrix<T> {
+        let rows = self.rows * other.rows;
+        let cols = self.cols * other.cols;
+        let mut row_offsets = Vec::with_capacity(rows + 1);
+        let mut column_indices = Vec::with_capacity(self.nnz() * other.nnz());
+        let mut values = Vec::with_capacity(self.nnz() * other.nnz());
+        row_offsets.push(0);
+
+        for i in 0..self.rows {
+            for k in 0..other.rows {
+                for a in self.row_offsets[i]..self.row_offsets[i + 1] {
+                    let (j, self_value) = (self.column_indices[a], self.values[a]);
+                    for b in other.row_offsets[k]..other.row_offsets[k + 1] {
+                        column_indices.push(j * other.cols + other.column_indices[b]);
+                        values.push(self_value * other.values[b]);
+                    }
+                }
+                row_offsets.push(values.len());
+            }
+        }
+
+        SparseMatrix {
+            rows,
+            cols,
+            row_offsets,
+            column_indices,
+            values,
+        }
+    }
+
+    pub fn add_to(&self, other: &Self) -> Result<SparseMatrix<T>, MathError> {
+        if self.rows != other.rows || self.cols != other.cols {
+            return Err(MathError::DimensionMismatch {
+                left: (self.rows, self.cols),
+                right: (other.rows, other.cols),
+            });
+        }
+
+        let mut coo = self.to_coo();
+        coo.entries.extend(other.iter());
+        Ok(coo.to_csr())
+    }
+
+    pub fn scale(&self, scalar: T) -> SparseMatrix<T> {
+        let mut result = self.clone();
+        for value in result.values.iter_mut() {
+            *value *= scalar;
+        }
+        result
+    }
+
+    pub fn transpose(&self) -> SparseMatrix<T> {
+        self.transposed_with(|value| value)
+    }
+
+    /// Conjugate transpose.
+    pub fn adjoint(&self) -> SparseMatrix<T> {
+        self.transposed_with(T::conj)
+    }
+
+    fn transposed_with(&self, map: impl Fn(T) -> T) -> SparseMatrix<T> {
+        let mut coo = CooMatrix::new(self.cols, self.rows);
+        coo.entries = self
+            .iter()
+            .map(|(row, col, value)| (col, row, map(value)))
+            .collect();
+        coo.to_csr()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{column_vector, complex, matrix, Complex};
+
+    fn sample() -> Matrix<Complex<f64>> {
+        matrix!([complex!(1.0, 0.0), complex!(0.0, 0.0), complex!(0.0, 2.0)];
+                [complex!(0.0, 0.0), complex!(0.0, 0.0), complex!(0.0, 0.0)];
+                [complex!(3.0, -1.0), complex!(4.0, 0.0), complex!(0.0, 0.0)])
+    }
+
+    #[test]
+    fn coo_sums_duplicates_and_sorts() {
+        let mut coo = CooMatrix::new(2, 3);
+        coo.push(1, 2, 1.0);
+        coo.push(0, 1, 2.0);
+        coo.push(1, 2, 3.0);
+        coo.push(1, 0, 5.0);
+
+        let csr = coo.to_csr();
+        assert_eq!(csr.nnz(), 3);
+        assert_eq!(
+            csr.iter().collect::<Vec<_>>(),
+            vec![(0, 1, 2.0), (1, 0, 5.0), (1, 2, 4.0)]
+        );
+        assert_eq!(csr.get(1, 1), 0.0);
+    }
+
+    #[test]
+    fn dense_round_trip() {
+        let dense = sample();
+        let sparse = SparseMatrix::from_dense(&dense);
+
+        assert_eq!(sparse.nnz(), 4);
+        assert!(sparse.to_dense().approx_eq(&dense, 0.0));
+        assert!(sparse.to_coo().to_csr().to_dense().approx_eq(&dense, 0.0));
+    }
+
+    #[test]
+    fn matches_dense_operations() {
+        let dense = sample();
+        let sparse = SparseMatrix::from_dense(&dense);
+        let vector = column_vector![complex!(1.0, 1.0), complex!(2.0, 0.0), complex!(0.0, -1.0)];
+
+        let expected = vector.mul_matrix(&dense).unwrap();
+        let product = sparse.mul_vector(&vector).unwrap();
+        for i in 0..3 {
+            assert!((product.get(i) - expected.get(i)).abs() < 1e-12);
+        }
+
+        assert!(sparse.adjoint().to_dense().approx_eq(&dense.adjoint(), 0.0));
+        assert!(sparse
+            .transpose()
+            .to_dense()
+            .approx_eq(&dense.transpose(), 0.0));
+        assert!(sparse
+            .kronecker(&sparse)
+            .to_dense()
+            .approx_eq(&dense.kronecker(&dense), 0.0));
+        assert!(sparse
+            .add_to(&sparse.adjoint())
+            .unwrap()
+            .to_dense()
+            .approx_eq(&dense.add_to(&dense.adjoint()).unwrap(), 0.0));
+        assert!(sparse
+            .scale(complex!(0.0, 2.0))
+            .to_dense()
+            .approx_eq(&dense.scale(complex!(0.0, 2.0)), 0.0));
+    }
+
+    #[test]
+    fn dimension_mismatches_are_reported() {
+        let sparse: SparseMatrix<f64> = SparseMatrix::identity(3);
+        let vector: ColumnVector<f64> = column_vector![1.0, 2.0];
+
+        assert_eq!(
+            sparse.mul_vector(&vector).unwrap_err(),
+            MathError::DimensionMismatch {
+                left: (3, 3),
+                right: (2, 1)
+            }
+        );
+        assert!(sparse.add_to(&SparseMatrix::zeros(3, 2)).is_err());
+    }
+}
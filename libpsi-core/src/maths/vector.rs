@@ -1,4 +1,4 @@
-use super::{Float, Matrix};
+use super::{parallel, Complex, Float, MathError, Matrix};
 use core::{fmt, ops};
 
 #[macro_export]
@@ -27,7 +27,12 @@ pub trait Vector<T: Float> {
     fn set(&mut self, index: usize, value: T);
     fn size(&self) -> usize;
 
-    fn dot(&self, other: &Self) -> T;
+    /// Bilinear product `Σ aᵢbᵢ`; no operand is conjugated. See [`VectorImpl::inner`] for the
+    /// inner product of complex vectors.
+    fn dot(&self, other: &Self) -> Result<T, MathError>;
+    /// [`Vector::dot`] without the length check, which is only asserted in debug builds.
+    fn dot_unchecked(&self, other: &Self) -> T;
+    /// Euclidean norm `sqrt(Σ |aᵢ|²)`, also for complex entries.
     fn norm(&self) -> T;
 
     fn max(&self) -> T;
@@ -42,56 +47,98 @@ pub trait VectorMatrix<T: Float> {
 }
 
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VectorImpl<T: Float, const ROWS: usize, const COLS: usize>(Vec<T>);
 pub type RowVector<T> = VectorImpl<T, 1, 0>;
 pub type ColumnVector<T> = VectorImpl<T, 0, 1>;
 
 impl<T: Float> ColumnVector<T> {
-    pub fn mul_matrix(&self, matrix: &Matrix<T>) -> Option<ColumnVector<T>> {
+    /// Matrix-vector product `M v`.
+    pub fn mul_matrix(&self, matrix: &Matrix<T>) -> Result<ColumnVector<T>, MathError> {
         if matrix.cols != self.size() {
-            return None;
+            return Err(MathError::DimensionMismatch {
+                left: (matrix.rows, matrix.cols),
+                right: self.shape(),
+            });
         }
 
-        let mut result = ColumnVector::new(vec![T::zero(); matrix.rows]);
+        Ok(self.mul_matrix_unchecked(matrix))
+    }
 
-        for i in 0..matrix.rows {
-            let mut sum = T::zero();
-            for j in 0..matrix.cols {
-                sum = sum + (matrix.get(i, j) * self.get(j));
+    /// [`ColumnVector::mul_matrix`] without the shape check, which is only asserted in debug
+    /// builds.
+    pub fn mul_matrix_unchecked(&self, matrix: &Matrix<T>) -> ColumnVector<T> {
+        debug_assert_eq!(matrix.cols, self.size(), "matrix-vector dimension mismatch");
+
+        const BAND: usize = 64;
+        let mut result = vec![T::zero(); matrix.rows];
+        let work = matrix.rows * matrix.cols;
+
+        parallel::for_each_chunk(&mut result, BAND, work, |band, entries| {
+            for (offset, entry) in entries.iter_mut().enumerate() {
+                let row = &matrix.data[(band * BAND + offset) * matrix.cols..][..matrix.cols];
+                *entry = row
+                    .iter()
+                    .zip(&self.0)
+                    .fold(T::zero(), |acc, (a, b)| acc + *a * *b);
             }
-            result.set(i, sum);
-        }
+        });
 
-        Some(result)
+        ColumnVector::new(result)
     }
 
     pub fn transpose(&self) -> RowVector<T> {
         RowVector::new(self.0.clone())
     }
+
+    /// The bra `⟨ψ|` of the ket `|ψ⟩`.
+    pub fn adjoint(&self) -> RowVector<T> {
+        RowVector::new(self.0.iter().map(|x| x.conj()).collect())
+    }
 }
 
 impl<T: Float> RowVector<T> {
-    pub fn mul_matrix(&self, matrix: &Matrix<T>) -> Option<RowVector<T>> {
+    /// Vector-matrix product `v M`.
+    pub fn mul_matrix(&self, matrix: &Matrix<T>) -> Result<RowVector<T>, MathError> {
         if self.size() != matrix.rows {
-            return None;
+            return Err(MathError::DimensionMismatch {
+                left: self.shape(),
+                right: (matrix.rows, matrix.cols),
+            });
         }
 
-        let mut result = RowVector::new(vec![T::zero(); matrix.cols]);
+        Ok(self.mul_matrix_unchecked(matrix))
+    }
 
-        for j in 0..matrix.cols {
-            let mut sum = T::zero();
-            for i in 0..matrix.rows {
-                sum = sum + (self.get(i) * matrix.get(i, j));
+    /// [`RowVector::mul_matrix`] without the shape check, which is only asserted in debug
+    /// builds.
+    pub fn mul_matrix_unchecked(&self, matrix: &Matrix<T>) -> RowVector<T> {
+        debug_assert_eq!(self.size(), matrix.rows, "vector-matrix dimension mismatch");
+
+        const BAND: usize = 256;
+        let mut result = vec![T::zero(); matrix.cols];
+        let work = matrix.rows * matrix.cols;
+
+        // Each band of output columns accumulates the rows of `matrix` in order.
+        parallel::for_each_chunk(&mut result, BAND, work, |band, entries| {
+            let first_col = band * BAND;
+            for (i, alpha) in self.0.iter().enumerate() {
+                let row = &matrix.data[i * matrix.cols + first_col..][..entries.len()];
+                T::mul_add(*alpha, row, entries);
             }
-            result.set(j, sum);
-        }
+        });
 
-        Some(result)
+        RowVector::new(result)
     }
 
     pub fn transpose(&self) -> ColumnVector<T> {
         ColumnVector::new(self.0.clone())
     }
+
+    /// The ket `|ψ⟩` of the bra `⟨ψ|`.
+    pub fn adjoint(&self) -> ColumnVector<T> {
+        ColumnVector::new(self.0.iter().map(|x| x.conj()).collect())
+    }
 }
 
 impl<T: Float> VectorMatrix<T> for RowVector<T> {
@@ -127,20 +174,36 @@ impl<T: Float, const ROWS: usize, const COLS: usize> Vector<T> for VectorImpl<T,
         self.0.len()
     }
 
-    fn dot(&self, other: &Self) -> T {
-        self.0
-            .iter()
-            .zip(other.0.iter())
-            .map(|(a, b)| *a * *b)
-            .fold(T::zero(), |acc, x| acc + x)
+    fn dot(&self, other: &Self) -> Result<T, MathError> {
+        if self.size() != other.size() {
+            return Err(self.mismatch(other));
+        }
+
+        Ok(self.dot_unchecked(other))
+    }
+
+    fn dot_unchecked(&self, other: &Self) -> T {
+        debug_assert_eq!(
+            self.size(),
+            other.size(),
+            "vector dot product dimension mismatch"
+        );
+
+        parallel::sum(self.size(), |range| {
+            self.0[range.clone()]
+                .iter()
+                .zip(&other.0[range])
+                .fold(T::zero(), |acc, (a, b)| acc + *a * *b)
+        })
     }
 
     fn norm(&self) -> T {
-        self.0
-            .iter()
-            .map(|x| *x * *x)
-            .fold(T::zero(), |acc, x| acc + x)
-            .sqrt()
+        parallel::sum(self.size(), |range| {
+            self.0[range]
+                .iter()
+                .fold(T::zero(), |acc, x| acc + x.conj() * *x)
+        })
+        .sqrt()
     }
 
     fn max(&self) -> T {
@@ -165,38 +228,81 @@ impl<T: Float, const ROWS: usize, const COLS: usize> Vector<T> for VectorImpl<T,
 }
 
 impl<T: Float, const ROWS: usize, const COLS: usize> VectorImpl<T, ROWS, COLS> {
-    pub fn add_to(&self, other: &Self) -> Option<VectorImpl<T, ROWS, COLS>> {
+    pub fn as_slice(&self) -> &[T] {
+        &self.0
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.0
+    }
+
+    pub fn add_to(&self, other: &Self) -> Result<VectorImpl<T, ROWS, COLS>, MathError> {
         if self.size() != other.size() {
-            return None;
+            return Err(self.mismatch(other));
         }
 
-        let mut result = VectorImpl::new(vec![T::zero(); ROWS * COLS]);
+        Ok(self.add_unchecked(other))
+    }
+
+    /// [`VectorImpl::add_to`] without the length check, which is only asserted in debug builds.
+    pub fn add_unchecked(&self, other: &Self) -> VectorImpl<T, ROWS, COLS> {
+        debug_assert_eq!(self.size(), other.size(), "vector sum dimension mismatch");
+
+        let mut result = VectorImpl::new(vec![T::zero(); self.size()]);
 
         for i in 0..self.size() {
             let sum = self.get(i) + other.get(i);
             result.set(i, sum);
         }
 
-        Some(result)
+        result
     }
 
-    pub fn subtract(&self, other: &Self) -> Option<VectorImpl<T, ROWS, COLS>> {
+    pub fn subtract(&self, other: &Self) -> Result<VectorImpl<T, ROWS, COLS>, MathError> {
         if self.size() != other.size() {
-            return None;
+            return Err(self.mismatch(other));
         }
 
-        let mut result = VectorImpl::new(vec![T::zero(); ROWS * COLS]);
+        Ok(self.subtract_unchecked(other))
+    }
+
+    /// [`VectorImpl::subtract`] without the length check, which is only asserted in debug
+    /// builds.
+    pub fn subtract_unchecked(&self, other: &Self) -> VectorImpl<T, ROWS, COLS> {
+        debug_assert_eq!(
+            self.size(),
+            other.size(),
+            "vector difference dimension mismatch"
+        );
+
+        let mut result = VectorImpl::new(vec![T::zero(); self.size()]);
 
         for i in 0..self.size() {
             let sum = self.get(i) - other.get(i);
             result.set(i, sum);
         }
 
-        Some(result)
+        result
+    }
+
+    /// `(rows, cols)` of the vector seen as a matrix.
+    pub fn shape(&self) -> (usize, usize) {
+        if ROWS == 1 {
+            (1, self.size())
+        } else {
+            (self.size(), 1)
+        }
+    }
+
+    fn mismatch(&self, other: &Self) -> MathError {
+        MathError::DimensionMismatch {
+            left: self.shape(),
+            right: other.shape(),
+        }
     }
 
     pub fn scale(&self, scalar: T) -> VectorImpl<T, ROWS, COLS> {
-        let mut result = VectorImpl::new(vec![T::zero(); ROWS * COLS]);
+        let mut result = VectorImpl::new(vec![T::zero(); self.size()]);
 
         for i in 0..self.size() {
             let product = self.get(i) * scalar;
@@ -207,6 +313,69 @@ impl<T: Float, const ROWS: usize, const COLS: usize> VectorImpl<T, ROWS, COLS> {
     }
 }
 
+impl<T: Float, const ROWS: usize, const COLS: usize> VectorImpl<Complex<T>, ROWS, COLS>
+where
+    Complex<T>: Float,
+{
+    /// Inner product `⟨self|other⟩ = Σ conj(aᵢ) bᵢ`, conjugate-linear in `self`.
+    pub fn inner(&self, other: &Self) -> Result<Complex<T>, MathError> {
+        if self.size() != other.size() {
+            return Err(self.mismatch(other));
+        }
+
+        Ok(parallel::sum(self.size(), |range| {
+            self.0[range.clone()]
+                .iter()
+                .zip(&other.0[range])
+                .fold(Complex::new(T::zero(), T::zero()), |acc, (a, b)| {
+                    acc + a.get_conjugate() * *b
+                })
+        }))
+    }
+
+    /// Squared Euclidean norm `Σ |aᵢ|²`.
+    pub fn norm_sqr(&self) -> T {
+        parallel::sum(self.size(), |range| {
+            self.0[range]
+                .iter()
+                .fold(T::zero(), |acc, x| acc + x.norm2())
+        })
+    }
+
+    /// The vector scaled to unit norm. The zero vector is returned unchanged.
+    pub fn normalize(&self) -> Self {
+        let norm = self.norm_sqr().sqrt();
+        if norm == T::zero() {
+            return self.clone();
+        }
+
+        self.scale(Complex::from(T::one() / norm))
+    }
+
+    /// Outer product `|self⟩⟨other|`, the matrix with entries `aᵢ conj(bⱼ)`.
+    pub fn outer(&self, other: &Self) -> Matrix<Complex<T>> {
+        let mut result = Matrix::zeros(self.size(), other.size());
+        for (i, a) in self.0.iter().enumerate() {
+            for (j, b) in other.0.iter().enumerate() {
+                result.set(i, j, *a * b.get_conjugate());
+            }
+        }
+        result
+    }
+
+    /// Fidelity `|⟨self|other⟩|² / (⟨self|self⟩⟨other|other⟩)` between two pure states, which
+    /// need not be normalised.
+    pub fn fidelity(&self, other: &Self) -> Result<T, MathError> {
+        let overlap = self.inner(other)?;
+        Ok(overlap.norm2() / (self.norm_sqr() * other.norm_sqr()))
+    }
+
+    /// Euclidean distance `‖self - other‖`.
+    pub fn distance(&self, other: &Self) -> Result<T, MathError> {
+        Ok(self.subtract(other)?.norm_sqr().sqrt())
+    }
+}
+
 impl<T: Float, const ROWS: usize, const COLS: usize> ops::Index<usize>
     for VectorImpl<T, ROWS, COLS>
 {
@@ -256,3 +425,108 @@ impl<T: Float + fmt::Display> fmt::Display for ColumnVector<T> {
         write!(f, "{}", self.to_matrix())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{matrix, Complex};
+
+    #[test]
+    fn elementwise_operations() {
+        let a: ColumnVector<f64> = column_vector![1.0, 2.0, 3.0];
+        let b: ColumnVector<f64> = column_vector![4.0, 5.0, 6.0];
+
+        assert_eq!((a.clone() + &b).unwrap().0, vec![5.0, 7.0, 9.0]);
+        assert_eq!((b.clone() - &a).unwrap().0, vec![3.0, 3.0, 3.0]);
+    }
+
+    #[test]
+    fn dimension_mismatches_are_reported() {
+        let column: ColumnVector<f64> = column_vector![1.0, 2.0];
+        let row: RowVector<f64> = row_vector![1.0, 2.0, 3.0];
+        let matrix = matrix!([1.0, 0.0, 0.0]; [0.0, 1.0, 0.0]);
+
+        assert_eq!(
+            column.add_to(&column_vector![1.0]).unwrap_err(),
+            MathError::DimensionMismatch {
+                left: (2, 1),
+                right: (1, 1)
+            }
+        );
+        assert_eq!(
+            column.mul_matrix(&matrix).unwrap_err(),
+            MathError::DimensionMismatch {
+                left: (2, 3),
+                right: (2, 1)
+            }
+        );
+        assert_eq!(
+            row.mul_matrix(&matrix).unwrap_err(),
+            MathError::DimensionMismatch {
+                left: (1, 3),
+                right: (2, 3)
+            }
+        );
+        assert_eq!(
+            row.mul_matrix(&matrix.transpose()).unwrap().0,
+            vec![1.0, 2.0]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "dimension mismatch")]
+    fn mul_assign_panics_on_mismatch() {
+        let mut column: ColumnVector<f64> = column_vector![1.0, 2.0];
+        column *= &matrix!([1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn complex_inner_products() {
+        let i = Complex::new(0.0, 1.0);
+        let one = Complex::new(1.0, 0.0);
+        let psi: ColumnVector<Complex<f64>> = column_vector![one, i];
+        let phi: ColumnVector<Complex<f64>> = column_vector![one, -i];
+
+        assert_eq!(psi.inner(&psi).unwrap(), Complex::new(2.0, 0.0));
+        assert_eq!(psi.inner(&phi).unwrap(), Complex::new(0.0, 0.0));
+        assert_eq!(psi.dot(&psi), Ok(Complex::new(0.0, 0.0)));
+        assert_eq!(psi.dot_unchecked(&phi), Complex::new(2.0, 0.0));
+        assert_eq!(
+            psi.dot(&column_vector![one]),
+            Err(MathError::DimensionMismatch {
+                left: (2, 1),
+                right: (1, 1)
+            })
+        );
+        assert_eq!(psi.norm(), Complex::new(2.0_f64.sqrt(), 0.0));
+        assert_eq!(psi.norm_sqr(), 2.0);
+        assert_eq!(
+            psi.adjoint().mul_matrix(&psi.to_matrix()).unwrap().get(0),
+            Complex::new(2.0, 0.0)
+        );
+
+        let normalized = psi.normalize();
+        assert!((normalized.norm_sqr() - 1.0).abs() < 1e-15);
+        assert_eq!(psi.fidelity(&phi).unwrap(), 0.0);
+        assert!((psi.fidelity(&psi.scale(i)).unwrap() - 1.0).abs() < 1e-15);
+        assert_eq!(psi.distance(&phi).unwrap(), 2.0);
+
+        let projector = normalized.outer(&normalized);
+        assert!(projector.is_hermitian(1e-15));
+        assert!(projector
+            .dot(&projector)
+            .unwrap()
+            .approx_eq(&projector, 1e-15));
+        assert!((projector.trace().unwrap() - one).abs().to_f64() < 1e-15);
+
+        let zero: ColumnVector<Complex<f64>> = column_vector![Complex::new(0.0, 0.0)];
+        assert_eq!(zero.normalize().get(0), Complex::new(0.0, 0.0));
+        assert_eq!(
+            psi.inner(&zero).unwrap_err(),
+            MathError::DimensionMismatch {
+                left: (2, 1),
+                right: (1, 1)
+            }
+        );
+    }
+}
@@ -1,11 +1,11 @@
-use super::{Float, Matrix};
+use super::{Float, MathError, Matrix};
 use crate::{ColumnVector, RowVector, VectorImpl};
 use core::ops;
 
 impl<T: Float, const ROWS: usize, const COLS: usize> ops::Add<&VectorImpl<T, ROWS, COLS>>
     for VectorImpl<T, ROWS, COLS>
 {
-    type Output = Option<VectorImpl<T, ROWS, COLS>>;
+    type Output = Result<VectorImpl<T, ROWS, COLS>, MathError>;
 
     fn add(self, other: &VectorImpl<T, ROWS, COLS>) -> Self::Output {
         self.add_to(other)
@@ -15,7 +15,7 @@ impl<T: Float, const ROWS: usize, const COLS: usize> ops::Add<&VectorImpl<T, ROW
 impl<T: Float, const ROWS: usize, const COLS: usize> ops::Sub<&VectorImpl<T, ROWS, COLS>>
     for VectorImpl<T, ROWS, COLS>
 {
-    type Output = Option<VectorImpl<T, ROWS, COLS>>;
+    type Output = Result<VectorImpl<T, ROWS, COLS>, MathError>;
 
     fn sub(self, other: &VectorImpl<T, ROWS, COLS>) -> Self::Output {
         self.subtract(other)
@@ -42,9 +42,9 @@ impl<T: Float, const ROWS: usize, const COLS: usize> ops::AddAssign<VectorImpl<T
     for VectorImpl<T, ROWS, COLS>
 {
     fn add_assign(&mut self, other: VectorImpl<T, ROWS, COLS>) {
-        if let Some(result) = self.add_to(&other) {
-            *self = result;
-        }
+        *self = self
+            .add_to(&other)
+            .unwrap_or_else(|error| panic!("{}", error));
     }
 }
 
@@ -52,9 +52,9 @@ impl<T: Float, const ROWS: usize, const COLS: usize> ops::SubAssign<VectorImpl<T
     for VectorImpl<T, ROWS, COLS>
 {
     fn sub_assign(&mut self, other: VectorImpl<T, ROWS, COLS>) {
-        if let Some(result) = self.subtract(&other) {
-            *self = result;
-        }
+        *self = self
+            .subtract(&other)
+            .unwrap_or_else(|error| panic!("{}", error));
     }
 }
 
@@ -75,7 +75,7 @@ impl<T: Float, const ROWS: usize, const COLS: usize> ops::DivAssign<T>
 }
 
 impl<T: Float> ops::Mul<&Matrix<T>> for RowVector<T> {
-    type Output = Option<RowVector<T>>;
+    type Output = Result<RowVector<T>, MathError>;
 
     fn mul(self, matrix: &Matrix<T>) -> Self::Output {
         self.mul_matrix(matrix)
@@ -83,7 +83,7 @@ impl<T: Float> ops::Mul<&Matrix<T>> for RowVector<T> {
 }
 
 impl<T: Float> ops::Mul<&Matrix<T>> for ColumnVector<T> {
-    type Output = Option<ColumnVector<T>>;
+    type Output = Result<ColumnVector<T>, MathError>;
 
     fn mul(self, matrix: &Matrix<T>) -> Self::Output {
         self.mul_matrix(matrix)
@@ -92,16 +92,16 @@ impl<T: Float> ops::Mul<&Matrix<T>> for ColumnVector<T> {
 
 impl<T: Float> ops::MulAssign<&Matrix<T>> for RowVector<T> {
     fn mul_assign(&mut self, matrix: &Matrix<T>) {
-        if let Some(result) = self.mul_matrix(matrix) {
-            *self = result;
-        }
+        *self = self
+            .mul_matrix(matrix)
+            .unwrap_or_else(|error| panic!("{}", error));
     }
 }
 
 impl<T: Float> ops::MulAssign<&Matrix<T>> for ColumnVector<T> {
     fn mul_assign(&mut self, matrix: &Matrix<T>) {
-        if let Some(result) = self.mul_matrix(matrix) {
-            *self = result;
-        }
+        *self = self
+            .mul_matrix(matrix)
+            .unwrap_or_else(|error| panic!("{}", error));
     }
 }
@@ -0,0 +1,55 @@
+//! Seeded random fixtures and reference kernels shared by the unit tests and the benchmarks.
+//! Every random helper takes the generator first, followed by the dimensions of the result.
+
+use crate::{ColumnVector, Complex, Matrix, QuantumState, Vector};
+use rand::{rngs::StdRng, Rng};
+
+/// Matrix with real entries drawn uniformly from `[-1, 1)`.
+pub fn random_real_matrix(rng: &mut StdRng, rows: usize, cols: usize) -> Matrix<f64> {
+    Matrix::new(
+        rows,
+        cols,
+        (0..rows * cols).map(|_| rng.gen_range(-1.0..1.0)).collect(),
+    )
+}
+
+/// Matrix whose real and imaginary parts are drawn uniformly from `[-1, 1)`.
+pub fn random_matrix(rng: &mut StdRng, rows: usize, cols: usize) -> Matrix<Complex<f64>> {
+    Matrix::new(
+        rows,
+        cols,
+        (0..rows * cols).map(|_| random_complex(rng)).collect(),
+    )
+}
+
+/// Hermitian matrix `A + A†` for an `A` drawn like [`random_matrix`].
+pub fn random_hermitian(rng: &mut StdRng, n: usize) -> Matrix<Complex<f64>> {
+    let a = random_matrix(rng, n, n);
+    a.add_to(&a.adjoint()).unwrap()
+}
+
+/// Normalised state of `qubits` qubits with amplitudes drawn like [`random_matrix`].
+pub fn random_state(rng: &mut StdRng, qubits: usize) -> QuantumState {
+    let amplitudes = (0..1 << qubits).map(|_| random_complex(rng)).collect();
+    ColumnVector::new(amplitudes).normalize()
+}
+
+/// The triple loop product, the reference the optimised kernels are checked and measured
+/// against.
+pub fn naive_dot(a: &Matrix<Complex<f64>>, b: &Matrix<Complex<f64>>) -> Matrix<Complex<f64>> {
+    let mut result = Matrix::zeros(a.rows, b.cols);
+    for i in 0..a.rows {
+        for j in 0..b.cols {
+            let mut sum = Complex::new(0.0, 0.0);
+            for k in 0..a.cols {
+                sum += a.get(i, k) * b.get(k, j);
+            }
+            result.set(i, j, sum);
+        }
+    }
+    result
+}
+
+fn random_complex(rng: &mut StdRng) -> Complex<f64> {
+    Complex::new(rng.gen_range(-1.0..1.0), rng.gen_range(-1.0..1.0))
+}
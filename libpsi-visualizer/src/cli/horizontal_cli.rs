@@ -19,13 +19,13 @@ use libpsi_core::QuantumCircuit;
 
 #[allow(unused)]
 pub struct HorizontalCLIVisualizer<'a> {
-    circuit: &'a QuantumCircuit<'a>,
+    circuit: &'a QuantumCircuit,
     renderer: CLIRenderer,
 }
 
 impl<'a> fmt::Display for HorizontalCLIVisualizer<'a> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.renderer.to_string())
+        write!(f, "{}", self.renderer)
     }
 }
 
@@ -1,3 +1,5 @@
+use core::fmt;
+
 #[derive(Clone, Copy)]
 pub struct Quad {
     pub x: usize,
@@ -21,6 +23,17 @@ pub struct CLIRenderer {
     buffer: Vec<Vec<char>>,
 }
 
+impl fmt::Display for CLIRenderer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lines: Vec<String> = self
+            .buffer
+            .iter()
+            .map(|inner| inner.iter().collect::<String>())
+            .collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
 impl CLIRenderer {
     pub fn new(width: usize) -> CLIRenderer {
         CLIRenderer {
@@ -32,7 +45,7 @@ impl CLIRenderer {
         if y >= self.buffer.len() {
             self.buffer.resize(
                 y + 1,
-                vec![' '; self.buffer.get(0).map_or(0, |row| row.len())],
+                vec![' '; self.buffer.first().map_or(0, |row| row.len())],
             );
         }
 
@@ -41,15 +54,6 @@ impl CLIRenderer {
         }
     }
 
-    pub fn to_string(&self) -> String {
-        self.buffer
-            .clone()
-            .into_iter()
-            .map(|inner| inner.into_iter().collect::<String>())
-            .collect::<Vec<String>>()
-            .join("\n")
-    }
-
     pub fn place(&mut self, x: usize, y: usize, character: char) {
         self.expand(x, y);
         self.buffer[y][x] = character;